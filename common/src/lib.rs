@@ -1,15 +1,18 @@
 use std::collections::HashMap;
 use std::fs::File;
+use std::hash::Hash;
 use std::io::Error;
 use std::num::ParseIntError;
 use std::ops::Deref;
+use std::path::Path;
 use std::sync::OnceLock;
 use std::{fs, io};
 
 use anyhow::{anyhow, Result};
-use chrono::{DateTime, Duration, NaiveDate, NaiveTime, Utc};
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, NaiveTime, Utc};
 use encoding_rs::UTF_16LE;
 use evtx::{EvtxParser, ParserSettings};
+use nt_hive::KeyNode;
 use serde::{Deserialize, Serialize};
 
 // Types:
@@ -183,6 +186,118 @@ pub fn convert_win_time(wintime: u64) -> DateTime<Utc> {
     date + Duration::microseconds((wintime / 10) as i64)
 }
 
+///converts wintime to UTC, returns None if the timestamp was never set
+pub fn convert_win_time_opt(wintime: u64) -> Option<DateTime<Utc>> {
+    if wintime == 0 || wintime > i64::MAX as u64 {
+        return None;
+    }
+    Some(convert_win_time(wintime))
+}
+
+///converts unix time (seconds since 1970) to UTC, returns None if the timestamp was never set
+pub fn convert_unix_time(seconds: i64) -> Option<DateTime<Utc>> {
+    if seconds <= 0 {
+        return None;
+    }
+    DateTime::from_timestamp(seconds, 0)
+}
+
+///converts unix time in microseconds (PRTime, used by Firefox) to UTC, returns None if the timestamp was never set
+pub fn convert_unix_time_micros(micros: i64) -> Option<DateTime<Utc>> {
+    if micros <= 0 {
+        return None;
+    }
+    DateTime::from_timestamp(micros / 1_000_000, (micros % 1_000_000 * 1_000) as u32)
+}
+
+///converts webkit time (microseconds since 1601, used by Chromium browsers) to UTC, returns None if the timestamp was never set
+pub fn convert_webkit_time(webkit: i64) -> Option<DateTime<Utc>> {
+    if webkit <= 0 {
+        return None;
+    }
+    convert_win_time_opt((webkit as u64).checked_mul(10)?)
+}
+
+///converts a FAT/DOS date and time (as used in shell items) to NaiveDateTime
+pub fn convert_dos_time(date: u16, time: u16) -> Option<NaiveDateTime> {
+    let day = (date & 0x1F) as u32;
+    let month = ((date >> 5) & 0x0F) as u32;
+    let year = ((date >> 9) & 0x7F) as i32 + 1980;
+    let second = ((time & 0x1F) * 2) as u32;
+    let minute = ((time >> 5) & 0x3F) as u32;
+    let hour = ((time >> 11) & 0x1F) as u32;
+    NaiveDate::from_ymd_opt(year, month, day)?.and_hms_opt(hour, minute, second)
+}
+
+///converts a 16 byte SYSTEMTIME (year, month, day of week, day, hour, minute, second, milliseconds as u16) to NaiveDateTime
+pub fn convert_systemtime(buf: &[u8]) -> Option<NaiveDateTime> {
+    let field = |index: usize| read_u16_le(buf, index * 2).map(u32::from);
+    NaiveDate::from_ymd_opt(field(0)? as i32, field(1)?, field(3)?)?.and_hms_milli_opt(
+        field(4)?,
+        field(5)?,
+        field(6)?,
+        field(7)?,
+    )
+}
+
+// reading binary data
+///reads a little endian u16 at offset
+pub fn read_u16_le(buf: &[u8], offset: usize) -> Option<u16> {
+    let bytes = buf.get(offset..offset.checked_add(2)?)?;
+    Some(u16::from_le_bytes([bytes[0], bytes[1]]))
+}
+
+///reads a little endian u32 at offset
+pub fn read_u32_le(buf: &[u8], offset: usize) -> Option<u32> {
+    let bytes = buf.get(offset..offset.checked_add(4)?)?;
+    Some(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+///reads a little endian u64 at offset
+pub fn read_u64_le(buf: &[u8], offset: usize) -> Option<u64> {
+    let bytes = buf.get(offset..offset.checked_add(8)?)?;
+    let mut raw = [0u8; 8];
+    raw.copy_from_slice(bytes);
+    Some(u64::from_le_bytes(raw))
+}
+
+///reads a null terminated UTF-16LE string starting at offset
+pub fn read_utf16_string(buf: &[u8], offset: usize) -> Option<String> {
+    let raw = buf.get(offset..)?;
+    let chars: Vec<u16> = raw
+        .chunks_exact(2)
+        .map(|c| u16::from_le_bytes([c[0], c[1]]))
+        .take_while(|c| *c != 0)
+        .collect();
+    Some(String::from_utf16_lossy(&chars))
+}
+
+///reads a null terminated (extended) ascii string starting at offset
+pub fn read_ascii_string(buf: &[u8], offset: usize) -> Option<String> {
+    let raw = buf.get(offset..)?;
+    let length = raw.iter().position(|b| *b == 0).unwrap_or(raw.len());
+    read_extended_ascii(buf, offset, length)
+}
+
+///formats 16 bytes at offset as a GUID in registry notation, e.g. {20D04FE0-3AEA-1069-A2D8-08002B30309D}
+pub fn format_guid(buf: &[u8], offset: usize) -> Option<String> {
+    let raw = buf.get(offset..offset.checked_add(16)?)?;
+    Some(format!(
+        "{{{:08X}-{:04X}-{:04X}-{:02X}{:02X}-{:02X}{:02X}{:02X}{:02X}{:02X}{:02X}}}",
+        read_u32_le(raw, 0)?,
+        read_u16_le(raw, 4)?,
+        read_u16_le(raw, 6)?,
+        raw[8],
+        raw[9],
+        raw[10],
+        raw[11],
+        raw[12],
+        raw[13],
+        raw[14],
+        raw[15]
+    ))
+}
+
 // find needed files
 ///takes path of mounted filesystem and finds the system hive file (SYSTEM)
 pub fn find_system_hive(mnt_image_path: &str) -> Result<String, Error> {
@@ -193,6 +308,10 @@ pub fn find_system_hive(mnt_image_path: &str) -> Result<String, Error> {
 pub fn find_software_hive(mnt_image_path: &str) -> Result<String, Error> {
     Ok(mnt_image_path.to_owned() + "/Windows/System32/config/SOFTWARE")
 }
+///takes path of mounted filesystem and finds the sam hive file (SAM)
+pub fn find_sam_hive(mnt_image_path: &str) -> Result<String, Error> {
+    Ok(mnt_image_path.to_owned() + "/Windows/System32/config/SAM")
+}
 ///takes path of mounted filesystem and finds the system evtx file (System.evtx)
 pub fn find_system_evtx(mnt_image_path: &str) -> Result<String, Error> {
     Ok(mnt_image_path.to_owned() + "/Windows/System32/winevt/Logs/System.evtx")
@@ -201,11 +320,109 @@ pub fn find_system_evtx(mnt_image_path: &str) -> Result<String, Error> {
 pub fn find_security_evtx(mnt_image_path: &str) -> Result<String, Error> {
     Ok(mnt_image_path.to_owned() + "/Windows/System32/winevt/Logs/Security.evtx")
 }
+///takes path of mounted filesystem and finds the office alerts evtx file (OAlerts.evtx)
+///OAlerts.evtx only exists if MS Office is installed
+pub fn find_oalerts_evtx(mnt_image_path: &str) -> Result<String, Error> {
+    let path = mnt_image_path.to_owned() + "/Windows/System32/winevt/Logs/OAlerts.evtx";
+    if !fs::metadata(&path).is_ok_and(|metadata| metadata.is_file()) {
+        return Err(Error::new(
+            io::ErrorKind::NotFound,
+            format!("{path} does not exist!"),
+        ));
+    }
+    Ok(path)
+}
+///resolves a relative path (separated by "/") below base without regarding case, as NTFS is case insensitive but the mounted image may not be
+pub fn find_file_case_insensitive(base: &str, relative: &str) -> Option<String> {
+    let mut current = base.to_owned();
+    for component in relative.split('/').filter(|c| !c.is_empty()) {
+        let exact = format!("{current}/{component}");
+        if fs::metadata(&exact).is_ok() {
+            current = exact;
+            continue;
+        }
+        let entry = fs::read_dir(&current).ok()?.flatten().find(|entry| {
+            entry
+                .file_name()
+                .to_string_lossy()
+                .eq_ignore_ascii_case(component)
+        })?;
+        current = format!("{current}/{}", entry.file_name().to_string_lossy());
+    }
+    Some(current)
+}
+///takes path of mounted filesystem and finds all user profile folders (Users/* and XP's Documents and Settings/*)
+pub fn find_user_profiles(mnt_image_path: &str) -> Result<Vec<String>, Error> {
+    let mut profiles = Vec::new();
+    for base in ["Users", "Documents and Settings"] {
+        let base_path = format!("{mnt_image_path}/{base}");
+        let Ok(entries) = fs::read_dir(&base_path) else {
+            continue;
+        };
+        for entry in entries {
+            let entry = entry?;
+            // junctions like "All Users" or "Default User" are skipped, they point to other profiles
+            if entry.file_type()?.is_dir() {
+                profiles.push(format!(
+                    "{base_path}/{}",
+                    entry.file_name().to_string_lossy()
+                ));
+            }
+        }
+    }
+    if profiles.is_empty() {
+        return Err(Error::new(
+            io::ErrorKind::NotFound,
+            "No user profiles found!",
+        ));
+    }
+    Ok(profiles)
+}
+
+///name of the last path component (e.g. the user profile name of a profile folder), empty if there is none
+pub fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+///name of a numeric value (index into names), unknown values are kept as number
+pub fn lookup(names: &[&str], value: i64) -> String {
+    usize::try_from(value)
+        .ok()
+        .and_then(|i| names.get(i))
+        .map(|name| name.to_string())
+        .unwrap_or_else(|| value.to_string())
+}
+///REG_SZ or REG_EXPAND_SZ value of a key, None if it is missing or empty
+pub fn string_value(key: &KeyNode<&[u8]>, name: &str) -> Option<String> {
+    key.value(name)
+        .and_then(|v| v.ok())
+        .and_then(|v| v.string_data().ok())
+        .filter(|s| !s.is_empty())
+}
+///names from the root to the node, the nodes map to their parent and name (parent and child ids of a database)
+pub fn node_path<'a, K: Eq + Hash>(
+    nodes: &'a HashMap<K, (Option<K>, String)>,
+    key: &K,
+) -> Vec<&'a str> {
+    let mut names = Vec::new();
+    let mut current = Some(key);
+    // guards against loops in damaged databases
+    for _ in 0..64 {
+        let Some((parent, name)) = current.and_then(|k| nodes.get(k)) else {
+            break;
+        };
+        names.push(name.as_str());
+        current = parent.as_ref();
+    }
+    names.reverse();
+    names
+}
 
 pub fn parse_evtx(input: &str) -> Result<EvtxParser<File>, Error> {
     let settings = ParserSettings::default().separate_json_attributes(true);
     let parser = EvtxParser::from_path(input)
-        .unwrap()
+        .map_err(|err| Error::other(format!("{input}: {err}")))?
         .with_configuration(settings);
     Ok(parser)
 }
@@ -0,0 +1,311 @@
+// SAM, SOFTWARE, SYSTEM and NTUSER.DAT
+// "Microsoft 365 and Microsoft accounts can be used to log on to Windows, the cloud account is linked to a local account." - SANS Windows Forensic Analysis Poster, Cloud Account Details
+// SAM\Domains\Account\Users\<RID>: InternetUserName, InternetProviderName, InternetUID, GivenName and Surname (REG_BINARY, UTF-16LE)
+// SOFTWARE\Microsoft\IdentityStore\Cache\<SID>\IdentityCache\<SID>: UserName, ProviderName, DisplayName
+// SOFTWARE\Microsoft\IdentityCRL\UserExtendedProperties\<account>: cid of Microsoft accounts
+// NTUSER.DAT\Software\Microsoft\IdentityCRL\StoredIdentities\<account>: Microsoft accounts used by the user
+// SYSTEM\ControlSet001\Control\CloudDomainJoin\JoinInfo\<thumbprint>: TenantId, UserEmail, IdpDomain of the Entra ID (Azure AD) join
+// SYSTEM\ControlSet001\Control\CloudDomainJoin\TenantInfo\<tenant id>: DisplayName of the tenant
+// the cloud accounts are merged by SID into reg_useraccounts.json (see user_accounts.rs),
+// sources without SID are linked by the cloud account name
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+
+use anyhow::anyhow;
+use common::{find_software_hive, find_system_hive, string_value};
+use log::error;
+use nt_hive::{Hive, KeyNode};
+use serde::Serialize;
+
+use crate::account_usage::registry::sam_users::{utf16_value, SamUser};
+use crate::account_usage::registry::user_accounts::UserHive;
+
+const IDENTITY_STORE_CACHE: &str = "Microsoft\\IdentityStore\\Cache";
+const USER_EXTENDED_PROPERTIES: &str = "Microsoft\\IdentityCRL\\UserExtendedProperties";
+const STORED_IDENTITIES: &str = "Software\\Microsoft\\IdentityCRL\\StoredIdentities";
+const CLOUD_DOMAIN_JOIN: &str = "ControlSet001\\Control\\CloudDomainJoin";
+const MICROSOFT_ACCOUNT: &str = "Microsoft Account";
+const ENTRA_ID: &str = "Entra ID (Azure AD)";
+
+/// the Microsoft or Entra ID account a local account signs in with
+#[derive(Debug, Default, Serialize)]
+pub struct CloudAccount {
+    pub cloud_account_sources: Vec<String>,
+    pub provider: Option<String>,
+    pub cloud_account: Option<String>,
+    pub cloud_id: Option<String>,
+    pub display_name: Option<String>,
+    pub given_name: Option<String>,
+    pub surname: Option<String>,
+    pub tenant_id: Option<String>,
+    pub tenant_name: Option<String>,
+}
+
+impl CloudAccount {
+    fn new(source: &str) -> Self {
+        CloudAccount {
+            cloud_account_sources: vec![source.to_string()],
+            ..Default::default()
+        }
+    }
+
+    /// fills the missing values from another source of the same account
+    fn merge(&mut self, other: CloudAccount) {
+        for source in other.cloud_account_sources {
+            if !self.cloud_account_sources.contains(&source) {
+                self.cloud_account_sources.push(source);
+            }
+        }
+        self.provider = self.provider.take().or(other.provider);
+        self.cloud_account = self.cloud_account.take().or(other.cloud_account);
+        self.cloud_id = self.cloud_id.take().or(other.cloud_id);
+        self.display_name = self.display_name.take().or(other.display_name);
+        self.given_name = self.given_name.take().or(other.given_name);
+        self.surname = self.surname.take().or(other.surname);
+        self.tenant_id = self.tenant_id.take().or(other.tenant_id);
+        self.tenant_name = self.tenant_name.take().or(other.tenant_name);
+    }
+
+    fn account_name(&self) -> Option<String> {
+        self.cloud_account.as_ref().map(|name| name.to_lowercase())
+    }
+}
+
+/// cloud accounts by SID and the cloud accounts that can not be linked to a SID
+pub fn read_cloud_accounts(
+    input: &str,
+    sam_users: &[SamUser],
+    user_hives: &[UserHive],
+) -> (HashMap<String, CloudAccount>, Vec<CloudAccount>) {
+    let mut found: Vec<(Option<String>, CloudAccount)> = get_sam_accounts(sam_users);
+    match find_software_hive(input) {
+        Ok(path) => {
+            if let Err(err) = get_software_accounts(&path, &mut found) {
+                error!("Failed to get cloud accounts of {path}: {err}")
+            }
+        }
+        Err(err) => error!("Could not find Software hive: {err}"),
+    }
+    for user_hive in user_hives {
+        if let Err(err) = get_stored_identities(user_hive, &mut found) {
+            error!("Failed to get StoredIdentities of {}: {err}", user_hive.sid)
+        }
+    }
+    match find_system_hive(input) {
+        Ok(path) => {
+            if let Err(err) = get_cloud_domain_join(&path, &mut found) {
+                error!("Failed to get CloudDomainJoin of {path}: {err}")
+            }
+        }
+        Err(err) => error!("Could not find System hive: {err}"),
+    }
+    link_accounts(found)
+}
+
+/// local users with an InternetUserName
+fn get_sam_accounts(sam_users: &[SamUser]) -> Vec<(Option<String>, CloudAccount)> {
+    let mut found = Vec::new();
+    for user in sam_users {
+        let text = |name: &str| {
+            user.values
+                .get(name)
+                .map(|data| utf16_value(data))
+                .filter(|t| !t.is_empty())
+        };
+        let Some(cloud_account) = text("InternetUserName") else {
+            continue;
+        };
+        let mut account = CloudAccount::new("SAM");
+        account.provider = text("InternetProviderName").or(Some(MICROSOFT_ACCOUNT.to_string()));
+        account.cloud_account = Some(cloud_account);
+        account.cloud_id = text("InternetUID");
+        account.given_name = text("GivenName");
+        account.surname = text("Surname");
+        found.push((user.sid.clone(), account));
+    }
+    found
+}
+
+/// IdentityStore and IdentityCRL, both are read from the same SOFTWARE hive
+fn get_software_accounts(
+    software_path: &str,
+    found: &mut Vec<(Option<String>, CloudAccount)>,
+) -> anyhow::Result<()> {
+    let mut buffer = Vec::new();
+    File::open(software_path)?.read_to_end(&mut buffer)?;
+    let hive = Hive::without_validation(buffer.as_ref())?;
+    let root_key_node = hive.root_key_node()?;
+    if let Err(err) = get_identity_store(&root_key_node, found) {
+        error!("Failed to get IdentityStore of {software_path}: {err}")
+    }
+    if let Err(err) = get_user_extended_properties(&root_key_node, found) {
+        error!("Failed to get IdentityCRL of {software_path}: {err}")
+    }
+    Ok(())
+}
+
+fn get_identity_store(
+    root_key_node: &KeyNode<&[u8]>,
+    found: &mut Vec<(Option<String>, CloudAccount)>,
+) -> anyhow::Result<()> {
+    let cache_key = root_key_node
+        .subpath(IDENTITY_STORE_CACHE)
+        .ok_or(anyhow!("Key '{IDENTITY_STORE_CACHE}' can not be found!"))??;
+
+    let provider_keys = cache_key
+        .subkeys()
+        .ok_or(anyhow!("Subkeys can not be unwrapped!"))??;
+    for provider_key in provider_keys {
+        let provider_key = provider_key?;
+        let Some(Ok(identity_cache)) = provider_key.subkey("IdentityCache") else {
+            continue;
+        };
+        let Some(identity_keys) = identity_cache.subkeys() else {
+            continue;
+        };
+        for identity_key in identity_keys? {
+            let identity_key = identity_key?;
+            let text = |name: &str| string_value(&identity_key, name);
+            let mut account = CloudAccount::new("IdentityStore");
+            account.provider = text("ProviderName");
+            account.cloud_account = text("UserName");
+            account.display_name = text("DisplayName");
+            found.push((Some(identity_key.name()?.to_string()), account));
+        }
+    }
+    Ok(())
+}
+
+fn get_user_extended_properties(
+    root_key_node: &KeyNode<&[u8]>,
+    found: &mut Vec<(Option<String>, CloudAccount)>,
+) -> anyhow::Result<()> {
+    let properties_key = root_key_node
+        .subpath(USER_EXTENDED_PROPERTIES)
+        .ok_or(anyhow!(
+            "Key '{USER_EXTENDED_PROPERTIES}' can not be found!"
+        ))??;
+
+    let account_keys = properties_key
+        .subkeys()
+        .ok_or(anyhow!("Subkeys can not be unwrapped!"))??;
+    for account_key in account_keys {
+        let account_key = account_key?;
+        let mut account = CloudAccount::new("IdentityCRL");
+        account.provider = Some(MICROSOFT_ACCOUNT.to_string());
+        account.cloud_account = Some(account_key.name()?.to_string());
+        account.cloud_id = string_value(&account_key, "cid");
+        found.push((None, account));
+    }
+    Ok(())
+}
+
+fn get_stored_identities(
+    user_hive: &UserHive,
+    found: &mut Vec<(Option<String>, CloudAccount)>,
+) -> anyhow::Result<()> {
+    let mut buffer = Vec::new();
+    File::open(&user_hive.hive_path)?.read_to_end(&mut buffer)?;
+    let hive = Hive::without_validation(buffer.as_ref())?;
+    let root_key_node = hive.root_key_node()?;
+    let identities_key = root_key_node
+        .subpath(STORED_IDENTITIES)
+        .ok_or(anyhow!("Key '{STORED_IDENTITIES}' can not be found!"))??;
+
+    let account_keys = identities_key
+        .subkeys()
+        .ok_or(anyhow!("Subkeys can not be unwrapped!"))??;
+    for account_key in account_keys {
+        let account_key = account_key?;
+        let mut account = CloudAccount::new("IdentityCRL (NTUSER.DAT)");
+        account.provider = Some(MICROSOFT_ACCOUNT.to_string());
+        account.cloud_account = Some(account_key.name()?.to_string());
+        found.push((Some(user_hive.sid.clone()), account));
+    }
+    Ok(())
+}
+
+fn get_cloud_domain_join(
+    system_path: &str,
+    found: &mut Vec<(Option<String>, CloudAccount)>,
+) -> anyhow::Result<()> {
+    let mut buffer = Vec::new();
+    File::open(system_path)?.read_to_end(&mut buffer)?;
+    let hive = Hive::without_validation(buffer.as_ref())?;
+    let root_key_node = hive.root_key_node()?;
+    let join_key = root_key_node
+        .subpath(CLOUD_DOMAIN_JOIN)
+        .ok_or(anyhow!("Key '{CLOUD_DOMAIN_JOIN}' can not be found!"))??;
+
+    // tenant names by tenant id
+    let mut tenant_names: HashMap<String, String> = HashMap::new();
+    if let Some(Ok(tenant_info)) = join_key.subkey("TenantInfo") {
+        if let Some(tenant_keys) = tenant_info.subkeys() {
+            for tenant_key in tenant_keys? {
+                let tenant_key = tenant_key?;
+                if let Some(name) = string_value(&tenant_key, "DisplayName") {
+                    tenant_names.insert(tenant_key.name()?.to_string().to_lowercase(), name);
+                }
+            }
+        }
+    }
+
+    let join_info = join_key.subkey("JoinInfo").ok_or(anyhow!(
+        "Key '{CLOUD_DOMAIN_JOIN}\\JoinInfo' can not be found!"
+    ))??;
+    let join_keys = join_info
+        .subkeys()
+        .ok_or(anyhow!("Subkeys can not be unwrapped!"))??;
+    for join_key in join_keys {
+        let join_key = join_key?;
+        let mut account = CloudAccount::new("CloudDomainJoin");
+        account.provider = Some(ENTRA_ID.to_string());
+        account.cloud_account = string_value(&join_key, "UserEmail");
+        account.tenant_id = string_value(&join_key, "TenantId");
+        account.tenant_name = account
+            .tenant_id
+            .as_ref()
+            .and_then(|id| tenant_names.get(&id.to_lowercase()).cloned())
+            .or_else(|| string_value(&join_key, "IdpDomain"));
+        found.push((None, account));
+    }
+    Ok(())
+}
+
+/// merges the sources by SID, sources without SID get the SID of another source with the same cloud account
+fn link_accounts(
+    found: Vec<(Option<String>, CloudAccount)>,
+) -> (HashMap<String, CloudAccount>, Vec<CloudAccount>) {
+    let sids: HashMap<String, String> = found
+        .iter()
+        .filter_map(|(sid, account)| Some((account.account_name()?, sid.clone()?)))
+        .collect();
+
+    let mut linked: HashMap<String, CloudAccount> = HashMap::new();
+    // unlinked accounts by cloud account name, in the order they were found
+    let mut unlinked: Vec<CloudAccount> = Vec::new();
+    for (sid, account) in found {
+        let sid = sid.or_else(|| sids.get(&account.account_name()?).cloned());
+        match sid {
+            Some(sid) => match linked.get_mut(&sid) {
+                Some(existing) => existing.merge(account),
+                None => {
+                    linked.insert(sid, account);
+                }
+            },
+            None => {
+                let existing = unlinked.iter_mut().find(|existing| {
+                    existing.account_name().is_some()
+                        && existing.account_name() == account.account_name()
+                });
+                match existing {
+                    Some(existing) => existing.merge(account),
+                    None => unlinked.push(account),
+                }
+            }
+        }
+    }
+    (linked, unlinked)
+}
@@ -0,0 +1,110 @@
+// SAM
+// "The SAM registry file lists the local accounts of the system, including the last login, the last password change, the login count and the group membership of each account." - SANS Windows Forensic Analysis Poster, Last Login and Password Change
+// SAM\Domains\Account\Users\<RID>\F: FILETIMEs of the last logon (0x08), password last set (0x18), account expiry (0x20) and last failed logon (0x28),
+// account control flags (0x38), invalid password count (0x40) and logon count (0x42)
+// SAM\Domains\Account\Users\<RID>\V: user name, full name and comment
+// the group memberships come from SAM\Domains\<Builtin|Account>\Aliases
+// the accounts are merged with the ProfileList (SOFTWARE) by SID into reg_useraccounts.json, see user_accounts.rs
+
+use chrono::{DateTime, Utc};
+use common::{convert_win_time_opt, read_u16_le, read_u64_le};
+use log::error;
+use serde::Serialize;
+
+use crate::account_usage::registry::sam_users::{
+    read_sam_groups, v_string, SamUser, V_COMMENT, V_FULL_NAME,
+};
+
+// account control flags of the F record
+const ACCOUNT_FLAGS: [(u16, &str); 16] = [
+    (0x0001, "Account Disabled"),
+    (0x0002, "Home Directory Required"),
+    (0x0004, "Password Not Required"),
+    (0x0008, "Temporary Duplicate Account"),
+    (0x0010, "Normal User Account"),
+    (0x0020, "MNS Logon Account"),
+    (0x0040, "Interdomain Trust Account"),
+    (0x0080, "Workstation Trust Account"),
+    (0x0100, "Server Trust Account"),
+    (0x0200, "Password Does Not Expire"),
+    (0x0400, "Account Locked Out"),
+    (0x0800, "Encrypted Text Password Allowed"),
+    (0x1000, "Smartcard Required"),
+    (0x2000, "Trusted For Delegation"),
+    (0x4000, "Not Delegated"),
+    (0x8000, "Use DES Key Only"),
+];
+// account expiry of accounts that never expire
+const NEVER_EXPIRES: u64 = 0x7FFF_FFFF_FFFF_FFFF;
+
+/// decoded F and V record and the groups of a local account
+#[derive(Debug, Default, Serialize)]
+pub struct SamAccount {
+    // the SID is the key of the account view
+    #[serde(skip)]
+    pub sid: Option<String>,
+    pub rid: Option<u32>,
+    pub user_name: Option<String>,
+    pub full_name: Option<String>,
+    pub comment: Option<String>,
+    pub account_flags: Vec<String>,
+    pub last_logon: Option<DateTime<Utc>>,
+    pub password_last_set: Option<DateTime<Utc>>,
+    pub last_failed_logon: Option<DateTime<Utc>>,
+    pub account_expires: Option<DateTime<Utc>>,
+    pub logon_count: Option<u16>,
+    pub invalid_password_count: Option<u16>,
+    pub groups: Vec<String>,
+    pub sam_last_written: Option<DateTime<Utc>>,
+}
+
+/// decodes the local accounts read from the SAM hive, the groups are read from the same hive
+pub fn read_sam_accounts(sam_path: &str, users: &[SamUser]) -> Vec<SamAccount> {
+    let groups = read_sam_groups(sam_path).unwrap_or_else(|err| {
+        error!("Failed to get groups of {sam_path}: {err}");
+        Vec::new()
+    });
+
+    let mut account_list: Vec<SamAccount> = Vec::new();
+    for user in users {
+        let f = user.values.get("F").map(Vec::as_slice).unwrap_or_default();
+        let v = user.values.get("V").map(Vec::as_slice).unwrap_or_default();
+        let time = |offset: usize| read_u64_le(f, offset).and_then(convert_win_time_opt);
+        let flags = read_u16_le(f, 0x38).unwrap_or_default();
+        let account_expires = read_u64_le(f, 0x20)
+            .filter(|expires| *expires != NEVER_EXPIRES)
+            .and_then(convert_win_time_opt);
+        let groups = user
+            .sid
+            .as_ref()
+            .map(|sid| {
+                groups
+                    .iter()
+                    .filter(|group| group.members.contains(sid))
+                    .map(|group| group.name.clone())
+                    .collect()
+            })
+            .unwrap_or_default();
+        account_list.push(SamAccount {
+            sid: user.sid.clone(),
+            rid: Some(user.rid),
+            user_name: user.user_name.clone(),
+            full_name: v_string(v, V_FULL_NAME),
+            comment: v_string(v, V_COMMENT),
+            account_flags: ACCOUNT_FLAGS
+                .iter()
+                .filter(|(flag, _)| flags & flag != 0)
+                .map(|(_, name)| name.to_string())
+                .collect(),
+            last_logon: time(0x08),
+            password_last_set: time(0x18),
+            last_failed_logon: time(0x28),
+            account_expires,
+            logon_count: read_u16_le(f, 0x42),
+            invalid_password_count: read_u16_le(f, 0x40),
+            groups,
+            sam_last_written: Some(user.key_last_written),
+        });
+    }
+    account_list
+}
@@ -1,3 +1,4 @@
-//pub mod cloud_account_details;
-//pub mod last_login_password_change;
+pub mod cloud_account_details;
+pub mod last_login_password_change;
+pub mod sam_users;
 pub mod user_accounts;
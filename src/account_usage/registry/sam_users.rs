@@ -0,0 +1,183 @@
+// SAM
+// SAM\Domains\Account\Users\<RID as 8 hex digits>: F (fixed size record), V (variable size record) and further values of the user
+// V starts with 12 byte entries (offset, length, unknown) of the variable fields, the data follows at 0xCC
+// SAM\Domains\Account\V ends with the SID of the computer (S-1-5-21-x-y-z), the SID of a user is the computer SID and the RID
+// SAM\Domains\<Builtin|Account>\Aliases\<RID>\C: name, comment and member SIDs of a local group, the data follows at 0x34
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+
+use anyhow::anyhow;
+use chrono::{DateTime, Utc};
+use common::{convert_win_time, read_u32_le};
+use nt_hive::Hive;
+
+const SAM_ACCOUNT: &str = "SAM\\Domains\\Account";
+const SAM_DOMAINS: [&str; 2] = ["SAM\\Domains\\Builtin", "SAM\\Domains\\Account"];
+const C_DATA_OFFSET: usize = 0x34;
+const V_DATA_OFFSET: usize = 0xCC;
+const V_ENTRY_SIZE: usize = 12;
+// fields of the V record
+pub const V_USER_NAME: usize = 1;
+pub const V_FULL_NAME: usize = 2;
+pub const V_COMMENT: usize = 3;
+// revision 1, 4 sub authorities, authority 5 (NT) and first sub authority 21 (non unique)
+const MACHINE_SID_PREFIX: [u8; 12] = [1, 4, 0, 0, 0, 0, 0, 5, 21, 0, 0, 0];
+
+/// a local user of the SAM hive with the raw data of all its values
+pub struct SamUser {
+    pub rid: u32,
+    pub sid: Option<String>,
+    pub user_name: Option<String>,
+    pub values: HashMap<String, Vec<u8>>,
+    pub key_last_written: DateTime<Utc>,
+}
+
+/// a local group of the SAM hive
+pub struct SamGroup {
+    pub name: String,
+    pub members: Vec<String>,
+}
+
+/// reads all users below SAM\Domains\Account\Users
+pub fn read_sam_users(sam_path: &str) -> anyhow::Result<Vec<SamUser>> {
+    let mut buffer = Vec::new();
+    File::open(sam_path)?.read_to_end(&mut buffer)?;
+
+    let hive = Hive::without_validation(buffer.as_ref())?;
+    let root_key_node = hive.root_key_node()?;
+    let account_key = root_key_node
+        .subpath(SAM_ACCOUNT)
+        .ok_or(anyhow!("Key '{SAM_ACCOUNT}' can not be found!"))??;
+    let machine_sid = account_key
+        .value("V")
+        .and_then(|v| v.ok())
+        .and_then(|v| v.data().ok())
+        .and_then(|d| d.into_vec().ok())
+        .and_then(|v| machine_sid(&v));
+
+    let users_key = account_key
+        .subkey("Users")
+        .ok_or(anyhow!("Key '{SAM_ACCOUNT}\\Users' can not be found!"))??;
+    let user_keys = users_key
+        .subkeys()
+        .ok_or(anyhow!("Subkeys can not be unwrapped!"))??;
+
+    let mut users = Vec::new();
+    for user_key in user_keys {
+        let user_key = user_key?;
+        // "Names" holds the user names, all other keys are RIDs
+        let Ok(rid) = u32::from_str_radix(&user_key.name()?.to_string(), 16) else {
+            continue;
+        };
+        let mut values = HashMap::new();
+        if let Some(user_values) = user_key.values() {
+            for value in user_values? {
+                let value = value?;
+                values.insert(value.name()?.to_string(), value.data()?.into_vec()?);
+            }
+        }
+        users.push(SamUser {
+            rid,
+            sid: machine_sid.as_ref().map(|sid| format!("{sid}-{rid}")),
+            user_name: values.get("V").and_then(|v| v_string(v, V_USER_NAME)),
+            values,
+            key_last_written: convert_win_time(user_key.header().timestamp.get()),
+        });
+    }
+    Ok(users)
+}
+
+/// reads the local groups (aliases) of the Builtin and the Account domain
+pub fn read_sam_groups(sam_path: &str) -> anyhow::Result<Vec<SamGroup>> {
+    let mut buffer = Vec::new();
+    File::open(sam_path)?.read_to_end(&mut buffer)?;
+
+    let hive = Hive::without_validation(buffer.as_ref())?;
+    let root_key_node = hive.root_key_node()?;
+    let mut groups = Vec::new();
+    for domain in SAM_DOMAINS {
+        let Some(aliases_key) = root_key_node.subpath(&format!("{domain}\\Aliases")) else {
+            continue;
+        };
+        let Some(alias_keys) = aliases_key?.subkeys() else {
+            continue;
+        };
+        for alias_key in alias_keys? {
+            let alias_key = alias_key?;
+            // "Members" and "Names" are no groups
+            if u32::from_str_radix(&alias_key.name()?.to_string(), 16).is_err() {
+                continue;
+            }
+            let Some(c) = alias_key.value("C") else {
+                continue;
+            };
+            if let Some(group) = read_c_record(&c?.data()?.into_vec()?) {
+                groups.push(group);
+            }
+        }
+    }
+    Ok(groups)
+}
+
+/// name (offset 0x10, length 0x14) and members (offset 0x28, count 0x30) of a group
+fn read_c_record(c: &[u8]) -> Option<SamGroup> {
+    let name_offset = C_DATA_OFFSET.checked_add(read_u32_le(c, 0x10)? as usize)?;
+    let name_length = read_u32_le(c, 0x14)? as usize;
+    let name = utf16_value(c.get(name_offset..name_offset.checked_add(name_length)?)?);
+    let mut offset = C_DATA_OFFSET.checked_add(read_u32_le(c, 0x28)? as usize)?;
+    let count = read_u32_le(c, 0x30)?;
+    let mut members = Vec::new();
+    for _ in 0..count {
+        let Some((sid, length)) = binary_sid(c, offset) else {
+            break;
+        };
+        members.push(sid);
+        offset += length;
+    }
+    Some(SamGroup { name, members })
+}
+
+/// a binary SID at offset as "S-1-5-..." and its length
+fn binary_sid(data: &[u8], offset: usize) -> Option<(String, usize)> {
+    let revision = *data.get(offset)?;
+    let sub_authority_count = usize::from(*data.get(offset + 1)?);
+    let authority = data
+        .get(offset + 2..offset + 8)?
+        .iter()
+        .fold(0u64, |authority, byte| (authority << 8) | u64::from(*byte));
+    let mut sid = format!("S-{revision}-{authority}");
+    for i in 0..sub_authority_count {
+        sid.push_str(&format!("-{}", read_u32_le(data, offset + 8 + i * 4)?));
+    }
+    Some((sid, 8 + sub_authority_count * 4))
+}
+
+/// a UTF-16 field of the V record, None if it is empty
+pub fn v_string(v: &[u8], field: usize) -> Option<String> {
+    let offset = read_u32_le(v, field * V_ENTRY_SIZE)? as usize;
+    let length = read_u32_le(v, field * V_ENTRY_SIZE + 4)? as usize;
+    let start = V_DATA_OFFSET.checked_add(offset)?;
+    let data = v.get(start..start.checked_add(length)?)?;
+    (!data.is_empty()).then(|| utf16_value(data))
+}
+
+/// REG_BINARY values with UTF-16LE text (e.g. InternetUserName), trailing nulls are removed
+pub fn utf16_value(data: &[u8]) -> String {
+    let chars: Vec<u16> = data
+        .chunks_exact(2)
+        .map(|c| u16::from_le_bytes([c[0], c[1]]))
+        .collect();
+    String::from_utf16_lossy(&chars)
+        .trim_end_matches('\0')
+        .to_string()
+}
+
+/// "S-1-5-21-x-y-z" from the binary SID at the end of SAM\Domains\Account\V
+fn machine_sid(v: &[u8]) -> Option<String> {
+    let start = v
+        .windows(MACHINE_SID_PREFIX.len() + 12)
+        .rposition(|w| w.starts_with(&MACHINE_SID_PREFIX))?;
+    binary_sid(v, start).map(|(sid, _)| sid)
+}
@@ -1,25 +1,57 @@
 // "Identify both local and domain accounts with interactive logins to the system"- SANS Windows Forensic Analysis Poster, User Accounts
 // SOFTWARE\Microsoft\Windows NT\CurrentVersion\ProfileList
+// merged by SID with the local accounts of the SAM (see last_login_password_change.rs) and their cloud accounts (see cloud_account_details.rs),
+// one entry per SID of either source: domain, Entra ID and service profiles have no SAM account,
+// local accounts that never logged on interactively have no profile, cloud accounts without SID have neither
 
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::{BufWriter, Read, Write};
 
 use anyhow::anyhow;
 use chrono::{DateTime, Utc};
-use common::convert_win_time;
+use common::{convert_win_time, find_file_case_insensitive, find_sam_hive};
+use log::error;
 use nt_hive::Hive;
 use serde::Serialize;
 
+use crate::account_usage::registry::cloud_account_details::{read_cloud_accounts, CloudAccount};
+use crate::account_usage::registry::last_login_password_change::{read_sam_accounts, SamAccount};
+use crate::account_usage::registry::sam_users::read_sam_users;
+
 #[derive(Debug, Serialize)]
+struct UserAccountEntry {
+    // ProfileList, timestamp is the last write time of the profile key
+    timestamp: Option<DateTime<Utc>>,
+    sid: Option<String>,
+    profile_image_path: Option<String>,
+    // SAM
+    #[serde(flatten)]
+    sam_account: SamAccount,
+    // Microsoft or Entra ID account
+    #[serde(flatten)]
+    cloud_account: CloudAccount,
+}
+
 struct ProfileListEntry {
     timestamp: DateTime<Utc>,
     sid: String, //using key name instead of Sid:RegBinary for now
     profile_image_path: String,
 }
 
-// retrieve data about users
-pub fn get_profile_list(reg_file: &str, outpath: &str) -> anyhow::Result<()> {
-    print!("Working on User Accounts: ");
+/// location of UsrClass.dat relative to the profile folder (Vista+)
+pub const USRCLASS_DAT: &str = "AppData/Local/Microsoft/Windows/UsrClass.dat";
+
+/// user hive (e.g. NTUSER.DAT) of a profile from the ProfileList, located on the mounted image
+#[derive(Debug, Clone)]
+pub struct UserHive {
+    pub sid: String,
+    pub profile_image_path: String,
+    pub hive_path: String,
+}
+
+// read the ProfileList
+fn read_profile_list(reg_file: &str) -> anyhow::Result<Vec<ProfileListEntry>> {
     let mut buffer = Vec::new();
     File::open(reg_file)?.read_to_end(&mut buffer)?;
 
@@ -53,17 +85,137 @@ pub fn get_profile_list(reg_file: &str, outpath: &str) -> anyhow::Result<()> {
         };
         profile_list_list.push(profile_list_entry);
     }
+    Ok(profile_list_list)
+}
+
+// retrieve data about users, the ProfileList joined with the local accounts of the SAM and their cloud accounts
+pub fn get_user_accounts_data(
+    input: &str,
+    reg_file: &str,
+    user_hives: &[UserHive],
+    outpath: &str,
+) -> anyhow::Result<()> {
+    print!("Working on User Accounts: ");
+    let profile_list_list = read_profile_list(reg_file).unwrap_or_else(|err| {
+        error!("Failed to get Profile List Data: {err}");
+        Vec::new()
+    });
+    let sam_path = find_sam_hive(input)?;
+    let sam_users = read_sam_users(&sam_path).unwrap_or_else(|err| {
+        error!("Failed to get SAM Accounts: {err}");
+        Vec::new()
+    });
+    let sam_accounts = read_sam_accounts(&sam_path, &sam_users);
+    let (mut cloud_accounts, unlinked_cloud_accounts) =
+        read_cloud_accounts(input, &sam_users, user_hives);
+
+    let mut user_account_list: Vec<UserAccountEntry> = profile_list_list
+        .into_iter()
+        .map(|profile| UserAccountEntry {
+            sid: Some(profile.sid),
+            profile_image_path: Some(profile.profile_image_path),
+            timestamp: Some(profile.timestamp),
+            sam_account: SamAccount::default(),
+            cloud_account: CloudAccount::default(),
+        })
+        .collect();
+    for sam_account in sam_accounts {
+        let profile = user_account_list
+            .iter_mut()
+            .find(|account| sam_account.sid.is_some() && account.sid == sam_account.sid);
+        match profile {
+            Some(account) => account.sam_account = sam_account,
+            None => user_account_list.push(UserAccountEntry {
+                sid: sam_account.sid.clone(),
+                profile_image_path: None,
+                timestamp: None,
+                sam_account,
+                cloud_account: CloudAccount::default(),
+            }),
+        }
+    }
+    for account in user_account_list.iter_mut() {
+        if let Some(cloud_account) = account
+            .sid
+            .as_ref()
+            .and_then(|sid| cloud_accounts.remove(sid))
+        {
+            account.cloud_account = cloud_account;
+        }
+    }
+    // cloud accounts of SIDs without profile and SAM account, e.g. from the IdentityStore
+    let mut remaining: Vec<(Option<String>, CloudAccount)> = cloud_accounts
+        .into_iter()
+        .map(|(sid, cloud_account)| (Some(sid), cloud_account))
+        .collect();
+    remaining.sort_by(|(a, _), (b, _)| a.cmp(b));
+    remaining.extend(unlinked_cloud_accounts.into_iter().map(|c| (None, c)));
+    for (sid, cloud_account) in remaining {
+        user_account_list.push(UserAccountEntry {
+            sid,
+            profile_image_path: None,
+            timestamp: None,
+            sam_account: SamAccount::default(),
+            cloud_account,
+        });
+    }
 
-    if profile_list_list.is_empty() {
+    if user_account_list.is_empty() {
         println!("Nothing to do here, continuing with next job.");
         return Ok(());
     }
 
     let file = File::create(format!("{outpath}/reg_useraccounts.json"))?;
     let mut writer = BufWriter::new(file);
-    serde_json::to_writer(&mut writer, &profile_list_list)?;
+    serde_json::to_writer(&mut writer, &user_account_list)?;
     writer.flush()?;
 
     println!("Done here!");
     Ok(())
 }
+
+/// maps the SIDs of the ProfileList to their profile paths
+pub fn get_profile_paths(reg_file: &str) -> anyhow::Result<HashMap<String, String>> {
+    Ok(read_profile_list(reg_file)?
+        .into_iter()
+        .map(|profile| (profile.sid, profile.profile_image_path))
+        .collect())
+}
+
+/// finds a per-user hive (relative to the profile folder) for every profile in the ProfileList of the SOFTWARE hive
+pub fn find_user_hives(
+    mnt_image_path: &str,
+    reg_file: &str,
+    hive_name: &str,
+) -> anyhow::Result<Vec<UserHive>> {
+    let mut user_hives = Vec::new();
+    for profile in read_profile_list(reg_file)? {
+        // e.g. C:\Users\ally, %SystemDrive%\Users\ally or %systemroot%\system32\config\systemprofile
+        let windows_path = profile.profile_image_path.replace('\\', "/");
+        let lower = windows_path.to_ascii_lowercase();
+        let relative = if let Some(rest) = lower.strip_prefix("%systemroot%") {
+            format!(
+                "Windows{}",
+                &windows_path[windows_path.len() - rest.len()..]
+            )
+        } else if let Some(rest) = lower.strip_prefix("%systemdrive%") {
+            windows_path[windows_path.len() - rest.len()..].to_string()
+        } else if let Some((_, rest)) = windows_path.split_once(':') {
+            rest.to_string()
+        } else {
+            windows_path.clone()
+        };
+        let relative = format!("{}/{hive_name}", relative.trim_matches('/'));
+        if let Some(hive_path) = find_file_case_insensitive(mnt_image_path, &relative) {
+            user_hives.push(UserHive {
+                sid: profile.sid,
+                profile_image_path: profile.profile_image_path,
+                hive_path,
+            });
+        }
+    }
+    if user_hives.is_empty() {
+        return Err(anyhow!("No {hive_name} found for any profile!"));
+    }
+    Ok(user_hives)
+}
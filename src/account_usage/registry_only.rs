@@ -2,7 +2,7 @@ use anyhow::anyhow;
 use common::find_software_hive;
 use log::error;
 
-use crate::account_usage::registry::user_accounts::get_profile_list;
+use crate::account_usage::registry::user_accounts::{find_user_hives, get_user_accounts_data};
 
 pub fn get_accountusage_registry_data(input: &str, outpath: &str) -> anyhow::Result<()> {
     // SOFTWARE hive
@@ -11,8 +11,15 @@ pub fn get_accountusage_registry_data(input: &str, outpath: &str) -> anyhow::Res
     match find_software_hive(input) {
         Ok(path) => {
             found_something = true;
-            if let Err(err) = get_profile_list(&path, outpath) {
-                error!("Failed to get Profile List Data: {err}")
+            let ntuser_hives = match find_user_hives(input, &path, "NTUSER.DAT") {
+                Ok(ntuser_hives) => ntuser_hives,
+                Err(err) => {
+                    error!("Could not find NTUSER.DAT hives: {err}");
+                    Vec::new()
+                }
+            };
+            if let Err(err) = get_user_accounts_data(input, &path, &ntuser_hives, outpath) {
+                error!("Failed to get User Accounts: {err}")
             }
         }
         Err(err) => {
@@ -0,0 +1,232 @@
+// "Windows 10 Timeline ... records recently used applications and files in a SQLite database" - SANS Windows Forensic Analysis Poster, Windows 10 Timeline
+// %USERPROFILE%\AppData\Local\ConnectedDevicesPlatform\<L.Username|Microsoft Account ID>\ActivitiesCache.db
+// Activity holds the synced activities, ActivityOperation the ones not yet uploaded, Activity_PackageId the applications per activity
+// AppId and Payload are JSON, all timestamps are unix time in seconds
+
+use std::collections::HashMap;
+use std::fs;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use common::{
+    convert_unix_time, file_name, find_file_case_insensitive, find_user_profiles, format_guid,
+};
+use log::error;
+use rusqlite::types::ValueRef;
+use rusqlite::Row;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::formats::sqlite::{integer_column, text_column, with_sqlite_copy};
+
+const CDP_FOLDER: &str = "AppData/Local/ConnectedDevicesPlatform";
+
+#[derive(Debug, Serialize)]
+struct TimelineEntry {
+    user_profile: String,
+    database: String,
+    table: String,
+    id: Option<String>,
+    activity_type: Option<i64>,
+    activity_type_name: Option<String>,
+    // ActivityOperation only, 1 = created, 2 = updated, 3 = deleted
+    operation_type: Option<i64>,
+    application: Option<String>,
+    platform: Option<String>,
+    app_display_name: Option<String>,
+    display_text: Option<String>,
+    description: Option<String>,
+    content_uri: Option<String>,
+    start_time: Option<DateTime<Utc>>,
+    end_time: Option<DateTime<Utc>>,
+    last_modified: Option<DateTime<Utc>>,
+    expiration: Option<DateTime<Utc>>,
+    focus_duration_seconds: Option<i64>,
+    platform_device_id: Option<String>,
+    // platform and package name from Activity_PackageId
+    packages: Vec<String>,
+    clipboard_payload: Option<String>,
+}
+
+pub fn get_timeline_data(input: &str, outpath: &str) -> anyhow::Result<()> {
+    print!("Working on Windows 10 Timeline: ");
+    let mut timeline_list: Vec<TimelineEntry> = Vec::new();
+
+    for profile in find_user_profiles(input).unwrap_or_default() {
+        let Some(cdp_folder) = find_file_case_insensitive(&profile, CDP_FOLDER) else {
+            continue;
+        };
+        let profile_name = file_name(Path::new(&profile));
+        let Ok(entries) = fs::read_dir(&cdp_folder) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let folder = entry.path();
+            if !folder.is_dir() {
+                continue;
+            }
+            let Some(database) =
+                find_file_case_insensitive(&folder.to_string_lossy(), "ActivitiesCache.db")
+            else {
+                continue;
+            };
+            if let Err(err) = read_activities_cache(&profile_name, &database, &mut timeline_list) {
+                error!("Failed to read {database}: {err}")
+            }
+        }
+    }
+
+    if timeline_list.is_empty() {
+        println!("Nothing to do here, continuing with next job.");
+        return Ok(());
+    }
+
+    let file = File::create(format!("{outpath}/tl_timeline.json"))?;
+    let mut writer = BufWriter::new(file);
+    serde_json::to_writer(&mut writer, &timeline_list)?;
+    writer.flush()?;
+
+    println!("Done here!");
+    Ok(())
+}
+
+fn read_activities_cache(
+    user_profile: &str,
+    database: &str,
+    timeline_list: &mut Vec<TimelineEntry>,
+) -> anyhow::Result<()> {
+    let entries = with_sqlite_copy(database, |connection| {
+        let mut packages: HashMap<String, Vec<String>> = HashMap::new();
+        // older databases may not have the table
+        if let Ok(mut statement) = connection.prepare("SELECT * FROM Activity_PackageId") {
+            let mut rows = statement.query([])?;
+            while let Some(row) = rows.next()? {
+                let Some(id) = guid_column(row, "ActivityId") else {
+                    continue;
+                };
+                let platform = text_column(row, "Platform").unwrap_or_default();
+                let package = text_column(row, "PackageName").unwrap_or_default();
+                packages
+                    .entry(id)
+                    .or_default()
+                    .push(format!("{platform}: {package}"));
+            }
+        }
+
+        let mut entries = Vec::new();
+        for table in ["Activity", "ActivityOperation"] {
+            let mut statement = connection.prepare(&format!("SELECT * FROM {table}"))?;
+            let mut rows = statement.query([])?;
+            while let Some(row) = rows.next()? {
+                let mut entry = build_entry(user_profile, database, table, row);
+                if let Some(id) = &entry.id {
+                    entry.packages = packages.get(id).cloned().unwrap_or_default();
+                }
+                entries.push(entry);
+            }
+        }
+        Ok(entries)
+    })?;
+    timeline_list.extend(entries);
+    Ok(())
+}
+
+fn build_entry(user_profile: &str, database: &str, table: &str, row: &Row) -> TimelineEntry {
+    let payload: Value = text_column(row, "Payload")
+        .and_then(|p| serde_json::from_str(&p).ok())
+        .unwrap_or_default();
+    let payload_text = |name: &str| {
+        payload
+            .get(name)
+            .and_then(Value::as_str)
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+    };
+    let (application, platform) = text_column(row, "AppId")
+        .map(|app_id| parse_app_id(&app_id))
+        .unwrap_or_default();
+    let activity_type = integer_column(row, "ActivityType");
+    let start_time = integer_column(row, "StartTime").and_then(convert_unix_time);
+    let end_time = integer_column(row, "EndTime").and_then(convert_unix_time);
+    // the payload of "in focus" activities has the time the application was actually used
+    let focus_duration_seconds = payload
+        .get("activeDurationSeconds")
+        .and_then(Value::as_i64)
+        .or_else(|| Some((end_time? - start_time?).num_seconds()));
+
+    TimelineEntry {
+        user_profile: user_profile.to_string(),
+        database: database.to_string(),
+        table: table.to_string(),
+        id: guid_column(row, "Id"),
+        activity_type,
+        activity_type_name: activity_type
+            .and_then(activity_type_name)
+            .map(str::to_string),
+        operation_type: integer_column(row, "OperationType"),
+        application,
+        platform,
+        app_display_name: payload_text("appDisplayName"),
+        display_text: payload_text("displayText"),
+        description: payload_text("description"),
+        content_uri: payload_text("contentUri")
+            .or_else(|| text_column(row, "ContentUri"))
+            .filter(|s| !s.is_empty()),
+        start_time,
+        end_time,
+        last_modified: integer_column(row, "LastModifiedTime").and_then(convert_unix_time),
+        expiration: integer_column(row, "ExpirationTime").and_then(convert_unix_time),
+        focus_duration_seconds,
+        platform_device_id: text_column(row, "PlatformDeviceId").filter(|s| !s.is_empty()),
+        packages: Vec::new(),
+        clipboard_payload: text_column(row, "ClipboardPayload")
+            .filter(|s| !s.is_empty() && s != "[]"),
+    }
+}
+
+/// AppId is a list like [{"application":"Microsoft.Office.WINWORD.EXE.15","platform":"windows_win32"}, ...],
+/// the Windows entries are preferred over packageId and the other platforms
+fn parse_app_id(app_id: &str) -> (Option<String>, Option<String>) {
+    let Ok(Value::Array(apps)) = serde_json::from_str::<Value>(app_id) else {
+        return (Some(app_id.to_string()), None);
+    };
+    let field = |app: &Value, name: &str| app.get(name).and_then(Value::as_str).map(str::to_string);
+    let app = apps
+        .iter()
+        .find(|app| {
+            matches!(
+                app.get("platform").and_then(Value::as_str),
+                Some("windows_win32" | "x_exe_path" | "windows_universal")
+            )
+        })
+        .or_else(|| apps.first());
+    match app {
+        Some(app) => (field(app, "application"), field(app, "platform")),
+        None => (None, None),
+    }
+}
+
+fn activity_type_name(activity_type: i64) -> Option<&'static str> {
+    let name = match activity_type {
+        2 => "Notification",
+        3 => "Mobile Device Backup",
+        5 => "Open App/File/Page",
+        6 => "App In Use/Focus",
+        10 => "Clipboard",
+        11 | 12 | 15 => "System",
+        16 => "Copy/Paste",
+        _ => return None,
+    };
+    Some(name)
+}
+
+/// ids are stored as 16 byte blobs
+fn guid_column(row: &Row, name: &str) -> Option<String> {
+    match row.get_ref(name).ok()? {
+        ValueRef::Blob(b) => format_guid(b, 0),
+        ValueRef::Text(t) => Some(String::from_utf8_lossy(t).to_string()),
+        _ => None,
+    }
+}
@@ -0,0 +1,11 @@
+// FilesOnly Processing Mode for Application Execution
+use log::error;
+
+use crate::application_execution::files::timeline::get_timeline_data;
+
+pub fn get_applicationexecution_file_data(input: &str, outpath: &str) -> anyhow::Result<()> {
+    if let Err(err) = get_timeline_data(input, outpath) {
+        error!("Failed to get Windows 10 Timeline: {err}")
+    }
+    Ok(())
+}
@@ -0,0 +1,164 @@
+// SOFTWARE and NTUSER.DAT
+// "Records which applications accessed the microphone, webcam, location and other privacy sensitive capabilities, including when the access started and stopped." - SANS Windows Forensic Analysis Poster, CapabilityAccessManager
+// SOFTWARE\Microsoft\Windows\CurrentVersion\CapabilityAccessManager\ConsentStore\<Capability>\<App> (system wide)
+// NTUSER.DAT\Software\Microsoft\Windows\CurrentVersion\CapabilityAccessManager\ConsentStore\<Capability>\<App> (per user)
+// packaged apps are subkeys of the capability, desktop apps are below "NonPackaged" with "#" instead of "\" in the path
+// LastUsedTimeStart and LastUsedTimeStop are FILETIMEs, "Value" is the consent (Allow/Deny)
+
+use std::fs::File;
+use std::io::{BufWriter, Read, Write};
+
+use anyhow::anyhow;
+use chrono::{DateTime, Utc};
+use common::convert_win_time_opt;
+use log::error;
+use nt_hive::{Hive, KeyNode};
+use serde::Serialize;
+
+use crate::account_usage::registry::user_accounts::UserHive;
+
+const CONSENT_STORE: &str =
+    "Microsoft\\Windows\\CurrentVersion\\CapabilityAccessManager\\ConsentStore";
+
+#[derive(Debug, Serialize)]
+struct CapabilityAccessEntry {
+    hive: String,
+    // None for the system wide entries of the SOFTWARE hive
+    sid: Option<String>,
+    profile_image_path: Option<String>,
+    capability: String,
+    application: String,
+    non_packaged: bool,
+    consent: Option<String>,
+    last_used_start: Option<DateTime<Utc>>,
+    last_used_stop: Option<DateTime<Utc>>,
+}
+
+pub fn get_capability_access_data(
+    reg_file: &str,
+    user_hives: &[UserHive],
+    outpath: &str,
+) -> anyhow::Result<()> {
+    print!("Working on CapabilityAccessManager: ");
+    let mut capability_list: Vec<CapabilityAccessEntry> = Vec::new();
+
+    if let Err(err) = get_consent_store(reg_file, CONSENT_STORE, None, &mut capability_list) {
+        error!("Failed to get CapabilityAccessManager of SOFTWARE: {err}")
+    }
+    for user_hive in user_hives {
+        let key_path = format!("Software\\{CONSENT_STORE}");
+        if let Err(err) = get_consent_store(
+            &user_hive.hive_path,
+            &key_path,
+            Some(user_hive),
+            &mut capability_list,
+        ) {
+            error!(
+                "Failed to get CapabilityAccessManager of {}: {err}",
+                user_hive.sid
+            )
+        }
+    }
+
+    if capability_list.is_empty() {
+        println!("Nothing to do here, continuing with next job.");
+        return Ok(());
+    }
+
+    let file = File::create(format!("{outpath}/reg_capability_access.json"))?;
+    let mut writer = BufWriter::new(file);
+    serde_json::to_writer(&mut writer, &capability_list)?;
+    writer.flush()?;
+
+    println!("Done here!");
+    Ok(())
+}
+
+fn get_consent_store(
+    hive_path: &str,
+    key_path: &str,
+    user_hive: Option<&UserHive>,
+    capability_list: &mut Vec<CapabilityAccessEntry>,
+) -> anyhow::Result<()> {
+    let mut buffer = Vec::new();
+    File::open(hive_path)?.read_to_end(&mut buffer)?;
+
+    let hive = Hive::without_validation(buffer.as_ref())?;
+    let root_key_node = hive.root_key_node()?;
+    let consent_store_key = root_key_node
+        .subpath(key_path)
+        .ok_or(anyhow!("Key '{key_path}' can not be found!"))??;
+
+    let capability_keys = consent_store_key
+        .subkeys()
+        .ok_or(anyhow!("Subkeys can not be unwrapped!"))??;
+    for capability_key in capability_keys {
+        let capability_key = capability_key?;
+        let capability = capability_key.name()?.to_string();
+        let Some(app_keys) = capability_key.subkeys() else {
+            continue;
+        };
+        for app_key in app_keys? {
+            let app_key = app_key?;
+            let application = app_key.name()?.to_string();
+            if application.eq_ignore_ascii_case("NonPackaged") {
+                let Some(desktop_app_keys) = app_key.subkeys() else {
+                    continue;
+                };
+                for desktop_app_key in desktop_app_keys? {
+                    let desktop_app_key = desktop_app_key?;
+                    let application = desktop_app_key.name()?.to_string().replace('#', "\\");
+                    capability_list.push(build_entry(
+                        hive_path,
+                        user_hive,
+                        &capability,
+                        application,
+                        true,
+                        &desktop_app_key,
+                    ));
+                }
+            } else {
+                capability_list.push(build_entry(
+                    hive_path,
+                    user_hive,
+                    &capability,
+                    application,
+                    false,
+                    &app_key,
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn build_entry(
+    hive_path: &str,
+    user_hive: Option<&UserHive>,
+    capability: &str,
+    application: String,
+    non_packaged: bool,
+    app_key: &KeyNode<&[u8]>,
+) -> CapabilityAccessEntry {
+    let time_value = |name: &str| {
+        app_key
+            .value(name)
+            .and_then(|v| v.ok())
+            .and_then(|v| v.qword_data().ok())
+            .and_then(convert_win_time_opt)
+    };
+    CapabilityAccessEntry {
+        hive: hive_path.to_string(),
+        sid: user_hive.map(|u| u.sid.clone()),
+        profile_image_path: user_hive.map(|u| u.profile_image_path.clone()),
+        capability: capability.to_string(),
+        application,
+        non_packaged,
+        consent: app_key
+            .value("Value")
+            .and_then(|v| v.ok())
+            .and_then(|v| v.string_data().ok()),
+        last_used_start: time_value("LastUsedTimeStart"),
+        last_used_stop: time_value("LastUsedTimeStop"),
+    }
+}
@@ -0,0 +1,96 @@
+// NTUSER.DAT
+// "Tracks application usage on the taskbar, e.g. how often an application was switched to, launched or had its jump list opened." - SANS Windows Forensic Analysis Poster, Taskbar Feature Usage
+// NTUSER.DAT\Software\Microsoft\Windows\CurrentVersion\Explorer\FeatureUsage\<Feature>
+// every value is an application (path or AppUserModelID) with a DWORD counter, the key timestamp is the last update of the feature
+
+use std::fs::File;
+use std::io::{BufWriter, Read, Write};
+
+use anyhow::anyhow;
+use chrono::{DateTime, Utc};
+use common::convert_win_time;
+use log::error;
+use nt_hive::Hive;
+use serde::Serialize;
+
+use crate::account_usage::registry::user_accounts::UserHive;
+
+const FEATURES: [&str; 4] = [
+    "AppSwitched",
+    "AppLaunch",
+    "ShowJumpView",
+    "AppBadgeUpdated",
+];
+
+#[derive(Debug, Serialize)]
+struct FeatureUsageEntry {
+    sid: String,
+    profile_image_path: String,
+    feature: String,
+    application: String,
+    count: u32,
+    key_last_written: DateTime<Utc>,
+}
+
+pub fn get_featureusage_data(user_hives: &[UserHive], outpath: &str) -> anyhow::Result<()> {
+    print!("Working on Taskbar FeatureUsage: ");
+    let mut featureusage_list: Vec<FeatureUsageEntry> = Vec::new();
+
+    for user_hive in user_hives {
+        if let Err(err) = get_user_featureusage(user_hive, &mut featureusage_list) {
+            error!("Failed to get FeatureUsage of {}: {err}", user_hive.sid)
+        }
+    }
+
+    if featureusage_list.is_empty() {
+        println!("Nothing to do here, continuing with next job.");
+        return Ok(());
+    }
+
+    let file = File::create(format!("{outpath}/reg_featureusage.json"))?;
+    let mut writer = BufWriter::new(file);
+    serde_json::to_writer(&mut writer, &featureusage_list)?;
+    writer.flush()?;
+
+    println!("Done here!");
+    Ok(())
+}
+
+fn get_user_featureusage(
+    user_hive: &UserHive,
+    featureusage_list: &mut Vec<FeatureUsageEntry>,
+) -> anyhow::Result<()> {
+    let mut buffer = Vec::new();
+    File::open(&user_hive.hive_path)?.read_to_end(&mut buffer)?;
+
+    let hive = Hive::without_validation(buffer.as_ref())?;
+    let root_key_node = hive.root_key_node()?;
+    let sub_key_node = root_key_node
+        .subpath("Software\\Microsoft\\Windows\\CurrentVersion\\Explorer\\FeatureUsage")
+        .ok_or(anyhow!(
+            "Key 'Software\\Microsoft\\Windows\\CurrentVersion\\Explorer\\FeatureUsage' can not be found!"
+        ))??;
+
+    for feature in FEATURES {
+        let Some(feature_key) = sub_key_node.subkey(feature) else {
+            continue;
+        };
+        let feature_key = feature_key?;
+        let key_last_written = convert_win_time(feature_key.header().timestamp.get());
+        let Some(values) = feature_key.values() else {
+            continue;
+        };
+        for value in values? {
+            let value = value?;
+            featureusage_list.push(FeatureUsageEntry {
+                sid: user_hive.sid.clone(),
+                profile_image_path: user_hive.profile_image_path.clone(),
+                feature: feature.to_string(),
+                application: value.name()?.to_string(),
+                count: value.dword_data().unwrap_or_default(),
+                key_last_written,
+            });
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,4 @@
+pub mod capability_access;
+pub mod feature_usage;
+pub mod run_mru;
+pub mod user_assist;
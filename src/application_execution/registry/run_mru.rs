@@ -0,0 +1,80 @@
+// NTUSER.DAT
+// "Whenever someone does a Start -> Run command, it will log the entry for the command they executed." - SANS Windows Forensic Analysis Poster, Commands Executed in the Run Dialog
+// NTUSER.DAT\Software\Microsoft\Windows\CurrentVersion\Explorer\RunMRU
+// values are named a-z and ordered by MRUList, every command ends with "\1"
+
+use std::fs::File;
+use std::io::{BufWriter, Read, Write};
+
+use anyhow::anyhow;
+use chrono::{DateTime, Utc};
+use common::read_utf16_string;
+use log::error;
+use nt_hive::Hive;
+use serde::Serialize;
+
+use crate::account_usage::registry::user_accounts::UserHive;
+use crate::formats::mru::{mru_position, read_mru_values};
+
+#[derive(Debug, Serialize)]
+struct RunMruEntry {
+    sid: String,
+    profile_image_path: String,
+    value_name: String,
+    mru_position: Option<usize>,
+    command: String,
+    // only known for the most recently used entry
+    executed: Option<DateTime<Utc>>,
+}
+
+pub fn get_runmru_data(user_hives: &[UserHive], outpath: &str) -> anyhow::Result<()> {
+    print!("Working on RunMRU: ");
+    let mut runmru_list: Vec<RunMruEntry> = Vec::new();
+
+    for user_hive in user_hives {
+        if let Err(err) = get_user_runmru(user_hive, &mut runmru_list) {
+            error!("Failed to get RunMRU of {}: {err}", user_hive.sid)
+        }
+    }
+
+    if runmru_list.is_empty() {
+        println!("Nothing to do here, continuing with next job.");
+        return Ok(());
+    }
+
+    let file = File::create(format!("{outpath}/reg_runmru.json"))?;
+    let mut writer = BufWriter::new(file);
+    serde_json::to_writer(&mut writer, &runmru_list)?;
+    writer.flush()?;
+
+    println!("Done here!");
+    Ok(())
+}
+
+fn get_user_runmru(user_hive: &UserHive, runmru_list: &mut Vec<RunMruEntry>) -> anyhow::Result<()> {
+    let mut buffer = Vec::new();
+    File::open(&user_hive.hive_path)?.read_to_end(&mut buffer)?;
+
+    let hive = Hive::without_validation(buffer.as_ref())?;
+    let root_key_node = hive.root_key_node()?;
+    let sub_key_node = root_key_node
+        .subpath("Software\\Microsoft\\Windows\\CurrentVersion\\Explorer\\RunMRU")
+        .ok_or(anyhow!(
+            "Key 'Software\\Microsoft\\Windows\\CurrentVersion\\Explorer\\RunMRU' can not be found!"
+        ))??;
+
+    let mru_values = read_mru_values(&sub_key_node)?;
+    for (value_name, data) in &mru_values.values {
+        let command = read_utf16_string(data, 0).unwrap_or_default();
+        let position = mru_position(&mru_values.order, value_name);
+        runmru_list.push(RunMruEntry {
+            sid: user_hive.sid.clone(),
+            profile_image_path: user_hive.profile_image_path.clone(),
+            value_name: value_name.clone(),
+            mru_position: position,
+            command: command.trim_end_matches("\\1").to_string(),
+            executed: (position == Some(0)).then_some(mru_values.timestamp),
+        });
+    }
+    Ok(())
+}
@@ -0,0 +1,162 @@
+// NTUSER.DAT
+// "GUI-based programs launched from the desktop are tracked in the launcher on a Windows System." - SANS Windows Forensic Analysis Poster, UserAssist
+// NTUSER.DAT\Software\Microsoft\Windows\CurrentVersion\Explorer\UserAssist\{GUID}\Count
+// value names are ROT13 encoded, value data is 72 bytes on Windows 7+ and 16 bytes on XP
+
+use std::fs::File;
+use std::io::{BufWriter, Read, Write};
+
+use anyhow::anyhow;
+use chrono::{DateTime, Utc};
+use common::{convert_win_time, convert_win_time_opt, read_u32_le, read_u64_le};
+use log::error;
+use nt_hive::Hive;
+use serde::Serialize;
+
+use crate::account_usage::registry::user_accounts::UserHive;
+use crate::formats::guids::known_folder_path;
+
+#[derive(Debug, Serialize)]
+struct UserAssistEntry {
+    sid: String,
+    profile_image_path: String,
+    key_timestamp: DateTime<Utc>,
+    guid: String,
+    guid_type: String,
+    name: String,
+    path: String,
+    run_count: Option<u32>,
+    focus_count: Option<u32>,
+    focus_time_ms: Option<u32>,
+    last_executed: Option<DateTime<Utc>>,
+}
+
+pub fn get_userassist_data(user_hives: &[UserHive], outpath: &str) -> anyhow::Result<()> {
+    print!("Working on UserAssist: ");
+    let mut userassist_list: Vec<UserAssistEntry> = Vec::new();
+
+    for user_hive in user_hives {
+        match get_user_entries(user_hive) {
+            Ok(mut entries) => userassist_list.append(&mut entries),
+            Err(err) => error!("Failed to get UserAssist of {}: {err}", user_hive.sid),
+        }
+    }
+
+    if userassist_list.is_empty() {
+        println!("Nothing to do here, continuing with next job.");
+        return Ok(());
+    }
+
+    let file = File::create(format!("{outpath}/reg_userassist.json"))?;
+    let mut writer = BufWriter::new(file);
+    serde_json::to_writer(&mut writer, &userassist_list)?;
+    writer.flush()?;
+
+    println!("Done here!");
+    Ok(())
+}
+
+fn get_user_entries(user_hive: &UserHive) -> anyhow::Result<Vec<UserAssistEntry>> {
+    let mut buffer = Vec::new();
+    File::open(&user_hive.hive_path)?.read_to_end(&mut buffer)?;
+
+    let hive = Hive::without_validation(buffer.as_ref())?;
+    let root_key_node = hive.root_key_node()?;
+    let sub_key_node = root_key_node
+        .subpath("Software\\Microsoft\\Windows\\CurrentVersion\\Explorer\\UserAssist")
+        .ok_or(anyhow!(
+            "Key 'Software\\Microsoft\\Windows\\CurrentVersion\\Explorer\\UserAssist' can not be found!"
+        ))??;
+
+    let sub_key_nodes = sub_key_node
+        .subkeys()
+        .ok_or(anyhow!("Subkeys can not be unwrapped!"))??;
+
+    let mut entries = Vec::new();
+    for guid_key in sub_key_nodes {
+        let guid_key = guid_key?;
+        let guid = guid_key.name()?.to_string();
+        let Some(count_key) = guid_key.subkey("Count") else {
+            continue;
+        };
+        let count_key = count_key?;
+        let key_timestamp = convert_win_time(count_key.header().timestamp.get());
+        let Some(values) = count_key.values() else {
+            continue;
+        };
+        for value in values? {
+            let value = value?;
+            let name = rot13(&value.name()?.to_string());
+            // session information, not a program
+            if name.starts_with("UEME_CTLSESSION") {
+                continue;
+            }
+            let data = value.data()?.into_vec()?;
+            let (run_count, focus_count, focus_time_ms, last_executed) = if data.len() >= 68 {
+                (
+                    read_u32_le(&data, 4),
+                    read_u32_le(&data, 8),
+                    read_u32_le(&data, 12),
+                    read_u64_le(&data, 60).and_then(convert_win_time_opt),
+                )
+            } else if data.len() >= 16 {
+                // XP starts counting at 5
+                (
+                    read_u32_le(&data, 4).map(|c| c.saturating_sub(5)),
+                    None,
+                    None,
+                    read_u64_le(&data, 8).and_then(convert_win_time_opt),
+                )
+            } else {
+                (None, None, None, None)
+            };
+            entries.push(UserAssistEntry {
+                sid: user_hive.sid.clone(),
+                profile_image_path: user_hive.profile_image_path.clone(),
+                key_timestamp,
+                guid: guid.clone(),
+                guid_type: guid_type(&guid),
+                path: resolve_known_folder(&name),
+                name,
+                run_count,
+                focus_count,
+                focus_time_ms,
+                last_executed,
+            });
+        }
+    }
+    Ok(entries)
+}
+
+/// value names are ROT13 "encrypted"
+fn rot13(input: &str) -> String {
+    input
+        .chars()
+        .map(|c| match c {
+            'a'..='m' | 'A'..='M' => ((c as u8) + 13) as char,
+            'n'..='z' | 'N'..='Z' => ((c as u8) - 13) as char,
+            _ => c,
+        })
+        .collect()
+}
+
+/// replaces a leading known folder GUID with its path
+fn resolve_known_folder(name: &str) -> String {
+    if let Some((guid, rest)) = name.split_once('}') {
+        if let Some(path) = known_folder_path(&format!("{guid}}}")) {
+            return format!("{path}{rest}");
+        }
+    }
+    name.to_string()
+}
+
+fn guid_type(guid: &str) -> String {
+    match guid.to_uppercase().as_str() {
+        "{CEBFF5CD-ACE2-4F4F-9178-9926F41749EA}" => "Executable File Execution",
+        "{F4E57C4B-2036-45F0-A9AB-443BCFE33D9F}" => "Shortcut File Execution",
+        "{75048700-EF1F-11D0-9888-006097DEACF9}" => "Active Desktop (XP)",
+        "{5E6AB780-7743-11CF-A12B-00AA004AE837}" => "Internet Toolbar (XP)",
+        _ => "Unknown",
+    }
+    .to_string()
+}
@@ -0,0 +1,44 @@
+// RegistryOnly Processing Mode for Application Execution
+use anyhow::anyhow;
+use common::find_software_hive;
+use log::error;
+
+use crate::account_usage::registry::user_accounts::find_user_hives;
+use crate::application_execution::registry::capability_access::get_capability_access_data;
+use crate::application_execution::registry::feature_usage::get_featureusage_data;
+use crate::application_execution::registry::run_mru::get_runmru_data;
+use crate::application_execution::registry::user_assist::get_userassist_data;
+
+pub fn get_applicationexecution_registry_data(input: &str, outpath: &str) -> anyhow::Result<()> {
+    let mut found_something = false;
+    // SOFTWARE hive (needed to find the NTUSER.DAT hives)
+    match find_software_hive(input) {
+        Ok(path) => match find_user_hives(input, &path, "NTUSER.DAT") {
+            Ok(user_hives) => {
+                found_something = true;
+                if let Err(err) = get_userassist_data(&user_hives, outpath) {
+                    error!("Failed to get UserAssist Data: {err}")
+                }
+                if let Err(err) = get_runmru_data(&user_hives, outpath) {
+                    error!("Failed to get RunMRU: {err}")
+                }
+                if let Err(err) = get_featureusage_data(&user_hives, outpath) {
+                    error!("Failed to get Taskbar FeatureUsage: {err}")
+                }
+                if let Err(err) = get_capability_access_data(&path, &user_hives, outpath) {
+                    error!("Failed to get CapabilityAccessManager: {err}")
+                }
+            }
+            Err(err) => {
+                error!("Could not find NTUSER.DAT hives: {err}")
+            }
+        },
+        Err(err) => {
+            error!("Could not find Software hive: {err}")
+        }
+    }
+    if !found_something {
+        return Err(anyhow!("No NTUSER.DAT found!"));
+    }
+    Ok(())
+}
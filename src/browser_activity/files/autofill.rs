@@ -0,0 +1,227 @@
+// "Many databases store data that a user has typed into the browser." - SANS Windows Forensic Analysis Poster, Auto-Complete Data
+// <Chromium profile>\Web Data (SQLite), all timestamps are unix time in seconds
+// autofill: form field name, value, use count, first and last use
+// addresses: autofill_profiles (+ _names, _emails, _phones) in older versions,
+// local_addresses or addresses with one row per field in the *_type_tokens table in newer versions
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use common::convert_unix_time;
+use log::error;
+use rusqlite::types::ValueRef;
+use rusqlite::Connection;
+
+use crate::browser_activity::files::browsers::{
+    find_chromium_profiles, write_json, AddressEntry, BrowserProfile, FormHistoryEntry,
+};
+use crate::formats::sqlite::{integer_column, text_column, with_sqlite_copy};
+
+// address table and the table with its fields (None if the fields are columns)
+const ADDRESS_TABLES: [(&str, Option<&str>); 3] = [
+    ("autofill_profiles", None),
+    ("local_addresses", Some("local_addresses_type_tokens")),
+    ("addresses", Some("address_type_tokens")),
+];
+// tables with additional fields of autofill_profiles
+const PROFILE_FIELD_TABLES: [&str; 3] = [
+    "autofill_profile_names",
+    "autofill_profile_emails",
+    "autofill_profile_phones",
+];
+// columns that are metadata of the address, not a field
+const METADATA_COLUMNS: [&str; 6] = [
+    "guid",
+    "origin",
+    "label",
+    "language_code",
+    "use_count",
+    "use_date",
+];
+
+pub fn get_autofill_data(input: &str, outpath: &str) -> anyhow::Result<()> {
+    print!("Working on Browser Autofill: ");
+    let mut autofill_list: Vec<FormHistoryEntry> = Vec::new();
+    let mut address_list: Vec<AddressEntry> = Vec::new();
+
+    for profile in find_chromium_profiles(input) {
+        let database = format!("{}/Web Data", profile.path);
+        if !Path::new(&database).is_file() {
+            continue;
+        }
+        let result = with_sqlite_copy(&database, |connection| {
+            read_autofill(connection, &profile, &database, &mut autofill_list)?;
+            read_addresses(connection, &profile, &database, &mut address_list);
+            Ok(())
+        });
+        if let Err(err) = result {
+            error!("Failed to read {database}: {err}")
+        }
+    }
+
+    if autofill_list.is_empty() && address_list.is_empty() {
+        println!("Nothing to do here, continuing with next job.");
+        return Ok(());
+    }
+
+    write_json(&format!("{outpath}/br_autofill.json"), &autofill_list)?;
+    write_json(&format!("{outpath}/br_addresses.json"), &address_list)?;
+
+    println!("Done here!");
+    Ok(())
+}
+
+fn read_autofill(
+    connection: &Connection,
+    profile: &BrowserProfile,
+    database: &str,
+    autofill_list: &mut Vec<FormHistoryEntry>,
+) -> anyhow::Result<()> {
+    let mut statement = connection.prepare("SELECT * FROM autofill")?;
+    let mut rows = statement.query([])?;
+    while let Some(row) = rows.next()? {
+        autofill_list.push(FormHistoryEntry {
+            user_profile: profile.user_profile.clone(),
+            browser: profile.browser.clone(),
+            browser_profile: profile.browser_profile.clone(),
+            database: database.to_string(),
+            field_name: text_column(row, "name").unwrap_or_default(),
+            value: text_column(row, "value").unwrap_or_default(),
+            times_used: integer_column(row, "count"),
+            first_used: integer_column(row, "date_created").and_then(convert_unix_time),
+            last_used: integer_column(row, "date_last_used").and_then(convert_unix_time),
+        });
+    }
+    Ok(())
+}
+
+/// the address tables depend on the version, missing tables are skipped
+fn read_addresses(
+    connection: &Connection,
+    profile: &BrowserProfile,
+    database: &str,
+    address_list: &mut Vec<AddressEntry>,
+) {
+    for (table, token_table) in ADDRESS_TABLES {
+        // the address table itself does not exist in every version
+        let mut fields: HashMap<String, BTreeMap<String, String>> = HashMap::new();
+        if read_text_columns(connection, table, &mut fields).is_err() {
+            continue;
+        }
+        match token_table {
+            Some(token_table) => {
+                if let Err(err) = read_type_tokens(connection, token_table, &mut fields) {
+                    error!("Failed to read {token_table} of {database}: {err}")
+                }
+            }
+            None => {
+                for field_table in PROFILE_FIELD_TABLES {
+                    if let Err(err) = read_text_columns(connection, field_table, &mut fields) {
+                        error!("Failed to read {field_table} of {database}: {err}")
+                    }
+                }
+            }
+        }
+        let Ok(mut statement) = connection.prepare(&format!("SELECT * FROM {table}")) else {
+            continue;
+        };
+        let Ok(mut rows) = statement.query([]) else {
+            continue;
+        };
+        while let Ok(Some(row)) = rows.next() {
+            let guid = text_column(row, "guid").unwrap_or_default();
+            address_list.push(AddressEntry {
+                user_profile: profile.user_profile.clone(),
+                browser: profile.browser.clone(),
+                browser_profile: profile.browser_profile.clone(),
+                database: database.to_string(),
+                fields: fields.remove(&guid).unwrap_or_default(),
+                guid,
+                use_count: integer_column(row, "use_count"),
+                last_used: integer_column(row, "use_date").and_then(convert_unix_time),
+                modified: integer_column(row, "date_modified").and_then(convert_unix_time),
+            });
+        }
+    }
+}
+
+/// all non empty text columns of a table by guid, the column name is the field name
+fn read_text_columns(
+    connection: &Connection,
+    table: &str,
+    fields: &mut HashMap<String, BTreeMap<String, String>>,
+) -> anyhow::Result<()> {
+    let mut statement = connection.prepare(&format!("SELECT * FROM {table}"))?;
+    let column_names: Vec<String> = statement
+        .column_names()
+        .iter()
+        .map(|name| name.to_string())
+        .collect();
+    let mut rows = statement.query([])?;
+    while let Some(row) = rows.next()? {
+        let Some(guid) = text_column(row, "guid") else {
+            continue;
+        };
+        let entry = fields.entry(guid).or_default();
+        for (index, name) in column_names.iter().enumerate() {
+            if METADATA_COLUMNS.contains(&name.as_str()) {
+                continue;
+            }
+            if let Ok(ValueRef::Text(text)) = row.get_ref(index) {
+                if !text.is_empty() {
+                    entry.insert(name.clone(), String::from_utf8_lossy(text).to_string());
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// one row per field (guid, type, value), the type is a Chromium field type
+fn read_type_tokens(
+    connection: &Connection,
+    table: &str,
+    fields: &mut HashMap<String, BTreeMap<String, String>>,
+) -> anyhow::Result<()> {
+    let mut statement = connection.prepare(&format!("SELECT * FROM {table}"))?;
+    let mut rows = statement.query([])?;
+    while let Some(row) = rows.next()? {
+        let (Some(guid), Some(field_type), Some(value)) = (
+            text_column(row, "guid"),
+            integer_column(row, "type"),
+            text_column(row, "value"),
+        ) else {
+            continue;
+        };
+        if value.is_empty() {
+            continue;
+        }
+        fields
+            .entry(guid)
+            .or_default()
+            .insert(field_type_name(field_type), value);
+    }
+    Ok(())
+}
+
+/// names of the common address field types, others are kept as number
+fn field_type_name(field_type: i64) -> String {
+    let name = match field_type {
+        3 => "first_name",
+        4 => "middle_name",
+        5 => "last_name",
+        7 => "full_name",
+        9 => "email",
+        14 => "phone_number",
+        30 => "address_line_1",
+        31 => "address_line_2",
+        33 => "city",
+        34 => "state",
+        35 => "zip_code",
+        36 => "country",
+        60 => "company_name",
+        77 => "street_address",
+        _ => return format!("type_{field_type}"),
+    };
+    name.to_string()
+}
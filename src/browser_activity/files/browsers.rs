@@ -0,0 +1,406 @@
+// browser profile discovery and the normalized output of all browsers
+// Chromium based browsers keep their profiles ("Default", "Profile 1", ...) in a "User Data" folder,
+// Opera uses the "Opera Stable" folder as profile itself
+// Firefox based browsers keep their profiles ("xxxxxxxx.default-release", ...) in a "Profiles" folder
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use common::{file_name, find_file_case_insensitive, find_user_profiles};
+use serde::Serialize;
+
+// browser name and user data folder relative to the user profile
+const CHROMIUM_BROWSERS: [(&str, &str); 9] = [
+    ("Chrome", "AppData/Local/Google/Chrome/User Data"),
+    ("Chrome Beta", "AppData/Local/Google/Chrome Beta/User Data"),
+    ("Chrome Canary", "AppData/Local/Google/Chrome SxS/User Data"),
+    ("Chromium", "AppData/Local/Chromium/User Data"),
+    ("Edge", "AppData/Local/Microsoft/Edge/User Data"),
+    (
+        "Brave",
+        "AppData/Local/BraveSoftware/Brave-Browser/User Data",
+    ),
+    ("Vivaldi", "AppData/Local/Vivaldi/User Data"),
+    ("Opera", "AppData/Roaming/Opera Software/Opera Stable"),
+    ("Opera GX", "AppData/Roaming/Opera Software/Opera GX Stable"),
+];
+// files that only exist in a profile folder
+const CHROMIUM_PROFILE_FILES: [&str; 2] = ["Preferences", "History"];
+const FIREFOX_BROWSERS: [(&str, &str); 2] = [
+    ("Firefox", "AppData/Roaming/Mozilla/Firefox/Profiles"),
+    ("LibreWolf", "AppData/Roaming/librewolf/Profiles"),
+];
+const FIREFOX_PROFILE_FILES: [&str; 2] = ["prefs.js", "places.sqlite"];
+// web storage and IndexedDB of websites, extensions and the browser itself
+const FIREFOX_STORAGE_FOLDERS: [&str; 3] = ["default", "permanent", "temporary"];
+
+pub struct BrowserProfile {
+    // name of the Windows user profile folder
+    pub user_profile: String,
+    pub browser: String,
+    // name of the browser profile folder
+    pub browser_profile: String,
+    pub path: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct HistoryEntry {
+    pub user_profile: String,
+    pub browser: String,
+    pub browser_profile: String,
+    pub database: String,
+    pub url: String,
+    pub title: Option<String>,
+    pub visit_time: Option<DateTime<Utc>>,
+    pub visit_count: Option<i64>,
+    pub typed_count: Option<i64>,
+    pub transition: Option<String>,
+    pub transition_qualifiers: Vec<String>,
+    pub visit_duration_seconds: Option<f64>,
+    pub from_url: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DownloadEntry {
+    pub user_profile: String,
+    pub browser: String,
+    pub browser_profile: String,
+    pub database: String,
+    // last url of the redirect chain
+    pub url: Option<String>,
+    pub url_chain: Vec<String>,
+    pub referrer: Option<String>,
+    pub tab_url: Option<String>,
+    pub target_path: Option<String>,
+    pub mime_type: Option<String>,
+    pub received_bytes: Option<i64>,
+    pub total_bytes: Option<i64>,
+    pub start_time: Option<DateTime<Utc>>,
+    pub end_time: Option<DateTime<Utc>>,
+    pub state: Option<String>,
+    pub danger_type: Option<String>,
+    pub opened: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BookmarkEntry {
+    pub user_profile: String,
+    pub browser: String,
+    pub browser_profile: String,
+    pub source_file: String,
+    pub url: String,
+    pub title: Option<String>,
+    // folder path of the bookmark, e.g. "menu/Work"
+    pub folder: Option<String>,
+    pub date_added: Option<DateTime<Utc>>,
+    pub last_modified: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FormHistoryEntry {
+    pub user_profile: String,
+    pub browser: String,
+    pub browser_profile: String,
+    pub database: String,
+    pub field_name: String,
+    pub value: String,
+    pub times_used: Option<i64>,
+    pub first_used: Option<DateTime<Utc>>,
+    pub last_used: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SessionEntry {
+    pub user_profile: String,
+    pub browser: String,
+    pub browser_profile: String,
+    pub source_file: String,
+    pub window: usize,
+    pub tab: usize,
+    // navigation entry of the tab history, the current one is the page that was shown
+    pub entry: usize,
+    pub current: bool,
+    // tab or window was already closed when the session was written
+    pub closed: bool,
+    pub url: String,
+    pub title: Option<String>,
+    pub last_accessed: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CookieEntry {
+    pub user_profile: String,
+    pub browser: String,
+    pub browser_profile: String,
+    pub database: String,
+    pub host: String,
+    pub name: String,
+    pub path: Option<String>,
+    // Chromium encrypts the value, it is only reported if stored in plain text
+    pub value: Option<String>,
+    pub encrypted_value_present: bool,
+    pub created: Option<DateTime<Utc>>,
+    pub expires: Option<DateTime<Utc>>,
+    pub last_accessed: Option<DateTime<Utc>>,
+    pub secure: Option<bool>,
+    pub http_only: Option<bool>,
+    pub persistent: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AddressEntry {
+    pub user_profile: String,
+    pub browser: String,
+    pub browser_profile: String,
+    pub database: String,
+    pub guid: String,
+    // e.g. "full_name", "email", "city"
+    pub fields: BTreeMap<String, String>,
+    pub use_count: Option<i64>,
+    pub last_used: Option<DateTime<Utc>>,
+    pub modified: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LoginEntry {
+    pub user_profile: String,
+    pub browser: String,
+    pub browser_profile: String,
+    pub source_file: String,
+    pub origin_url: String,
+    pub action_url: Option<String>,
+    // Firefox encrypts the username as well
+    pub username: Option<String>,
+    // passwords are never decrypted
+    pub password_present: bool,
+    pub times_used: Option<i64>,
+    pub created: Option<DateTime<Utc>>,
+    pub last_used: Option<DateTime<Utc>>,
+    pub password_changed: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExtensionEntry {
+    pub user_profile: String,
+    pub browser: String,
+    pub browser_profile: String,
+    pub source_file: String,
+    pub id: String,
+    pub name: Option<String>,
+    pub version: Option<String>,
+    pub description: Option<String>,
+    pub enabled: Option<bool>,
+    pub installed: Option<DateTime<Utc>>,
+    pub updated: Option<DateTime<Utc>>,
+    // web store, sideloaded, unpacked, ...
+    pub install_source: Option<String>,
+    pub path: Option<String>,
+    pub permissions: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PreferencesEntry {
+    pub user_profile: String,
+    pub browser: String,
+    pub browser_profile: String,
+    pub source_file: String,
+    pub download_directory: Option<String>,
+    pub last_download_directory: Option<String>,
+    pub sync_account: Option<String>,
+    pub homepage: Option<String>,
+    pub startup_urls: Vec<String>,
+    pub search_engine: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WebStorageEntry {
+    pub user_profile: String,
+    pub browser: String,
+    pub browser_profile: String,
+    pub source_file: String,
+    // Local Storage, Session Storage or IndexedDB
+    pub storage_type: String,
+    pub origin: String,
+    // IndexedDB database and object store
+    pub database: Option<String>,
+    pub key: String,
+    pub value: String,
+    // false for overwritten or deleted LevelDB records that were not compacted yet
+    pub live: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FileSystemEntry {
+    pub user_profile: String,
+    pub browser: String,
+    pub browser_profile: String,
+    pub source_file: String,
+    pub origin: Option<String>,
+    // temporary or persistent
+    pub storage_type: String,
+    pub path: String,
+    pub data_file: Option<String>,
+    pub modified: Option<DateTime<Utc>>,
+    pub live: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MediaHistoryEntry {
+    pub user_profile: String,
+    pub browser: String,
+    pub browser_profile: String,
+    pub database: String,
+    // playback (watch time of a url) or session (position in the media of a url)
+    pub record_type: String,
+    pub url: String,
+    pub origin: Option<String>,
+    pub title: Option<String>,
+    pub artist: Option<String>,
+    pub album: Option<String>,
+    pub source_title: Option<String>,
+    pub watch_time_seconds: Option<f64>,
+    pub duration_seconds: Option<f64>,
+    pub position_seconds: Option<f64>,
+    pub has_audio: Option<bool>,
+    pub has_video: Option<bool>,
+    pub last_updated: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UrlParameterEntry {
+    pub user_profile: String,
+    pub browser: String,
+    pub browser_profile: String,
+    pub database: String,
+    pub url: String,
+    pub visit_time: Option<DateTime<Utc>>,
+    pub search_engine: Option<String>,
+    pub search_terms: Option<String>,
+    // time the search results were generated (Google "ei" parameter)
+    pub search_time: Option<DateTime<Utc>>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub tracking_parameters: BTreeMap<String, String>,
+    pub parameters: BTreeMap<String, String>,
+}
+
+/// all profiles of all Chromium based browsers of all users
+pub fn find_chromium_profiles(input: &str) -> Vec<BrowserProfile> {
+    let mut profiles = Vec::new();
+    for user_profile in find_user_profiles(input).unwrap_or_default() {
+        let user_profile_name = file_name(Path::new(&user_profile));
+        for (browser, user_data) in CHROMIUM_BROWSERS {
+            let Some(user_data_path) = find_file_case_insensitive(&user_profile, user_data) else {
+                continue;
+            };
+            let user_data_path = Path::new(&user_data_path);
+            let mut candidates = vec![user_data_path.to_path_buf()];
+            if let Ok(entries) = fs::read_dir(user_data_path) {
+                candidates.extend(entries.flatten().map(|e| e.path()).filter(|p| p.is_dir()));
+            }
+            for candidate in candidates {
+                if !CHROMIUM_PROFILE_FILES
+                    .iter()
+                    .any(|file| candidate.join(file).is_file())
+                {
+                    continue;
+                }
+                profiles.push(BrowserProfile {
+                    user_profile: user_profile_name.clone(),
+                    browser: browser.to_string(),
+                    browser_profile: file_name(&candidate),
+                    path: candidate.to_string_lossy().to_string(),
+                });
+            }
+        }
+    }
+    profiles
+}
+
+/// all profiles of all Firefox based browsers of all users
+pub fn find_firefox_profiles(input: &str) -> Vec<BrowserProfile> {
+    let mut profiles = Vec::new();
+    for user_profile in find_user_profiles(input).unwrap_or_default() {
+        let user_profile_name = file_name(Path::new(&user_profile));
+        for (browser, profiles_folder) in FIREFOX_BROWSERS {
+            let Some(profiles_path) = find_file_case_insensitive(&user_profile, profiles_folder)
+            else {
+                continue;
+            };
+            let Ok(entries) = fs::read_dir(&profiles_path) else {
+                continue;
+            };
+            for candidate in entries.flatten().map(|e| e.path()) {
+                if !FIREFOX_PROFILE_FILES
+                    .iter()
+                    .any(|file| candidate.join(file).is_file())
+                {
+                    continue;
+                }
+                profiles.push(BrowserProfile {
+                    user_profile: user_profile_name.clone(),
+                    browser: browser.to_string(),
+                    browser_profile: file_name(&candidate),
+                    path: candidate.to_string_lossy().to_string(),
+                });
+            }
+        }
+    }
+    profiles
+}
+
+/// origin and folder of all origins in the storage folder of a Firefox profile
+pub fn firefox_storage_origins(profile: &BrowserProfile) -> Vec<(String, String)> {
+    let mut origins = Vec::new();
+    for storage in FIREFOX_STORAGE_FOLDERS {
+        let Ok(entries) = fs::read_dir(format!("{}/storage/{storage}", profile.path)) else {
+            continue;
+        };
+        for origin_path in entries.flatten().map(|e| e.path()).filter(|p| p.is_dir()) {
+            origins.push((
+                firefox_origin(&file_name(&origin_path)),
+                origin_path.to_string_lossy().to_string(),
+            ));
+        }
+    }
+    origins
+}
+
+/// "https+++www.example.com+8080^userContextId=1" -> "https://www.example.com:8080^userContextId=1"
+fn firefox_origin(folder: &str) -> String {
+    let (origin, attributes) = match folder.split_once('^') {
+        Some((origin, attributes)) => (origin, format!("^{attributes}")),
+        None => (folder, String::new()),
+    };
+    let origin = origin.replacen("+++", "://", 1);
+    let origin = match origin.rsplit_once('+') {
+        Some((host, port)) if !port.is_empty() && port.chars().all(|c| c.is_ascii_digit()) => {
+            format!("{host}:{port}")
+        }
+        _ => origin,
+    };
+    format!("{origin}{attributes}")
+}
+
+/// reads a JSON file, some of them start with a byte order mark
+pub fn read_json(path: &str) -> anyhow::Result<serde_json::Value> {
+    let data = fs::read(path)?;
+    let data = data.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(&data);
+    Ok(serde_json::from_slice(data)?)
+}
+
+/// writes the entries to path, nothing is written if there are none
+pub fn write_json<T: Serialize>(path: &str, entries: &[T]) -> anyhow::Result<()> {
+    if entries.is_empty() {
+        return Ok(());
+    }
+    let file = File::create(path)?;
+    let mut writer = BufWriter::new(file);
+    serde_json::to_writer(&mut writer, entries)?;
+    writer.flush()?;
+    Ok(())
+}
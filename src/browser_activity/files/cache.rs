@@ -0,0 +1,583 @@
+// "The cache is where web page components can be stored locally to speed up subsequent visits." - SANS Windows Forensic Analysis Poster, Cache
+// Chromium blockfile cache: <profile>\Cache\index, data_0 - data_3 (block files) and f_* (external files)
+// Chromium simple cache: <profile>\Cache\Cache_Data\<hash>_0 (one file per entry, headers and body)
+// Firefox: %USERPROFILE%\AppData\Local\Mozilla\Firefox\Profiles\<profile>\cache2\entries\<hash>
+// cached bodies are exported (decompressed) to a subfolder of the output path if requested
+// format references: https://www.chromium.org/developers/design-documents/network-stack/disk-cache/
+// and https://github.com/cclgroupltd/ccl_chromium_reader (simple cache)
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::fs::File;
+use std::io::{BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::anyhow;
+use chrono::{DateTime, Datelike, Utc};
+use common::{convert_unix_time, convert_webkit_time, read_u32_le, read_u64_le};
+use flate2::read::{DeflateDecoder, GzDecoder, ZlibDecoder};
+use log::error;
+use serde::Serialize;
+
+use crate::browser_activity::files::browsers::{
+    find_chromium_profiles, find_firefox_profiles, BrowserProfile,
+};
+
+const EXPORT_FOLDER: &str = "browser_cache";
+// cache folders of a Chromium profile, newer versions use Cache_Data
+const CHROMIUM_CACHE_FOLDERS: [&str; 2] = ["Cache/Cache_Data", "Cache"];
+// blockfile cache
+const BLOCKFILE_MAGIC: u32 = 0xC103_CAC3;
+const BLOCKFILE_TABLE_OFFSET: usize = 368;
+const BLOCKFILE_DEFAULT_TABLE_LEN: usize = 0x10000;
+const BLOCKFILE_HEADER_SIZE: usize = 8192;
+const BLOCKFILE_ENTRY_SIZE: usize = 256;
+const BLOCKFILE_INLINE_KEY_OFFSET: usize = 96;
+// simple cache
+const SIMPLE_MAGIC: u64 = 0xFCFB_6D1B_A772_5C30;
+const SIMPLE_EOF_MAGIC: u64 = 0xF4FA_6F45_970D_41D8;
+const SIMPLE_HEADER_SIZE: usize = 24;
+const SIMPLE_EOF_SIZE: usize = 24;
+const SIMPLE_FLAG_HAS_KEY_SHA256: u32 = 2;
+// Firefox cache2, one 2 byte hash per chunk of the content
+const CACHE2_CHUNK_SIZE: usize = 256 * 1024;
+
+#[derive(Debug, Serialize)]
+struct CacheEntry {
+    user_profile: String,
+    browser: String,
+    browser_profile: String,
+    cache_type: String,
+    source_file: String,
+    url: String,
+    key: String,
+    status: Option<String>,
+    content_type: Option<String>,
+    content_encoding: Option<String>,
+    headers: Vec<String>,
+    body_size: usize,
+    request_time: Option<DateTime<Utc>>,
+    response_time: Option<DateTime<Utc>>,
+    created: Option<DateTime<Utc>>,
+    last_used: Option<DateTime<Utc>>,
+    last_modified: Option<DateTime<Utc>>,
+    fetch_count: Option<u32>,
+    exported_file: Option<String>,
+}
+
+/// a cache entry as read from the cache, the body is only kept until it is exported
+#[derive(Default)]
+struct RawCacheEntry {
+    source_file: String,
+    key: String,
+    headers: Vec<String>,
+    body: Vec<u8>,
+    request_time: Option<DateTime<Utc>>,
+    response_time: Option<DateTime<Utc>>,
+    created: Option<DateTime<Utc>>,
+    last_used: Option<DateTime<Utc>>,
+    last_modified: Option<DateTime<Utc>>,
+    fetch_count: Option<u32>,
+}
+
+/// lazily loaded data_N files of a blockfile cache
+struct BlockFiles {
+    folder: PathBuf,
+    files: HashMap<u32, Vec<u8>>,
+}
+
+pub fn get_browser_cache_data(
+    input: &str,
+    outpath: &str,
+    export_bodies: bool,
+) -> anyhow::Result<()> {
+    print!("Working on Browser Cache: ");
+    let export_path = format!("{outpath}/{EXPORT_FOLDER}");
+    if export_bodies {
+        fs::create_dir_all(&export_path)?;
+    }
+    let mut cache_list: Vec<CacheEntry> = Vec::new();
+
+    for profile in find_chromium_profiles(input) {
+        // Opera keeps the profile in Roaming, but the cache in Local
+        let local_path = local_profile_path(&profile.path);
+        let mut seen = HashSet::new();
+        for base in [&profile.path, &local_path] {
+            for cache_folder in CHROMIUM_CACHE_FOLDERS {
+                let folder = Path::new(base).join(cache_folder);
+                if !folder.is_dir() || !seen.insert(folder.clone()) {
+                    continue;
+                }
+                let cache_type =
+                    if folder.join("index").is_file() && folder.join("data_0").is_file() {
+                        "Chromium Blockfile Cache"
+                    } else {
+                        "Chromium Simple Cache"
+                    };
+                let mut add = |raw: RawCacheEntry| {
+                    let export = export_bodies.then_some(export_path.as_str());
+                    cache_list.push(build_entry(
+                        &profile,
+                        cache_type,
+                        raw,
+                        export,
+                        cache_list.len(),
+                    ));
+                };
+                let result = if cache_type == "Chromium Blockfile Cache" {
+                    read_blockfile_cache(&folder, &mut add)
+                } else {
+                    read_simple_cache(&folder, &mut add)
+                };
+                if let Err(err) = result {
+                    error!("Failed to read {}: {err}", folder.display())
+                }
+            }
+        }
+    }
+
+    for profile in find_firefox_profiles(input) {
+        let folder = Path::new(&local_profile_path(&profile.path)).join("cache2/entries");
+        if !folder.is_dir() {
+            continue;
+        }
+        let mut add = |raw: RawCacheEntry| {
+            let export = export_bodies.then_some(export_path.as_str());
+            cache_list.push(build_entry(
+                &profile,
+                "Firefox cache2",
+                raw,
+                export,
+                cache_list.len(),
+            ));
+        };
+        if let Err(err) = read_cache2(&folder, &mut add) {
+            error!("Failed to read {}: {err}", folder.display())
+        }
+    }
+
+    if cache_list.is_empty() {
+        if export_bodies {
+            // no need to keep the empty export folder
+            let _ = fs::remove_dir(&export_path);
+        }
+        println!("Nothing to do here, continuing with next job.");
+        return Ok(());
+    }
+
+    let file = File::create(format!("{outpath}/br_cache.json"))?;
+    let mut writer = BufWriter::new(file);
+    serde_json::to_writer(&mut writer, &cache_list)?;
+    writer.flush()?;
+
+    println!("Done here!");
+    Ok(())
+}
+
+/// "C:/Users/ally/AppData/Roaming/Mozilla/..." -> "C:/Users/ally/AppData/Local/Mozilla/..."
+fn local_profile_path(path: &str) -> String {
+    path.replacen("/AppData/Roaming/", "/AppData/Local/", 1)
+}
+
+fn build_entry(
+    profile: &BrowserProfile,
+    cache_type: &str,
+    raw: RawCacheEntry,
+    export_path: Option<&str>,
+    number: usize,
+) -> CacheEntry {
+    let content_encoding = header_value(&raw.headers, "Content-Encoding");
+    let content_type = header_value(&raw.headers, "Content-Type");
+    let exported_file = export_path
+        .filter(|_| !raw.body.is_empty())
+        .and_then(|path| {
+            let body = decode_body(&raw.body, content_encoding.as_deref());
+            let name = format!(
+                "{}_{}_{number}.{}",
+                profile.browser,
+                profile.browser_profile,
+                extension(content_type.as_deref())
+            )
+            .replace(' ', "_");
+            let file_path = format!("{path}/{name}");
+            match fs::write(&file_path, body) {
+                Ok(()) => Some(file_path),
+                Err(err) => {
+                    error!("Failed to export cache entry {file_path}: {err}");
+                    None
+                }
+            }
+        });
+    CacheEntry {
+        user_profile: profile.user_profile.clone(),
+        browser: profile.browser.clone(),
+        browser_profile: profile.browser_profile.clone(),
+        cache_type: cache_type.to_string(),
+        source_file: raw.source_file,
+        url: key_url(&raw.key),
+        key: raw.key,
+        status: raw
+            .headers
+            .first()
+            .filter(|h| h.starts_with("HTTP"))
+            .cloned(),
+        content_type,
+        content_encoding,
+        body_size: raw.body.len(),
+        headers: raw.headers,
+        request_time: raw.request_time,
+        response_time: raw.response_time,
+        created: raw.created,
+        last_used: raw.last_used,
+        last_modified: raw.last_modified,
+        fetch_count: raw.fetch_count,
+        exported_file,
+    }
+}
+
+/// the index is a hash table of cache addresses, collisions are chained with the "next" address of the entry
+fn read_blockfile_cache(folder: &Path, add: &mut dyn FnMut(RawCacheEntry)) -> anyhow::Result<()> {
+    let index = fs::read(folder.join("index"))?;
+    if read_u32_le(&index, 0) != Some(BLOCKFILE_MAGIC) {
+        return Err(anyhow!("Invalid blockfile cache index!"));
+    }
+    let table_len = match read_u32_le(&index, 28) {
+        Some(0) | None => BLOCKFILE_DEFAULT_TABLE_LEN,
+        Some(len) => len as usize,
+    };
+    let mut block_files = BlockFiles {
+        folder: folder.to_path_buf(),
+        files: HashMap::new(),
+    };
+    let mut seen = HashSet::new();
+    for bucket in 0..table_len {
+        let Some(mut address) = read_u32_le(&index, BLOCKFILE_TABLE_OFFSET + bucket * 4) else {
+            break;
+        };
+        while address != 0 && seen.insert(address) {
+            let Some(entry) = block_files.read(address, BLOCKFILE_ENTRY_SIZE) else {
+                break;
+            };
+            address = read_u32_le(&entry, 4).unwrap_or_default();
+            add(read_blockfile_entry(&mut block_files, &entry));
+        }
+    }
+    Ok(())
+}
+
+/// EntryStore: rankings node, creation time, key and the addresses and sizes of the streams
+fn read_blockfile_entry(block_files: &mut BlockFiles, entry: &[u8]) -> RawCacheEntry {
+    let key_length = read_u32_le(entry, 32).unwrap_or_default() as usize;
+    let long_key = read_u32_le(entry, 36).unwrap_or_default();
+    let key = if long_key != 0 {
+        block_files.read(long_key, key_length).unwrap_or_default()
+    } else {
+        let end = (BLOCKFILE_INLINE_KEY_OFFSET + key_length).min(entry.len());
+        entry[BLOCKFILE_INLINE_KEY_OFFSET.min(end)..end].to_vec()
+    };
+    // stream 0 contains the response info, stream 1 the body
+    let stream = |block_files: &mut BlockFiles, index: usize| {
+        let size = read_u32_le(entry, 40 + index * 4).unwrap_or_default() as usize;
+        let address = read_u32_le(entry, 56 + index * 4).unwrap_or_default();
+        block_files.read(address, size).unwrap_or_default()
+    };
+    let (headers, request_time, response_time) = parse_response_info(&stream(block_files, 0));
+    let body = stream(block_files, 1);
+    // the rankings node has last used and last modified time
+    let rankings = read_u32_le(entry, 8)
+        .and_then(|address| block_files.read(address, 16))
+        .unwrap_or_default();
+    let webkit_time =
+        |data: &[u8], offset| read_u64_le(data, offset).and_then(|t| convert_webkit_time(t as i64));
+    RawCacheEntry {
+        source_file: block_files.folder.to_string_lossy().to_string(),
+        key: String::from_utf8_lossy(&key)
+            .trim_end_matches('\0')
+            .to_string(),
+        headers,
+        body,
+        request_time,
+        response_time,
+        created: webkit_time(entry, 24),
+        last_used: webkit_time(&rankings, 0),
+        last_modified: webkit_time(&rankings, 8),
+        fetch_count: None,
+    }
+}
+
+impl BlockFiles {
+    /// reads size bytes at a cache address, in an external file (f_xxxxxx) or a block file (data_N)
+    fn read(&mut self, address: u32, size: usize) -> Option<Vec<u8>> {
+        // the highest bit marks an initialized address
+        if address & 0x8000_0000 == 0 {
+            return None;
+        }
+        let file_type = (address >> 28) & 0x7;
+        if file_type == 0 {
+            let data =
+                fs::read(self.folder.join(format!("f_{:06x}", address & 0x0FFF_FFFF))).ok()?;
+            return Some(data[..size.min(data.len())].to_vec());
+        }
+        let block_size = match file_type {
+            1 => 36,
+            2 => 256,
+            3 => 1024,
+            4 => 4096,
+            5 => 8,
+            6 => 104,
+            7 => 48,
+            _ => return None,
+        };
+        let file_number = (address >> 16) & 0xFF;
+        let start_block = (address & 0xFFFF) as usize;
+        if !self.files.contains_key(&file_number) {
+            let data = fs::read(self.folder.join(format!("data_{file_number}"))).ok()?;
+            self.files.insert(file_number, data);
+        }
+        let data = self.files.get(&file_number)?;
+        let offset = BLOCKFILE_HEADER_SIZE + start_block * block_size;
+        let end = (offset + size).min(data.len());
+        data.get(offset..end).map(<[u8]>::to_vec)
+    }
+}
+
+/// every entry is a file named <16 hex digits>_0
+fn read_simple_cache(folder: &Path, add: &mut dyn FnMut(RawCacheEntry)) -> anyhow::Result<()> {
+    for file in fs::read_dir(folder)? {
+        let path = file?.path();
+        let name = path
+            .file_name()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let Some(hash) = name.strip_suffix("_0") else {
+            continue;
+        };
+        if hash.len() != 16 || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+            continue;
+        }
+        match read_simple_cache_entry(&path) {
+            Ok(raw) => add(raw),
+            Err(err) => error!("Failed to read {}: {err}", path.display()),
+        }
+    }
+    Ok(())
+}
+
+/// header and key, stream 1 (body) and its EOF record, stream 0 (response info), key hash and its EOF record
+fn read_simple_cache_entry(path: &Path) -> anyhow::Result<RawCacheEntry> {
+    let data = fs::read(path)?;
+    if read_u64_le(&data, 0) != Some(SIMPLE_MAGIC) {
+        return Err(anyhow!("Invalid simple cache entry!"));
+    }
+    let key_length = read_u32_le(&data, 12).ok_or(anyhow!("Header is truncated!"))? as usize;
+    let key = data
+        .get(SIMPLE_HEADER_SIZE..SIMPLE_HEADER_SIZE + key_length)
+        .ok_or(anyhow!("Key is truncated!"))?;
+
+    let eof0 = data
+        .len()
+        .checked_sub(SIMPLE_EOF_SIZE)
+        .ok_or(anyhow!("Entry is truncated!"))?;
+    let (flags, stream0_size) = read_simple_eof(&data, eof0)?;
+    let stream0_end = if flags & SIMPLE_FLAG_HAS_KEY_SHA256 != 0 {
+        eof0.saturating_sub(32)
+    } else {
+        eof0
+    };
+    let stream0_start = stream0_end
+        .checked_sub(stream0_size)
+        .ok_or(anyhow!("Stream 0 is truncated!"))?;
+    let eof1 = stream0_start
+        .checked_sub(SIMPLE_EOF_SIZE)
+        .ok_or(anyhow!("Stream 1 is truncated!"))?;
+    let (_, stream1_size) = read_simple_eof(&data, eof1)?;
+    let body_start = SIMPLE_HEADER_SIZE + key_length;
+    let body = data
+        .get(body_start..body_start + stream1_size)
+        .unwrap_or_default();
+
+    let (headers, request_time, response_time) =
+        parse_response_info(&data[stream0_start..stream0_end]);
+    Ok(RawCacheEntry {
+        source_file: path.to_string_lossy().to_string(),
+        key: String::from_utf8_lossy(key).to_string(),
+        headers,
+        body: body.to_vec(),
+        request_time,
+        response_time,
+        ..Default::default()
+    })
+}
+
+/// EOF record: magic, flags, crc32 and size of the stream before it
+fn read_simple_eof(data: &[u8], offset: usize) -> anyhow::Result<(u32, usize)> {
+    if read_u64_le(data, offset) != Some(SIMPLE_EOF_MAGIC) {
+        return Err(anyhow!("Invalid EOF record at {offset}!"));
+    }
+    let flags = read_u32_le(data, offset + 8).unwrap_or_default();
+    let size = read_u32_le(data, offset + 16).ok_or(anyhow!("EOF record is truncated!"))?;
+    Ok((flags, size as usize))
+}
+
+/// HttpResponseInfo pickle: payload size, flags, (extra flags), request time, response time, headers
+/// the raw headers are separated by "\0"
+fn parse_response_info(data: &[u8]) -> (Vec<String>, Option<DateTime<Utc>>, Option<DateTime<Utc>>) {
+    let plausible = |time: &DateTime<Utc>| (1990..=2100).contains(&time.year());
+    let time_at = |offset| {
+        read_u64_le(data, offset)
+            .and_then(|t| convert_webkit_time(t as i64))
+            .filter(plausible)
+    };
+    // newer versions write extra flags after the flags
+    let (request_time, response_time) = match time_at(8) {
+        Some(request_time) => (Some(request_time), time_at(16)),
+        None => (time_at(12), time_at(20)),
+    };
+    let headers = find(data, b"HTTP/")
+        .map(|start| {
+            let raw = &data[start..];
+            let end = find(raw, b"\0\0").unwrap_or(raw.len());
+            String::from_utf8_lossy(&raw[..end])
+                .split('\0')
+                .filter(|h| !h.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default();
+    (headers, request_time, response_time)
+}
+
+fn read_cache2(folder: &Path, add: &mut dyn FnMut(RawCacheEntry)) -> anyhow::Result<()> {
+    for file in fs::read_dir(folder)? {
+        let path = file?.path();
+        if !path.is_file() {
+            continue;
+        }
+        match read_cache2_entry(&path) {
+            Ok(raw) => add(raw),
+            Err(err) => error!("Failed to read {}: {err}", path.display()),
+        }
+    }
+    Ok(())
+}
+
+/// content, chunk hashes, metadata (big endian) with key and elements, offset of the metadata in the last 4 bytes
+fn read_cache2_entry(path: &Path) -> anyhow::Result<RawCacheEntry> {
+    let data = fs::read(path)?;
+    let read_u32_be = |offset: usize| {
+        data.get(offset..offset + 4)
+            .map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+    };
+    let metadata_offset = data
+        .len()
+        .checked_sub(4)
+        .and_then(read_u32_be)
+        .ok_or(anyhow!("Entry is truncated!"))? as usize;
+    // metadata hash and one hash per chunk of the content
+    let header = metadata_offset + 4 + metadata_offset.div_ceil(CACHE2_CHUNK_SIZE) * 2;
+    let version = read_u32_be(header).ok_or(anyhow!("Metadata is truncated!"))?;
+    let key_size = read_u32_be(header + 24).ok_or(anyhow!("Metadata is truncated!"))? as usize;
+    // version 2+ has additional flags
+    let key_start = header + if version >= 2 { 32 } else { 28 };
+    let key = data
+        .get(key_start..key_start + key_size)
+        .ok_or(anyhow!("Key is truncated!"))?;
+
+    // elements are "name\0value\0" pairs, "response-head" contains the response headers
+    let elements_end = data.len() - 4;
+    let elements = data
+        .get(key_start + key_size + 1..elements_end)
+        .unwrap_or_default();
+    let mut parts = elements.split(|b| *b == 0);
+    let mut headers = Vec::new();
+    while let (Some(name), Some(value)) = (parts.next(), parts.next()) {
+        if name == b"response-head" {
+            headers = String::from_utf8_lossy(value)
+                .split("\r\n")
+                .filter(|h| !h.is_empty())
+                .map(str::to_string)
+                .collect();
+        }
+    }
+
+    let unix_time = |offset| read_u32_be(offset).and_then(|t| convert_unix_time(t as i64));
+    Ok(RawCacheEntry {
+        source_file: path.to_string_lossy().to_string(),
+        key: String::from_utf8_lossy(key).to_string(),
+        headers,
+        body: data.get(..metadata_offset).unwrap_or_default().to_vec(),
+        fetch_count: read_u32_be(header + 4),
+        last_used: unix_time(header + 8),
+        last_modified: unix_time(header + 12),
+        ..Default::default()
+    })
+}
+
+/// keys contain more than the url, e.g. "1/0/_dk_https://a.com https://a.com https://a.com/x.js" (Chromium)
+/// or "O^partitionKey=...,a,:https://a.com/x.js" (Firefox)
+fn key_url(key: &str) -> String {
+    let last = key.rsplit(' ').next().unwrap_or(key);
+    match last.find(":http") {
+        Some(position) => last[position + 1..].to_string(),
+        None => last.to_string(),
+    }
+}
+
+fn header_value(headers: &[String], name: &str) -> Option<String> {
+    headers.iter().find_map(|header| {
+        let (header_name, value) = header.split_once(':')?;
+        header_name
+            .trim()
+            .eq_ignore_ascii_case(name)
+            .then(|| value.trim().to_string())
+    })
+}
+
+/// decompresses the body according to the Content-Encoding, the raw body is kept if that fails
+fn decode_body(body: &[u8], encoding: Option<&str>) -> Vec<u8> {
+    let mut decoded = Vec::new();
+    let result = match encoding.map(|e| e.trim().to_lowercase()).as_deref() {
+        Some("gzip" | "x-gzip") => GzDecoder::new(body).read_to_end(&mut decoded),
+        Some("deflate") => ZlibDecoder::new(body)
+            .read_to_end(&mut decoded)
+            .or_else(|_| {
+                decoded.clear();
+                DeflateDecoder::new(body).read_to_end(&mut decoded)
+            }),
+        Some("br") => brotli_decompressor::Decompressor::new(body, 4096).read_to_end(&mut decoded),
+        _ => return body.to_vec(),
+    };
+    match result {
+        Ok(_) => decoded,
+        Err(_) => body.to_vec(),
+    }
+}
+
+fn extension(content_type: Option<&str>) -> &'static str {
+    let mime = content_type
+        .and_then(|c| c.split(';').next())
+        .map(|m| m.trim().to_lowercase())
+        .unwrap_or_default();
+    match mime.as_str() {
+        "text/html" => "html",
+        "text/css" => "css",
+        "text/plain" => "txt",
+        "text/javascript" | "application/javascript" | "application/x-javascript" => "js",
+        "application/json" => "json",
+        "image/jpeg" => "jpg",
+        "image/png" => "png",
+        "image/gif" => "gif",
+        "image/webp" => "webp",
+        "image/svg+xml" => "svg",
+        "image/x-icon" | "image/vnd.microsoft.icon" => "ico",
+        "font/woff2" => "woff2",
+        "font/woff" => "woff",
+        "video/mp4" => "mp4",
+        "application/pdf" => "pdf",
+        _ => "bin",
+    }
+}
+
+fn find(data: &[u8], pattern: &[u8]) -> Option<usize> {
+    data.windows(pattern.len()).position(|w| w == pattern)
+}
@@ -0,0 +1,207 @@
+// "Records websites visited by date and time. Details stored for each local user account. Records number of times visited (frequency)." - SANS Windows Forensic Analysis Poster, History and Download History
+// "Chromium browsers store a complete download history, including the local path the file was saved to." - SANS Windows Forensic Analysis Poster, Browser Downloads
+// <Chromium profile>\History (SQLite): urls, visits, downloads, downloads_url_chains
+// all timestamps are WebKit time (microseconds since 1601), visit_duration is in microseconds
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use common::{convert_webkit_time, lookup};
+use log::error;
+
+use crate::browser_activity::files::browsers::{
+    find_chromium_profiles, write_json, BrowserProfile, DownloadEntry, HistoryEntry,
+};
+use crate::formats::sqlite::{integer_column, text_column, with_sqlite_copy};
+
+// core transition types, the lower byte of the transition
+const CORE_TRANSITIONS: [&str; 11] = [
+    "LINK",
+    "TYPED",
+    "AUTO_BOOKMARK",
+    "AUTO_SUBFRAME",
+    "MANUAL_SUBFRAME",
+    "GENERATED",
+    "AUTO_TOPLEVEL",
+    "FORM_SUBMIT",
+    "RELOAD",
+    "KEYWORD",
+    "KEYWORD_GENERATED",
+];
+// transition qualifiers, the upper bits of the transition
+const TRANSITION_QUALIFIERS: [(u32, &str); 8] = [
+    (0x0100_0000, "FORWARD_BACK"),
+    (0x0200_0000, "FROM_ADDRESS_BAR"),
+    (0x0400_0000, "HOME_PAGE"),
+    (0x0800_0000, "FROM_API"),
+    (0x1000_0000, "CHAIN_START"),
+    (0x2000_0000, "CHAIN_END"),
+    (0x4000_0000, "CLIENT_REDIRECT"),
+    (0x8000_0000, "SERVER_REDIRECT"),
+];
+const DOWNLOAD_STATES: [&str; 5] = [
+    "IN_PROGRESS",
+    "COMPLETE",
+    "CANCELLED",
+    "INTERRUPTED",
+    "INTERRUPTED",
+];
+const DANGER_TYPES: [&str; 20] = [
+    "NOT_DANGEROUS",
+    "DANGEROUS_FILE",
+    "DANGEROUS_URL",
+    "DANGEROUS_CONTENT",
+    "MAYBE_DANGEROUS_CONTENT",
+    "UNCOMMON_CONTENT",
+    "USER_VALIDATED",
+    "DANGEROUS_HOST",
+    "POTENTIALLY_UNWANTED",
+    "ALLOWLISTED_BY_POLICY",
+    "ASYNC_SCANNING",
+    "BLOCKED_PASSWORD_PROTECTED",
+    "BLOCKED_TOO_LARGE",
+    "SENSITIVE_CONTENT_WARNING",
+    "SENSITIVE_CONTENT_BLOCK",
+    "DEEP_SCANNED_SAFE",
+    "DEEP_SCANNED_OPENED_DANGEROUS",
+    "PROMPT_FOR_SCANNING",
+    "BLOCKED_UNSUPPORTED_FILETYPE",
+    "DANGEROUS_ACCOUNT_COMPROMISE",
+];
+
+pub fn get_chromium_history_data(input: &str, outpath: &str) -> anyhow::Result<()> {
+    print!("Working on Chromium History and Downloads: ");
+    let mut history_list: Vec<HistoryEntry> = Vec::new();
+    let mut download_list: Vec<DownloadEntry> = Vec::new();
+
+    for profile in find_chromium_profiles(input) {
+        let database = format!("{}/History", profile.path);
+        if !Path::new(&database).is_file() {
+            continue;
+        }
+        let result = with_sqlite_copy(&database, |connection| {
+            read_history(connection, &profile, &database, &mut history_list)?;
+            read_downloads(connection, &profile, &database, &mut download_list)
+        });
+        if let Err(err) = result {
+            error!("Failed to read {database}: {err}")
+        }
+    }
+
+    if history_list.is_empty() && download_list.is_empty() {
+        println!("Nothing to do here, continuing with next job.");
+        return Ok(());
+    }
+
+    write_json(
+        &format!("{outpath}/br_chromium_history.json"),
+        &history_list,
+    )?;
+    write_json(
+        &format!("{outpath}/br_chromium_downloads.json"),
+        &download_list,
+    )?;
+
+    println!("Done here!");
+    Ok(())
+}
+
+/// one entry per visit, also used by the url parameter analysis
+pub fn read_history(
+    connection: &rusqlite::Connection,
+    profile: &BrowserProfile,
+    database: &str,
+    history_list: &mut Vec<HistoryEntry>,
+) -> anyhow::Result<()> {
+    let mut statement = connection.prepare(
+        "SELECT urls.url, urls.title, urls.visit_count, urls.typed_count, visits.visit_time, \
+         visits.transition, visits.visit_duration, from_urls.url AS from_url \
+         FROM visits JOIN urls ON visits.url = urls.id \
+         LEFT JOIN visits from_visits ON visits.from_visit = from_visits.id \
+         LEFT JOIN urls from_urls ON from_visits.url = from_urls.id",
+    )?;
+    let mut rows = statement.query([])?;
+    while let Some(row) = rows.next()? {
+        let transition = integer_column(row, "transition").map(|t| t as u32);
+        history_list.push(HistoryEntry {
+            user_profile: profile.user_profile.clone(),
+            browser: profile.browser.clone(),
+            browser_profile: profile.browser_profile.clone(),
+            database: database.to_string(),
+            url: text_column(row, "url").unwrap_or_default(),
+            title: text_column(row, "title").filter(|t| !t.is_empty()),
+            visit_time: integer_column(row, "visit_time").and_then(convert_webkit_time),
+            visit_count: integer_column(row, "visit_count"),
+            typed_count: integer_column(row, "typed_count"),
+            transition: transition.map(|t| core_transition(t).to_string()),
+            transition_qualifiers: transition.map(transition_qualifiers).unwrap_or_default(),
+            visit_duration_seconds: integer_column(row, "visit_duration")
+                .filter(|d| *d > 0)
+                .map(|d| d as f64 / 1_000_000.0),
+            from_url: text_column(row, "from_url"),
+        });
+    }
+    Ok(())
+}
+
+fn read_downloads(
+    connection: &rusqlite::Connection,
+    profile: &BrowserProfile,
+    database: &str,
+    download_list: &mut Vec<DownloadEntry>,
+) -> anyhow::Result<()> {
+    // redirect chain of every download, ordered by chain_index
+    let mut url_chains: HashMap<i64, Vec<String>> = HashMap::new();
+    let mut statement =
+        connection.prepare("SELECT id, url FROM downloads_url_chains ORDER BY id, chain_index")?;
+    let mut rows = statement.query([])?;
+    while let Some(row) = rows.next()? {
+        if let (Some(id), Some(url)) = (integer_column(row, "id"), text_column(row, "url")) {
+            url_chains.entry(id).or_default().push(url);
+        }
+    }
+
+    // older schema versions do not have all columns, so they are read by name
+    let mut statement = connection.prepare("SELECT * FROM downloads")?;
+    let mut rows = statement.query([])?;
+    while let Some(row) = rows.next()? {
+        let url_chain = integer_column(row, "id")
+            .and_then(|id| url_chains.remove(&id))
+            .unwrap_or_default();
+        download_list.push(DownloadEntry {
+            user_profile: profile.user_profile.clone(),
+            browser: profile.browser.clone(),
+            browser_profile: profile.browser_profile.clone(),
+            database: database.to_string(),
+            url: url_chain.last().cloned(),
+            url_chain,
+            referrer: text_column(row, "referrer").filter(|r| !r.is_empty()),
+            tab_url: text_column(row, "tab_url").filter(|u| !u.is_empty()),
+            target_path: text_column(row, "target_path").filter(|p| !p.is_empty()),
+            mime_type: text_column(row, "mime_type").filter(|m| !m.is_empty()),
+            received_bytes: integer_column(row, "received_bytes"),
+            total_bytes: integer_column(row, "total_bytes"),
+            start_time: integer_column(row, "start_time").and_then(convert_webkit_time),
+            end_time: integer_column(row, "end_time").and_then(convert_webkit_time),
+            state: integer_column(row, "state").map(|s| lookup(&DOWNLOAD_STATES, s)),
+            danger_type: integer_column(row, "danger_type").map(|d| lookup(&DANGER_TYPES, d)),
+            opened: integer_column(row, "opened").map(|o| o != 0),
+        });
+    }
+    Ok(())
+}
+
+fn core_transition(transition: u32) -> &'static str {
+    CORE_TRANSITIONS
+        .get((transition & 0xFF) as usize)
+        .copied()
+        .unwrap_or("UNKNOWN")
+}
+
+fn transition_qualifiers(transition: u32) -> Vec<String> {
+    TRANSITION_QUALIFIERS
+        .iter()
+        .filter(|(flag, _)| transition & flag != 0)
+        .map(|(_, name)| name.to_string())
+        .collect()
+}
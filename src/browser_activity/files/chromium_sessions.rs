@@ -0,0 +1,246 @@
+// "Automatic crash recovery features built into the browser." - SANS Windows Forensic Analysis Poster, Session Restore
+// <Chromium profile>\Sessions\Session_<time> (open windows and tabs) and Tabs_<time> (recently closed tabs)
+// older versions: <Chromium profile>\Current Session, Last Session, Current Tabs and Last Tabs
+// SNSS command files: "SNSS" and a version, then commands of u16 size, u8 id and payload,
+// navigations are pickles (u32 payload size, 4 byte aligned fields), times are WebKit time
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use common::convert_webkit_time;
+use log::error;
+
+use crate::browser_activity::files::browsers::{
+    find_chromium_profiles, write_json, BrowserProfile, SessionEntry,
+};
+use crate::formats::pickle::Pickle;
+
+const LEGACY_SESSION_FILES: [&str; 4] = [
+    "Current Session",
+    "Last Session",
+    "Current Tabs",
+    "Last Tabs",
+];
+
+// commands of Session files
+const SESSION_SET_TAB_WINDOW: u8 = 0;
+const SESSION_UPDATE_TAB_NAVIGATION: u8 = 6;
+const SESSION_SET_SELECTED_NAVIGATION_INDEX: u8 = 7;
+const SESSION_TAB_CLOSED: u8 = 16;
+const SESSION_WINDOW_CLOSED: u8 = 17;
+const SESSION_LAST_ACTIVE_TIME: u8 = 21;
+// commands of Tabs files
+const TABS_UPDATE_TAB_NAVIGATION: u8 = 1;
+const TABS_SELECTED_NAVIGATION_IN_TAB: u8 = 4;
+
+pub fn get_chromium_sessions_data(input: &str, outpath: &str) -> anyhow::Result<()> {
+    print!("Working on Chromium Session Restore: ");
+    let mut session_list: Vec<SessionEntry> = Vec::new();
+
+    for profile in find_chromium_profiles(input) {
+        let mut session_files: Vec<String> = fs::read_dir(format!("{}/Sessions", profile.path))
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .map(|e| e.path())
+                    .filter(|p| p.is_file())
+                    .map(|p| p.to_string_lossy().to_string())
+                    .collect()
+            })
+            .unwrap_or_default();
+        session_files.sort();
+        session_files.extend(
+            LEGACY_SESSION_FILES
+                .iter()
+                .map(|file| format!("{}/{file}", profile.path))
+                .filter(|path| Path::new(path).is_file()),
+        );
+        for path in session_files {
+            let file_name = Path::new(&path)
+                .file_name()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let tabs_file = if file_name.starts_with("Session_") || file_name.ends_with("Session") {
+                false
+            } else if file_name.starts_with("Tabs_") || file_name.ends_with("Tabs") {
+                true
+            } else {
+                continue;
+            };
+            if let Err(err) = read_snss_file(&profile, &path, tabs_file, &mut session_list) {
+                error!("Failed to read {path}: {err}")
+            }
+        }
+    }
+
+    if session_list.is_empty() {
+        println!("Nothing to do here, continuing with next job.");
+        return Ok(());
+    }
+
+    write_json(
+        &format!("{outpath}/br_chromium_sessions.json"),
+        &session_list,
+    )?;
+
+    println!("Done here!");
+    Ok(())
+}
+
+struct Navigation {
+    tab_id: i32,
+    index: i32,
+    url: String,
+    title: String,
+    timestamp: Option<DateTime<Utc>>,
+}
+
+fn read_snss_file(
+    profile: &BrowserProfile,
+    path: &str,
+    tabs_file: bool,
+    session_list: &mut Vec<SessionEntry>,
+) -> anyhow::Result<()> {
+    let data = fs::read(path)?;
+    if data.len() < 8 || &data[0..4] != b"SNSS" {
+        anyhow::bail!("not a SNSS file");
+    }
+
+    // navigations by tab and index, the last command for a navigation wins
+    let mut navigations: HashMap<(i32, i32), Navigation> = HashMap::new();
+    let mut tab_windows: HashMap<i32, i32> = HashMap::new();
+    let mut selected_index: HashMap<i32, i32> = HashMap::new();
+    let mut last_active: HashMap<i32, DateTime<Utc>> = HashMap::new();
+    let mut closed_tabs: HashSet<i32> = HashSet::new();
+    let mut closed_windows: HashSet<i32> = HashSet::new();
+
+    let mut offset = 8;
+    while offset + 3 <= data.len() {
+        let size = u16::from_le_bytes([data[offset], data[offset + 1]]) as usize;
+        if size == 0 || offset + 2 + size > data.len() {
+            break;
+        }
+        let id = data[offset + 2];
+        let payload = &data[offset + 3..offset + 2 + size];
+        offset += 2 + size;
+
+        match (tabs_file, id) {
+            (false, SESSION_UPDATE_TAB_NAVIGATION) | (true, TABS_UPDATE_TAB_NAVIGATION) => {
+                if let Some(navigation) = read_navigation(payload) {
+                    navigations.insert((navigation.tab_id, navigation.index), navigation);
+                }
+            }
+            (false, SESSION_SET_TAB_WINDOW) => {
+                if let (Some(window_id), Some(tab_id)) =
+                    (read_i32(payload, 0), read_i32(payload, 4))
+                {
+                    tab_windows.insert(tab_id, window_id);
+                }
+            }
+            (false, SESSION_SET_SELECTED_NAVIGATION_INDEX) => {
+                if let (Some(tab_id), Some(index)) = (read_i32(payload, 0), read_i32(payload, 4)) {
+                    selected_index.insert(tab_id, index);
+                }
+            }
+            // pickled, but the pickle header is followed by the same fields
+            (true, TABS_SELECTED_NAVIGATION_IN_TAB) => {
+                if let (Some(tab_id), Some(index)) = (read_i32(payload, 4), read_i32(payload, 8)) {
+                    selected_index.insert(tab_id, index);
+                }
+            }
+            (false, SESSION_TAB_CLOSED) => {
+                if let Some(tab_id) = read_i32(payload, 0) {
+                    closed_tabs.insert(tab_id);
+                }
+            }
+            (false, SESSION_WINDOW_CLOSED) => {
+                if let Some(window_id) = read_i32(payload, 0) {
+                    closed_windows.insert(window_id);
+                }
+            }
+            // the time is 8 byte aligned after the tab id
+            (false, SESSION_LAST_ACTIVE_TIME) => {
+                if let (Some(tab_id), Some(time)) = (read_i32(payload, 0), read_i64(payload, 8)) {
+                    if let Some(time) = convert_webkit_time(time) {
+                        last_active.insert(tab_id, time);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut navigations: Vec<Navigation> = navigations.into_values().collect();
+    navigations.sort_by_key(|n| (tab_windows.get(&n.tab_id).copied(), n.tab_id, n.index));
+    for navigation in navigations {
+        let window_id = tab_windows
+            .get(&navigation.tab_id)
+            .copied()
+            .unwrap_or_default();
+        // every entry of a Tabs file is a closed tab
+        let closed = tabs_file
+            || closed_tabs.contains(&navigation.tab_id)
+            || closed_windows.contains(&window_id);
+        session_list.push(SessionEntry {
+            user_profile: profile.user_profile.clone(),
+            browser: profile.browser.clone(),
+            browser_profile: profile.browser_profile.clone(),
+            source_file: path.to_string(),
+            window: usize::try_from(window_id).unwrap_or_default(),
+            tab: usize::try_from(navigation.tab_id).unwrap_or_default(),
+            entry: usize::try_from(navigation.index).unwrap_or_default(),
+            current: selected_index.get(&navigation.tab_id) == Some(&navigation.index),
+            closed,
+            url: navigation.url,
+            title: Some(navigation.title).filter(|t| !t.is_empty()),
+            last_accessed: last_active
+                .get(&navigation.tab_id)
+                .copied()
+                .or(navigation.timestamp),
+        });
+    }
+    Ok(())
+}
+
+/// tab id, index, url, title, page state, transition, type mask, referrer, referrer policy,
+/// original request url, user agent override and timestamp of a pickled navigation
+fn read_navigation(payload: &[u8]) -> Option<Navigation> {
+    let mut pickle = Pickle::new(payload);
+    let tab_id = pickle.int()?;
+    let index = pickle.int()?;
+    let url = pickle.string()?;
+    let title = pickle.string16().unwrap_or_default();
+    // older versions end earlier, the timestamp is optional
+    let timestamp = (|| {
+        pickle.string()?;
+        pickle.int()?;
+        pickle.int()?;
+        pickle.string()?;
+        pickle.int()?;
+        pickle.string()?;
+        pickle.int()?;
+        pickle.int64()
+    })()
+    .and_then(convert_webkit_time);
+    Some(Navigation {
+        tab_id,
+        index,
+        url,
+        title,
+        timestamp,
+    })
+}
+
+fn read_i32(data: &[u8], offset: usize) -> Option<i32> {
+    Some(i32::from_le_bytes(
+        data.get(offset..offset + 4)?.try_into().ok()?,
+    ))
+}
+
+fn read_i64(data: &[u8], offset: usize) -> Option<i64> {
+    Some(i64::from_le_bytes(
+        data.get(offset..offset + 8)?.try_into().ok()?,
+    ))
+}
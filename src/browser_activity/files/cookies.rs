@@ -0,0 +1,135 @@
+// "Cookies provide insight into what websites have been visited and what activities might have taken place there." - SANS Windows Forensic Analysis Poster, Cookies
+// <Chromium profile>\Network\Cookies (older versions: <Chromium profile>\Cookies), WebKit time
+// <Firefox profile>\cookies.sqlite, PRTime, expiry in seconds (milliseconds in newer versions)
+
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use common::{convert_unix_time, convert_unix_time_micros, convert_webkit_time};
+use log::error;
+
+use crate::browser_activity::files::browsers::{
+    find_chromium_profiles, find_firefox_profiles, write_json, BrowserProfile, CookieEntry,
+};
+use crate::formats::sqlite::{integer_column, text_column, with_sqlite_copy};
+
+const CHROMIUM_COOKIE_FILES: [&str; 2] = ["Network/Cookies", "Cookies"];
+
+pub fn get_cookies_data(input: &str, outpath: &str) -> anyhow::Result<()> {
+    print!("Working on Browser Cookies: ");
+    let mut cookie_list: Vec<CookieEntry> = Vec::new();
+
+    for profile in find_chromium_profiles(input) {
+        // the old location is only used if the new one does not exist
+        let Some(database) = CHROMIUM_COOKIE_FILES
+            .iter()
+            .map(|file| format!("{}/{file}", profile.path))
+            .find(|path| Path::new(path).is_file())
+        else {
+            continue;
+        };
+        if let Err(err) = read_chromium_cookies(&profile, &database, &mut cookie_list) {
+            error!("Failed to read {database}: {err}")
+        }
+    }
+    for profile in find_firefox_profiles(input) {
+        let database = format!("{}/cookies.sqlite", profile.path);
+        if !Path::new(&database).is_file() {
+            continue;
+        }
+        if let Err(err) = read_firefox_cookies(&profile, &database, &mut cookie_list) {
+            error!("Failed to read {database}: {err}")
+        }
+    }
+
+    if cookie_list.is_empty() {
+        println!("Nothing to do here, continuing with next job.");
+        return Ok(());
+    }
+
+    write_json(&format!("{outpath}/br_cookies.json"), &cookie_list)?;
+
+    println!("Done here!");
+    Ok(())
+}
+
+fn read_chromium_cookies(
+    profile: &BrowserProfile,
+    database: &str,
+    cookie_list: &mut Vec<CookieEntry>,
+) -> anyhow::Result<()> {
+    with_sqlite_copy(database, |connection| {
+        let mut statement = connection.prepare("SELECT * FROM cookies")?;
+        let mut rows = statement.query([])?;
+        while let Some(row) = rows.next()? {
+            // the flags lost their "is_" prefix in newer versions
+            let flag = |names: [&str; 2]| {
+                names
+                    .iter()
+                    .find_map(|name| integer_column(row, name))
+                    .map(|f| f != 0)
+            };
+            cookie_list.push(CookieEntry {
+                user_profile: profile.user_profile.clone(),
+                browser: profile.browser.clone(),
+                browser_profile: profile.browser_profile.clone(),
+                database: database.to_string(),
+                host: text_column(row, "host_key").unwrap_or_default(),
+                name: text_column(row, "name").unwrap_or_default(),
+                path: text_column(row, "path"),
+                value: text_column(row, "value").filter(|v| !v.is_empty()),
+                encrypted_value_present: text_column(row, "encrypted_value")
+                    .is_some_and(|v| !v.is_empty()),
+                created: integer_column(row, "creation_utc").and_then(convert_webkit_time),
+                expires: integer_column(row, "expires_utc").and_then(convert_webkit_time),
+                last_accessed: integer_column(row, "last_access_utc").and_then(convert_webkit_time),
+                secure: flag(["is_secure", "secure"]),
+                http_only: flag(["is_httponly", "httponly"]),
+                persistent: flag(["is_persistent", "persistent"]),
+            });
+        }
+        Ok(())
+    })
+}
+
+fn read_firefox_cookies(
+    profile: &BrowserProfile,
+    database: &str,
+    cookie_list: &mut Vec<CookieEntry>,
+) -> anyhow::Result<()> {
+    with_sqlite_copy(database, |connection| {
+        let mut statement = connection.prepare("SELECT * FROM moz_cookies")?;
+        let mut rows = statement.query([])?;
+        while let Some(row) = rows.next()? {
+            cookie_list.push(CookieEntry {
+                user_profile: profile.user_profile.clone(),
+                browser: profile.browser.clone(),
+                browser_profile: profile.browser_profile.clone(),
+                database: database.to_string(),
+                host: text_column(row, "host").unwrap_or_default(),
+                name: text_column(row, "name").unwrap_or_default(),
+                path: text_column(row, "path"),
+                value: text_column(row, "value").filter(|v| !v.is_empty()),
+                encrypted_value_present: false,
+                created: integer_column(row, "creationTime").and_then(convert_unix_time_micros),
+                expires: integer_column(row, "expiry").and_then(firefox_expiry),
+                last_accessed: integer_column(row, "lastAccessed")
+                    .and_then(convert_unix_time_micros),
+                secure: integer_column(row, "isSecure").map(|f| f != 0),
+                http_only: integer_column(row, "isHttpOnly").map(|f| f != 0),
+                // Firefox does not keep session cookies on disk
+                persistent: Some(true),
+            });
+        }
+        Ok(())
+    })
+}
+
+/// seconds in older versions, milliseconds in newer ones (anything after the year 5138 in seconds)
+fn firefox_expiry(expiry: i64) -> Option<DateTime<Utc>> {
+    if expiry > 100_000_000_000 {
+        convert_unix_time_micros(expiry.checked_mul(1_000)?)
+    } else {
+        convert_unix_time(expiry)
+    }
+}
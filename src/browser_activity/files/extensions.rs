@@ -0,0 +1,293 @@
+// "Browser extensions can provide information about the user's interests and can be used for malicious purposes." - SANS Windows Forensic Analysis Poster, Extensions
+// <Chromium profile>\Extensions\<id>\<version>\manifest.json: name, version, description and permissions
+// <Chromium profile>\Preferences and Secure Preferences: extensions.settings.<id> with install time (WebKit time as string),
+// state, location and path (also of extensions not in the Extensions folder, e.g. unpacked ones)
+// <Firefox profile>\extensions.json: addons with install and update time (unix time in milliseconds)
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+
+use common::{convert_unix_time_micros, convert_webkit_time, lookup};
+use log::error;
+use serde_json::{Map, Value};
+
+use crate::browser_activity::files::browsers::{
+    find_chromium_profiles, find_firefox_profiles, read_json, write_json, BrowserProfile,
+    ExtensionEntry,
+};
+
+const CHROMIUM_PREFERENCE_FILES: [&str; 2] = ["Secure Preferences", "Preferences"];
+// values of extensions.settings.<id>.location
+const CHROMIUM_LOCATIONS: [&str; 11] = [
+    "invalid",
+    "internal",
+    "external preferences",
+    "external registry",
+    "unpacked",
+    "component",
+    "external preferences download",
+    "external policy download",
+    "command line",
+    "external policy",
+    "external component",
+];
+
+pub fn get_extensions_data(input: &str, outpath: &str) -> anyhow::Result<()> {
+    print!("Working on Browser Extensions: ");
+    let mut extension_list: Vec<ExtensionEntry> = Vec::new();
+
+    for profile in find_chromium_profiles(input) {
+        if let Err(err) = read_chromium_extensions(&profile, &mut extension_list) {
+            error!("Failed to read extensions of {}: {err}", profile.path)
+        }
+    }
+    for profile in find_firefox_profiles(input) {
+        let path = format!("{}/extensions.json", profile.path);
+        if !Path::new(&path).is_file() {
+            continue;
+        }
+        if let Err(err) = read_firefox_extensions(&profile, &path, &mut extension_list) {
+            error!("Failed to read {path}: {err}")
+        }
+    }
+
+    if extension_list.is_empty() {
+        println!("Nothing to do here, continuing with next job.");
+        return Ok(());
+    }
+
+    write_json(&format!("{outpath}/br_extensions.json"), &extension_list)?;
+
+    println!("Done here!");
+    Ok(())
+}
+
+fn read_chromium_extensions(
+    profile: &BrowserProfile,
+    extension_list: &mut Vec<ExtensionEntry>,
+) -> anyhow::Result<()> {
+    // install metadata by extension id, Secure Preferences wins over Preferences
+    let mut settings: BTreeMap<String, (String, Value)> = BTreeMap::new();
+    for preference_file in CHROMIUM_PREFERENCE_FILES {
+        let path = format!("{}/{preference_file}", profile.path);
+        if !Path::new(&path).is_file() {
+            continue;
+        }
+        let preferences = match read_json(&path) {
+            Ok(preferences) => preferences,
+            Err(err) => {
+                error!("Failed to read {path}: {err}");
+                continue;
+            }
+        };
+        let Some(extensions) = preferences
+            .pointer("/extensions/settings")
+            .and_then(Value::as_object)
+        else {
+            continue;
+        };
+        for (id, setting) in extensions {
+            settings
+                .entry(id.clone())
+                .or_insert_with(|| (path.clone(), setting.clone()));
+        }
+    }
+
+    // version folder by extension id, old versions are usually removed after an update
+    let mut manifests: BTreeMap<String, String> = BTreeMap::new();
+    if let Ok(entries) = fs::read_dir(format!("{}/Extensions", profile.path)) {
+        for extension_dir in entries.flatten().map(|e| e.path()).filter(|p| p.is_dir()) {
+            let Ok(versions) = fs::read_dir(&extension_dir) else {
+                continue;
+            };
+            let mut versions: Vec<_> = versions
+                .flatten()
+                .map(|e| e.path())
+                .filter(|p| p.join("manifest.json").is_file())
+                .collect();
+            versions.sort();
+            if let Some(version_dir) = versions.pop() {
+                let id = extension_dir
+                    .file_name()
+                    .map(|s| s.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                manifests.insert(id, version_dir.to_string_lossy().to_string());
+            }
+        }
+    }
+
+    let ids: BTreeSet<String> = settings.keys().chain(manifests.keys()).cloned().collect();
+    for id in ids {
+        let (settings_file, setting) = settings
+            .get(&id)
+            .cloned()
+            .unwrap_or((String::new(), Value::Null));
+        let version_dir = manifests.get(&id);
+        // unpacked extensions only have a manifest in their own path
+        let version_dir = version_dir.cloned().or_else(|| {
+            setting
+                .get("path")
+                .and_then(Value::as_str)
+                .filter(|p| Path::new(p).join("manifest.json").is_file())
+                .map(str::to_string)
+        });
+        let manifest = match &version_dir {
+            Some(dir) => match read_json(&format!("{dir}/manifest.json")) {
+                Ok(manifest) => manifest,
+                Err(err) => {
+                    error!("Failed to read manifest of {dir}: {err}");
+                    Value::Null
+                }
+            },
+            // component extensions keep their manifest in the preferences
+            None => setting.get("manifest").cloned().unwrap_or(Value::Null),
+        };
+        if manifest.is_null() && setting.is_null() {
+            continue;
+        }
+        let webkit_time = |name: &str| {
+            setting
+                .get(name)
+                .and_then(Value::as_str)
+                .and_then(|t| t.parse::<i64>().ok())
+                .and_then(convert_webkit_time)
+        };
+        let mut install_source = setting
+            .get("location")
+            .and_then(Value::as_i64)
+            .map(|l| lookup(&CHROMIUM_LOCATIONS, l));
+        if setting.get("from_webstore").and_then(Value::as_bool) == Some(true) {
+            install_source = Some("web store".to_string());
+        }
+        let source_file = match &version_dir {
+            Some(dir) => format!("{dir}/manifest.json"),
+            None => settings_file,
+        };
+        extension_list.push(ExtensionEntry {
+            user_profile: profile.user_profile.clone(),
+            browser: profile.browser.clone(),
+            browser_profile: profile.browser_profile.clone(),
+            source_file,
+            name: localized(&manifest, "name", version_dir.as_deref()),
+            version: manifest
+                .get("version")
+                .and_then(Value::as_str)
+                .map(str::to_string),
+            description: localized(&manifest, "description", version_dir.as_deref()),
+            // state 1 is enabled in older versions, newer ones only keep the disable reasons
+            enabled: setting
+                .get("state")
+                .and_then(Value::as_i64)
+                .map(|s| s == 1)
+                .or_else(|| {
+                    setting
+                        .get("disable_reasons")
+                        .map(|r| r.as_i64() == Some(0) || r.as_array().is_some_and(Vec::is_empty))
+                })
+                .or_else(|| (!setting.is_null()).then_some(true)),
+            installed: webkit_time("first_install_time").or_else(|| webkit_time("install_time")),
+            updated: webkit_time("last_update_time"),
+            install_source,
+            path: version_dir,
+            permissions: permissions(&manifest),
+            id,
+        });
+    }
+    Ok(())
+}
+
+/// resolves "__MSG_name__" with the messages of the default locale
+fn localized(manifest: &Value, field: &str, version_dir: Option<&str>) -> Option<String> {
+    let value = manifest.get(field).and_then(Value::as_str)?;
+    let Some(key) = value
+        .strip_prefix("__MSG_")
+        .and_then(|v| v.strip_suffix("__"))
+    else {
+        return Some(value.to_string());
+    };
+    let locale = manifest
+        .get("default_locale")
+        .and_then(Value::as_str)
+        .unwrap_or("en");
+    let messages: Map<String, Value> = version_dir
+        .and_then(|dir| read_json(&format!("{dir}/_locales/{locale}/messages.json")).ok())
+        .and_then(|messages| messages.as_object().cloned())
+        .unwrap_or_default();
+    // message names are case insensitive
+    messages
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(key))
+        .and_then(|(_, message)| message.get("message"))
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .or_else(|| Some(value.to_string()))
+}
+
+/// permissions and host permissions of the manifest
+fn permissions(manifest: &Value) -> Vec<String> {
+    ["permissions", "optional_permissions", "host_permissions"]
+        .iter()
+        .filter_map(|name| manifest.get(name).and_then(Value::as_array))
+        .flatten()
+        .filter_map(Value::as_str)
+        .map(str::to_string)
+        .collect()
+}
+
+fn read_firefox_extensions(
+    profile: &BrowserProfile,
+    path: &str,
+    extension_list: &mut Vec<ExtensionEntry>,
+) -> anyhow::Result<()> {
+    let extensions = read_json(path)?;
+    let Some(addons) = extensions.get("addons").and_then(Value::as_array) else {
+        return Ok(());
+    };
+    for addon in addons {
+        let text = |value: Option<&Value>| {
+            value
+                .and_then(Value::as_str)
+                .filter(|s| !s.is_empty())
+                .map(str::to_string)
+        };
+        let time = |name: &str| {
+            addon
+                .get(name)
+                .and_then(Value::as_i64)
+                .and_then(|ms| convert_unix_time_micros(ms.checked_mul(1_000)?))
+        };
+        let locale = addon.get("defaultLocale");
+        let permissions = ["userPermissions", "optionalPermissions"]
+            .iter()
+            .filter_map(|name| addon.get(name))
+            .flat_map(|p| {
+                ["permissions", "origins"]
+                    .iter()
+                    .filter_map(|name| p.get(name).and_then(Value::as_array))
+                    .flatten()
+                    .filter_map(Value::as_str)
+                    .map(str::to_string)
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        extension_list.push(ExtensionEntry {
+            user_profile: profile.user_profile.clone(),
+            browser: profile.browser.clone(),
+            browser_profile: profile.browser_profile.clone(),
+            source_file: path.to_string(),
+            id: text(addon.get("id")).unwrap_or_default(),
+            name: text(locale.and_then(|l| l.get("name"))),
+            version: text(addon.get("version")),
+            description: text(locale.and_then(|l| l.get("description"))),
+            enabled: addon.get("active").and_then(Value::as_bool),
+            installed: time("installDate"),
+            updated: time("updateDate"),
+            // app-profile, app-system-defaults, app-builtin, ...
+            install_source: text(addon.get("sourceURI")).or_else(|| text(addon.get("location"))),
+            path: text(addon.get("path")),
+            permissions,
+        });
+    }
+    Ok(())
+}
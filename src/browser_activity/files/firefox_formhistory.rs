@@ -0,0 +1,58 @@
+// "Many databases store data that a user has typed into the browser." - SANS Windows Forensic Analysis Poster, Auto-Complete Data
+// <Firefox profile>\formhistory.sqlite: moz_formhistory (field name, value, times used, first and last use as PRTime)
+
+use std::path::Path;
+
+use common::convert_unix_time_micros;
+use log::error;
+
+use crate::browser_activity::files::browsers::{
+    find_firefox_profiles, write_json, FormHistoryEntry,
+};
+use crate::formats::sqlite::{integer_column, text_column, with_sqlite_copy};
+
+pub fn get_firefox_formhistory_data(input: &str, outpath: &str) -> anyhow::Result<()> {
+    print!("Working on Firefox Form History: ");
+    let mut formhistory_list: Vec<FormHistoryEntry> = Vec::new();
+
+    for profile in find_firefox_profiles(input) {
+        let database = format!("{}/formhistory.sqlite", profile.path);
+        if !Path::new(&database).is_file() {
+            continue;
+        }
+        let result = with_sqlite_copy(&database, |connection| {
+            let mut statement = connection.prepare("SELECT * FROM moz_formhistory")?;
+            let mut rows = statement.query([])?;
+            while let Some(row) = rows.next()? {
+                formhistory_list.push(FormHistoryEntry {
+                    user_profile: profile.user_profile.clone(),
+                    browser: profile.browser.clone(),
+                    browser_profile: profile.browser_profile.clone(),
+                    database: database.clone(),
+                    field_name: text_column(row, "fieldname").unwrap_or_default(),
+                    value: text_column(row, "value").unwrap_or_default(),
+                    times_used: integer_column(row, "timesUsed"),
+                    first_used: integer_column(row, "firstUsed").and_then(convert_unix_time_micros),
+                    last_used: integer_column(row, "lastUsed").and_then(convert_unix_time_micros),
+                });
+            }
+            Ok(())
+        });
+        if let Err(err) = result {
+            error!("Failed to read {database}: {err}")
+        }
+    }
+
+    if formhistory_list.is_empty() {
+        println!("Nothing to do here, continuing with next job.");
+        return Ok(());
+    }
+
+    write_json(
+        &format!("{outpath}/br_firefox_formhistory.json"),
+        &formhistory_list,
+    )?;
+
+    println!("Done here!");
+    Ok(())
+}
@@ -0,0 +1,289 @@
+// "Records websites visited by date and time. Details stored for each local user account. Records number of times visited (frequency)." - SANS Windows Forensic Analysis Poster, History and Download History
+// "Bookmarks include default items, as well as those the user chose to save for future reference." - SANS Windows Forensic Analysis Poster, Bookmarks
+// <Firefox profile>\places.sqlite: moz_places, moz_historyvisits, moz_bookmarks, moz_annos (downloads)
+// visit and bookmark times are PRTime (microseconds since 1970), the download end time is in milliseconds
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use common::{convert_unix_time_micros, lookup, node_path};
+use log::error;
+use serde_json::Value;
+
+use crate::browser_activity::files::browsers::{
+    find_firefox_profiles, write_json, BookmarkEntry, BrowserProfile, DownloadEntry, HistoryEntry,
+};
+use crate::formats::sqlite::{integer_column, text_column, with_sqlite_copy};
+
+// visit types (moz_historyvisits.visit_type), starting with 1
+const VISIT_TYPES: [&str; 9] = [
+    "LINK",
+    "TYPED",
+    "BOOKMARK",
+    "EMBED",
+    "REDIRECT_PERMANENT",
+    "REDIRECT_TEMPORARY",
+    "DOWNLOAD",
+    "FRAMED_LINK",
+    "RELOAD",
+];
+// bookmark types (moz_bookmarks.type)
+const TYPE_BOOKMARK: i64 = 1;
+const TYPE_FOLDER: i64 = 2;
+// download states of the downloads/metaData annotation
+const DOWNLOAD_STATES: [&str; 10] = [
+    "IN_PROGRESS",
+    "COMPLETE",
+    "FAILED",
+    "CANCELLED",
+    "PAUSED",
+    "QUEUED",
+    "BLOCKED_PARENTAL",
+    "SCANNING",
+    "DIRTY",
+    "BLOCKED_POLICY",
+];
+
+/// download annotations of one place
+#[derive(Default)]
+struct DownloadAnnotations {
+    url: String,
+    destination: Option<String>,
+    metadata: Option<Value>,
+    date_added: Option<i64>,
+}
+
+pub fn get_firefox_places_data(input: &str, outpath: &str) -> anyhow::Result<()> {
+    print!("Working on Firefox History, Bookmarks and Downloads: ");
+    let mut history_list: Vec<HistoryEntry> = Vec::new();
+    let mut bookmark_list: Vec<BookmarkEntry> = Vec::new();
+    let mut download_list: Vec<DownloadEntry> = Vec::new();
+
+    for profile in find_firefox_profiles(input) {
+        let database = format!("{}/places.sqlite", profile.path);
+        if !Path::new(&database).is_file() {
+            continue;
+        }
+        let result = with_sqlite_copy(&database, |connection| {
+            read_history(connection, &profile, &database, &mut history_list)?;
+            read_bookmarks(connection, &profile, &database, &mut bookmark_list)?;
+            read_downloads(connection, &profile, &database, &mut download_list)
+        });
+        if let Err(err) = result {
+            error!("Failed to read {database}: {err}")
+        }
+    }
+
+    if history_list.is_empty() && bookmark_list.is_empty() && download_list.is_empty() {
+        println!("Nothing to do here, continuing with next job.");
+        return Ok(());
+    }
+
+    write_json(&format!("{outpath}/br_firefox_history.json"), &history_list)?;
+    write_json(
+        &format!("{outpath}/br_firefox_bookmarks.json"),
+        &bookmark_list,
+    )?;
+    write_json(
+        &format!("{outpath}/br_firefox_downloads.json"),
+        &download_list,
+    )?;
+
+    println!("Done here!");
+    Ok(())
+}
+
+/// one entry per visit, also used by the url parameter analysis
+pub fn read_history(
+    connection: &rusqlite::Connection,
+    profile: &BrowserProfile,
+    database: &str,
+    history_list: &mut Vec<HistoryEntry>,
+) -> anyhow::Result<()> {
+    let mut statement = connection.prepare(
+        "SELECT p.url, p.title, p.visit_count, p.typed, v.visit_date, v.visit_type, \
+         fp.url AS from_url FROM moz_historyvisits v JOIN moz_places p ON v.place_id = p.id \
+         LEFT JOIN moz_historyvisits fv ON v.from_visit = fv.id \
+         LEFT JOIN moz_places fp ON fv.place_id = fp.id",
+    )?;
+    let mut rows = statement.query([])?;
+    while let Some(row) = rows.next()? {
+        history_list.push(HistoryEntry {
+            user_profile: profile.user_profile.clone(),
+            browser: profile.browser.clone(),
+            browser_profile: profile.browser_profile.clone(),
+            database: database.to_string(),
+            url: text_column(row, "url").unwrap_or_default(),
+            title: text_column(row, "title").filter(|t| !t.is_empty()),
+            visit_time: integer_column(row, "visit_date").and_then(convert_unix_time_micros),
+            visit_count: integer_column(row, "visit_count"),
+            // Firefox only records whether the url was ever typed
+            typed_count: integer_column(row, "typed"),
+            transition: integer_column(row, "visit_type").map(visit_type),
+            transition_qualifiers: Vec::new(),
+            visit_duration_seconds: None,
+            from_url: text_column(row, "from_url"),
+        });
+    }
+    Ok(())
+}
+
+fn read_bookmarks(
+    connection: &rusqlite::Connection,
+    profile: &BrowserProfile,
+    database: &str,
+    bookmark_list: &mut Vec<BookmarkEntry>,
+) -> anyhow::Result<()> {
+    let mut statement = connection.prepare(
+        "SELECT b.id, b.parent, b.type, b.title, b.dateAdded, b.lastModified, p.url \
+         FROM moz_bookmarks b LEFT JOIN moz_places p ON b.fk = p.id",
+    )?;
+    let mut rows = statement.query([])?;
+    // folders (id -> parent, title) are needed to build the folder path
+    let mut folders: HashMap<i64, (Option<i64>, String)> = HashMap::new();
+    let mut bookmarks = Vec::new();
+    while let Some(row) = rows.next()? {
+        let id = integer_column(row, "id").unwrap_or_default();
+        let parent = integer_column(row, "parent").unwrap_or_default();
+        let title = text_column(row, "title").filter(|t| !t.is_empty());
+        match integer_column(row, "type") {
+            Some(TYPE_FOLDER) => {
+                folders.insert(id, (Some(parent), title.unwrap_or_default()));
+            }
+            Some(TYPE_BOOKMARK) => {
+                let Some(url) = text_column(row, "url") else {
+                    continue;
+                };
+                bookmarks.push((
+                    parent,
+                    url,
+                    title,
+                    integer_column(row, "dateAdded"),
+                    integer_column(row, "lastModified"),
+                ));
+            }
+            _ => {}
+        }
+    }
+
+    for (parent, url, title, date_added, last_modified) in bookmarks {
+        bookmark_list.push(BookmarkEntry {
+            user_profile: profile.user_profile.clone(),
+            browser: profile.browser.clone(),
+            browser_profile: profile.browser_profile.clone(),
+            source_file: database.to_string(),
+            url,
+            title,
+            folder: folder_path(&folders, parent),
+            date_added: date_added.and_then(convert_unix_time_micros),
+            last_modified: last_modified.and_then(convert_unix_time_micros),
+        });
+    }
+    Ok(())
+}
+
+/// "menu/Work/Projects", the untitled root folder is left out
+fn folder_path(folders: &HashMap<i64, (Option<i64>, String)>, id: i64) -> Option<String> {
+    let names: Vec<&str> = node_path(folders, &id)
+        .into_iter()
+        .filter(|title| !title.is_empty())
+        .collect();
+    (!names.is_empty()).then(|| names.join("/"))
+}
+
+fn read_downloads(
+    connection: &rusqlite::Connection,
+    profile: &BrowserProfile,
+    database: &str,
+    download_list: &mut Vec<DownloadEntry>,
+) -> anyhow::Result<()> {
+    // moz_annos does not exist in all versions
+    let Ok(mut statement) = connection.prepare(
+        "SELECT a.place_id, n.name, a.content, a.dateAdded, p.url FROM moz_annos a \
+         JOIN moz_anno_attributes n ON a.anno_attribute_id = n.id \
+         JOIN moz_places p ON a.place_id = p.id WHERE n.name LIKE 'downloads/%'",
+    ) else {
+        return Ok(());
+    };
+    let mut rows = statement.query([])?;
+    let mut downloads: BTreeMap<i64, DownloadAnnotations> = BTreeMap::new();
+    while let Some(row) = rows.next()? {
+        let Some(place_id) = integer_column(row, "place_id") else {
+            continue;
+        };
+        let download = downloads.entry(place_id).or_default();
+        download.url = text_column(row, "url").unwrap_or_default();
+        let content = text_column(row, "content");
+        match text_column(row, "name").as_deref() {
+            Some("downloads/destinationFileURI") => {
+                download.destination = content;
+                download.date_added = integer_column(row, "dateAdded");
+            }
+            Some("downloads/metaData") => {
+                download.metadata = content.and_then(|c| serde_json::from_str(&c).ok());
+            }
+            _ => {}
+        }
+    }
+
+    for download in downloads.into_values() {
+        let metadata = download.metadata.unwrap_or_default();
+        download_list.push(DownloadEntry {
+            user_profile: profile.user_profile.clone(),
+            browser: profile.browser.clone(),
+            browser_profile: profile.browser_profile.clone(),
+            database: database.to_string(),
+            url: Some(download.url.clone()),
+            url_chain: vec![download.url],
+            referrer: None,
+            tab_url: None,
+            target_path: download.destination.as_deref().map(file_uri_to_path),
+            mime_type: None,
+            received_bytes: None,
+            total_bytes: metadata.get("fileSize").and_then(Value::as_i64),
+            start_time: download.date_added.and_then(convert_unix_time_micros),
+            end_time: metadata
+                .get("endTime")
+                .and_then(Value::as_i64)
+                .and_then(|ms| convert_unix_time_micros(ms.checked_mul(1_000)?)),
+            state: metadata
+                .get("state")
+                .and_then(Value::as_i64)
+                .map(|s| lookup(&DOWNLOAD_STATES, s)),
+            danger_type: None,
+            opened: None,
+        });
+    }
+    Ok(())
+}
+
+fn visit_type(visit_type: i64) -> String {
+    lookup(&VISIT_TYPES, visit_type - 1)
+}
+
+/// "file:///C:/Users/ally/Downloads/a%20b.zip" -> "C:\Users\ally\Downloads\a b.zip"
+fn file_uri_to_path(uri: &str) -> String {
+    let Some(path) = uri.strip_prefix("file:///") else {
+        return uri.to_string();
+    };
+    let bytes = path.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = (bytes[i] == b'%')
+            .then(|| path.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|h| u8::from_str_radix(h, 16).ok());
+        match hex {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).replace('/', "\\")
+}
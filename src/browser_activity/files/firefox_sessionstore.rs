@@ -0,0 +1,113 @@
+// "Automatic crash recovery features built into the browser." - SANS Windows Forensic Analysis Poster, Session Restore
+// <Firefox profile>\sessionstore.jsonlz4 (written on a clean exit)
+// <Firefox profile>\sessionstore-backups\recovery.jsonlz4, recovery.baklz4 and previous.jsonlz4 (written while the browser runs)
+// mozLz4 compressed JSON: windows -> tabs -> entries (tab history), closed windows and tabs are kept in _closedWindows/_closedTabs
+
+use std::path::Path;
+
+use common::convert_unix_time_micros;
+use log::error;
+use serde_json::Value;
+
+use crate::browser_activity::files::browsers::{
+    find_firefox_profiles, write_json, BrowserProfile, SessionEntry,
+};
+use crate::formats::mozlz4::read_mozlz4;
+
+const SESSION_FILES: [&str; 4] = [
+    "sessionstore.jsonlz4",
+    "sessionstore-backups/recovery.jsonlz4",
+    "sessionstore-backups/recovery.baklz4",
+    "sessionstore-backups/previous.jsonlz4",
+];
+
+pub fn get_firefox_sessionstore_data(input: &str, outpath: &str) -> anyhow::Result<()> {
+    print!("Working on Firefox Session Restore: ");
+    let mut session_list: Vec<SessionEntry> = Vec::new();
+
+    for profile in find_firefox_profiles(input) {
+        for session_file in SESSION_FILES {
+            let path = format!("{}/{session_file}", profile.path);
+            if !Path::new(&path).is_file() {
+                continue;
+            }
+            if let Err(err) = read_session_file(&profile, &path, &mut session_list) {
+                error!("Failed to read {path}: {err}")
+            }
+        }
+    }
+
+    if session_list.is_empty() {
+        println!("Nothing to do here, continuing with next job.");
+        return Ok(());
+    }
+
+    write_json(
+        &format!("{outpath}/br_firefox_sessions.json"),
+        &session_list,
+    )?;
+
+    println!("Done here!");
+    Ok(())
+}
+
+fn read_session_file(
+    profile: &BrowserProfile,
+    path: &str,
+    session_list: &mut Vec<SessionEntry>,
+) -> anyhow::Result<()> {
+    let session: Value = serde_json::from_slice(&read_mozlz4(path)?)?;
+    let array = |value: &Value, name: &str| {
+        value
+            .get(name)
+            .and_then(Value::as_array)
+            .cloned()
+            .unwrap_or_default()
+    };
+
+    let open_windows = array(&session, "windows").into_iter().map(|w| (w, false));
+    let closed_windows = array(&session, "_closedWindows")
+        .into_iter()
+        .map(|w| (w, true));
+    for (window_index, (window, window_closed)) in open_windows.chain(closed_windows).enumerate() {
+        let open_tabs = array(&window, "tabs")
+            .into_iter()
+            .map(|t| (t, window_closed));
+        // closed tabs have their tab data in "state"
+        let closed_tabs = array(&window, "_closedTabs")
+            .into_iter()
+            .filter_map(|t| t.get("state").cloned())
+            .map(|t| (t, true));
+        for (tab_index, (tab, closed)) in open_tabs.chain(closed_tabs).enumerate() {
+            // "index" is the current entry, starting with 1
+            let current = tab.get("index").and_then(Value::as_u64).unwrap_or(1) as usize;
+            let last_accessed = tab
+                .get("lastAccessed")
+                .and_then(Value::as_i64)
+                .and_then(|ms| convert_unix_time_micros(ms.checked_mul(1_000)?));
+            for (entry_index, entry) in array(&tab, "entries").iter().enumerate() {
+                let Some(url) = entry.get("url").and_then(Value::as_str) else {
+                    continue;
+                };
+                session_list.push(SessionEntry {
+                    user_profile: profile.user_profile.clone(),
+                    browser: profile.browser.clone(),
+                    browser_profile: profile.browser_profile.clone(),
+                    source_file: path.to_string(),
+                    window: window_index,
+                    tab: tab_index,
+                    entry: entry_index,
+                    current: entry_index + 1 == current,
+                    closed,
+                    url: url.to_string(),
+                    title: entry
+                        .get("title")
+                        .and_then(Value::as_str)
+                        .map(str::to_string),
+                    last_accessed,
+                });
+            }
+        }
+    }
+    Ok(())
+}
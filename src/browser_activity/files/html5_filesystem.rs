@@ -0,0 +1,158 @@
+// "HTML5 FileSystem implements a virtual file system for websites, the files are stored in the browser profile." - SANS Windows Forensic Analysis Poster, HTML5 FileSystem
+// <Chromium profile>\File System\Origins (LevelDB): "ORIGIN:<origin>" -> folder of the origin
+// <Chromium profile>\File System\<folder>\t\Paths (temporary) and \p\Paths (persistent) (LevelDB):
+// "CHILD_OF:<parent id>:<name>" -> id, "<id>" -> pickle of parent id, data path, name and modification time (WebKit time)
+// the content of a file is stored at <folder>\<t|p>\<data path>
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use common::{convert_webkit_time, node_path};
+use log::error;
+
+use crate::browser_activity::files::browsers::{
+    find_chromium_profiles, write_json, BrowserProfile, FileSystemEntry,
+};
+use crate::formats::leveldb::read_leveldb;
+use crate::formats::pickle::Pickle;
+
+const STORAGE_TYPES: [(&str, &str); 2] = [("t", "temporary"), ("p", "persistent")];
+
+pub fn get_html5_filesystem_data(input: &str, outpath: &str) -> anyhow::Result<()> {
+    print!("Working on HTML5 FileSystem: ");
+    let mut file_list: Vec<FileSystemEntry> = Vec::new();
+
+    for profile in find_chromium_profiles(input) {
+        let file_system = format!("{}/File System", profile.path);
+        if !Path::new(&file_system).is_dir() {
+            continue;
+        }
+        if let Err(err) = read_file_system(&profile, &file_system, &mut file_list) {
+            error!("Failed to read {file_system}: {err}")
+        }
+    }
+
+    if file_list.is_empty() {
+        println!("Nothing to do here, continuing with next job.");
+        return Ok(());
+    }
+
+    write_json(&format!("{outpath}/br_html5_filesystem.json"), &file_list)?;
+
+    println!("Done here!");
+    Ok(())
+}
+
+fn read_file_system(
+    profile: &BrowserProfile,
+    file_system: &str,
+    file_list: &mut Vec<FileSystemEntry>,
+) -> anyhow::Result<()> {
+    // origin by folder, the live record wins
+    let mut origins: HashMap<String, String> = HashMap::new();
+    let origins_folder = format!("{file_system}/Origins");
+    if Path::new(&origins_folder).is_dir() {
+        let mut records = read_leveldb(&origins_folder)?;
+        records.sort_by_key(|r| r.live);
+        for record in records {
+            if let Some(origin) = record.key.strip_prefix(b"ORIGIN:") {
+                origins.insert(
+                    String::from_utf8_lossy(&record.value).to_string(),
+                    String::from_utf8_lossy(origin).to_string(),
+                );
+            }
+        }
+    }
+
+    for origin_folder in fs::read_dir(file_system)?
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_dir())
+    {
+        let folder_name = origin_folder
+            .file_name()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default();
+        for (short_name, storage_type) in STORAGE_TYPES {
+            let storage_folder = origin_folder.join(short_name);
+            let paths = storage_folder.join("Paths");
+            if !paths.is_dir() {
+                continue;
+            }
+            let paths = paths.to_string_lossy().to_string();
+            let records = match read_leveldb(&paths) {
+                Ok(records) => records,
+                Err(err) => {
+                    error!("Failed to read {paths}: {err}");
+                    continue;
+                }
+            };
+
+            // file infos by id, the key is the id in decimal
+            let mut file_infos: Vec<(u64, FileInfo, bool, String)> = records
+                .into_iter()
+                .filter_map(|record| {
+                    let id = std::str::from_utf8(&record.key).ok()?.parse::<u64>().ok()?;
+                    let file_info = read_file_info(&record.value)?;
+                    Some((id, file_info, record.live, record.source_file))
+                })
+                .collect();
+            file_infos.sort_by_key(|(id, _, live, _)| (*id, *live));
+            // the live record of an id wins for the path of its children
+            // the root has the id 0
+            let names: HashMap<u64, (Option<u64>, String)> = file_infos
+                .iter()
+                .map(|(id, info, _, _)| {
+                    let parent_id = (info.parent_id != 0).then_some(info.parent_id);
+                    (*id, (parent_id, info.name.clone()))
+                })
+                .collect();
+
+            for (id, info, live, source_file) in file_infos {
+                let data_file = (!info.data_path.is_empty()).then(|| {
+                    storage_folder
+                        .join(info.data_path.replace('\\', "/"))
+                        .to_string_lossy()
+                        .to_string()
+                });
+                file_list.push(FileSystemEntry {
+                    user_profile: profile.user_profile.clone(),
+                    browser: profile.browser.clone(),
+                    browser_profile: profile.browser_profile.clone(),
+                    source_file,
+                    origin: origins.get(&folder_name).cloned(),
+                    storage_type: storage_type.to_string(),
+                    path: format!("/{}", node_path(&names, &id).join("/")),
+                    data_file,
+                    modified: info.modified,
+                    live,
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+struct FileInfo {
+    parent_id: u64,
+    data_path: String,
+    name: String,
+    modified: Option<DateTime<Utc>>,
+}
+
+/// parent id, data path, name and modification time, paths are UTF-16 on Windows
+fn read_file_info(value: &[u8]) -> Option<FileInfo> {
+    let mut pickle = Pickle::new(value);
+    let parent_id = u64::try_from(pickle.int64()?).ok()?;
+    let data_path = pickle.string16()?;
+    let name = pickle.string16()?;
+    let modified = pickle.int64().and_then(convert_webkit_time);
+    Some(FileInfo {
+        parent_id,
+        data_path,
+        name,
+        modified,
+    })
+}
@@ -0,0 +1,429 @@
+// "IndexedDB is a database in the browser that websites use to store structured data, like offline mail or documents." - SANS Windows Forensic Analysis Poster, HTML5 Web Storage
+// <Chromium profile>\IndexedDB\<scheme>_<host>_<port>.indexeddb.leveldb (LevelDB): keys start with a prefix of
+// database, object store and index id, index 1 holds the records of an object store, the names are in the metadata
+// <Firefox profile>\storage\default\<origin>\idb\*.sqlite: object_data with (Snappy compressed) values
+// the values are serialized JavaScript objects, only the strings in them are extracted
+
+use std::collections::HashMap;
+use std::fs;
+
+use common::convert_unix_time_micros;
+use log::error;
+use rusqlite::types::ValueRef;
+
+use crate::browser_activity::files::browsers::{
+    find_chromium_profiles, find_firefox_profiles, firefox_storage_origins, write_json,
+    BrowserProfile, WebStorageEntry,
+};
+use crate::formats::leveldb::{read_leveldb, read_varint};
+use crate::formats::sqlite::{integer_column, with_sqlite_copy};
+
+const INDEXEDDB: &str = "IndexedDB";
+const CHROMIUM_FOLDER_SUFFIX: &str = ".indexeddb.leveldb";
+// Chromium metadata key types
+const DATABASE_NAME: u8 = 201;
+const OBJECT_STORE_METADATA: u8 = 50;
+const OBJECT_STORE_NAME: u8 = 0;
+// index id of the object store records
+const OBJECT_STORE_DATA: u64 = 1;
+// Chromium key types
+const KEY_NULL: u8 = 0;
+const KEY_STRING: u8 = 1;
+const KEY_DATE: u8 = 2;
+const KEY_NUMBER: u8 = 3;
+const KEY_ARRAY: u8 = 4;
+const KEY_BINARY: u8 = 6;
+// Firefox key types
+const FIREFOX_KEY_FLOAT: u8 = 0x10;
+const FIREFOX_KEY_DATE: u8 = 0x20;
+const FIREFOX_KEY_STRING: u8 = 0x30;
+const MIN_STRING_LENGTH: usize = 4;
+
+pub fn get_indexeddb_data(input: &str, outpath: &str) -> anyhow::Result<()> {
+    print!("Working on IndexedDB: ");
+    let mut storage_list: Vec<WebStorageEntry> = Vec::new();
+
+    for profile in find_chromium_profiles(input) {
+        let Ok(entries) = fs::read_dir(format!("{}/IndexedDB", profile.path)) else {
+            continue;
+        };
+        for folder in entries.flatten().map(|e| e.path()).filter(|p| p.is_dir()) {
+            let folder_name = folder
+                .file_name()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let Some(origin) = folder_name.strip_suffix(CHROMIUM_FOLDER_SUFFIX) else {
+                continue;
+            };
+            let folder = folder.to_string_lossy().to_string();
+            if let Err(err) = read_chromium_indexeddb(
+                &profile,
+                &folder,
+                &chromium_origin(origin),
+                &mut storage_list,
+            ) {
+                error!("Failed to read {folder}: {err}")
+            }
+        }
+    }
+    for profile in find_firefox_profiles(input) {
+        for (origin, origin_path) in firefox_storage_origins(&profile) {
+            let Ok(entries) = fs::read_dir(format!("{origin_path}/idb")) else {
+                continue;
+            };
+            for database in entries.flatten().map(|e| e.path()) {
+                if database.extension().is_none_or(|e| e != "sqlite") {
+                    continue;
+                }
+                let database = database.to_string_lossy().to_string();
+                if let Err(err) =
+                    read_firefox_indexeddb(&profile, &origin, &database, &mut storage_list)
+                {
+                    error!("Failed to read {database}: {err}")
+                }
+            }
+        }
+    }
+
+    if storage_list.is_empty() {
+        println!("Nothing to do here, continuing with next job.");
+        return Ok(());
+    }
+
+    write_json(&format!("{outpath}/br_indexeddb.json"), &storage_list)?;
+
+    println!("Done here!");
+    Ok(())
+}
+
+fn read_chromium_indexeddb(
+    profile: &BrowserProfile,
+    folder: &str,
+    origin: &str,
+    storage_list: &mut Vec<WebStorageEntry>,
+) -> anyhow::Result<()> {
+    let records = read_leveldb(folder)?;
+
+    // names of the databases and object stores from the metadata
+    let mut database_names: HashMap<u64, String> = HashMap::new();
+    let mut object_store_names: HashMap<(u64, u64), String> = HashMap::new();
+    for record in &records {
+        let mut offset = 0;
+        let Some((database_id, object_store_id, index_id)) =
+            read_key_prefix(&record.key, &mut offset)
+        else {
+            continue;
+        };
+        match (
+            database_id,
+            object_store_id,
+            index_id,
+            record.key.get(offset),
+        ) {
+            // global metadata: origin and name -> database id
+            (0, 0, 0, Some(&DATABASE_NAME)) => {
+                offset += 1;
+                let _origin = read_string_with_length(&record.key, &mut offset);
+                let (Some(name), Some(id)) = (
+                    read_string_with_length(&record.key, &mut offset),
+                    read_int(&record.value),
+                ) else {
+                    continue;
+                };
+                database_names.insert(id, name);
+            }
+            // database metadata: object store id and metadata type -> value
+            (database_id, 0, 0, Some(&OBJECT_STORE_METADATA)) if database_id > 0 => {
+                offset += 1;
+                let Some(object_store_id) = read_varint(&record.key, &mut offset) else {
+                    continue;
+                };
+                if record.key.get(offset) == Some(&OBJECT_STORE_NAME) {
+                    object_store_names.insert(
+                        (database_id, object_store_id),
+                        decode_utf16_be(&record.value),
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+
+    for record in records {
+        let mut offset = 0;
+        let Some((database_id, object_store_id, index_id)) =
+            read_key_prefix(&record.key, &mut offset)
+        else {
+            continue;
+        };
+        if database_id == 0 || object_store_id == 0 || index_id != OBJECT_STORE_DATA {
+            continue;
+        }
+        let key = decode_chromium_key(&record.key, &mut offset)
+            .unwrap_or_else(|| readable_strings(&record.key[offset.min(record.key.len())..]));
+        // the value starts with the version of the record
+        let mut value_offset = 0;
+        let _version = read_varint(&record.value, &mut value_offset);
+        let value = record.value.get(value_offset..).unwrap_or_default();
+        storage_list.push(WebStorageEntry {
+            user_profile: profile.user_profile.clone(),
+            browser: profile.browser.clone(),
+            browser_profile: profile.browser_profile.clone(),
+            source_file: record.source_file,
+            storage_type: INDEXEDDB.to_string(),
+            origin: origin.to_string(),
+            database: Some(format!(
+                "{}/{}",
+                database_names
+                    .get(&database_id)
+                    .cloned()
+                    .unwrap_or_else(|| database_id.to_string()),
+                object_store_names
+                    .get(&(database_id, object_store_id))
+                    .cloned()
+                    .unwrap_or_else(|| object_store_id.to_string())
+            )),
+            key,
+            value: readable_strings(value),
+            live: record.live,
+        });
+    }
+    Ok(())
+}
+
+fn read_firefox_indexeddb(
+    profile: &BrowserProfile,
+    origin: &str,
+    database: &str,
+    storage_list: &mut Vec<WebStorageEntry>,
+) -> anyhow::Result<()> {
+    with_sqlite_copy(database, |connection| {
+        let database_name: Option<String> = connection
+            .query_row("SELECT name FROM database", [], |row| row.get(0))
+            .ok();
+        let mut object_store_names: HashMap<i64, String> = HashMap::new();
+        let mut statement = connection.prepare("SELECT id, name FROM object_store")?;
+        let mut rows = statement.query([])?;
+        while let Some(row) = rows.next()? {
+            if let (Ok(id), Ok(name)) = (row.get(0), row.get(1)) {
+                object_store_names.insert(id, name);
+            }
+        }
+
+        let mut statement = connection.prepare("SELECT * FROM object_data")?;
+        let mut rows = statement.query([])?;
+        while let Some(row) = rows.next()? {
+            let blob = |name: &str| match row.get_ref(name) {
+                Ok(ValueRef::Blob(value)) | Ok(ValueRef::Text(value)) => value.to_vec(),
+                _ => Vec::new(),
+            };
+            let data = blob("data");
+            let data = snap::raw::Decoder::new()
+                .decompress_vec(&data)
+                .unwrap_or(data);
+            let object_store = integer_column(row, "object_store_id").unwrap_or_default();
+            storage_list.push(WebStorageEntry {
+                user_profile: profile.user_profile.clone(),
+                browser: profile.browser.clone(),
+                browser_profile: profile.browser_profile.clone(),
+                source_file: database.to_string(),
+                storage_type: INDEXEDDB.to_string(),
+                origin: origin.to_string(),
+                database: Some(format!(
+                    "{}/{}",
+                    database_name.clone().unwrap_or_default(),
+                    object_store_names
+                        .get(&object_store)
+                        .cloned()
+                        .unwrap_or_else(|| object_store.to_string())
+                )),
+                key: decode_firefox_key(&blob("key")),
+                value: readable_strings(&data),
+                live: true,
+            });
+        }
+        Ok(())
+    })
+}
+
+/// first byte with the sizes of the ids, then database, object store and index id (little endian)
+fn read_key_prefix(key: &[u8], offset: &mut usize) -> Option<(u64, u64, u64)> {
+    let first = *key.first()?;
+    let sizes = [
+        usize::from(first >> 5) + 1,
+        usize::from((first >> 2) & 0x07) + 1,
+        usize::from(first & 0x03) + 1,
+    ];
+    *offset = 1;
+    let mut ids = [0u64; 3];
+    for (id, size) in ids.iter_mut().zip(sizes) {
+        let bytes = key.get(*offset..*offset + size)?;
+        *id = bytes
+            .iter()
+            .rev()
+            .fold(0u64, |id, byte| (id << 8) | u64::from(*byte));
+        *offset += size;
+    }
+    Some((ids[0], ids[1], ids[2]))
+}
+
+/// EncodeInt: little endian without continuation bits, as many bytes as needed
+fn read_int(data: &[u8]) -> Option<u64> {
+    if data.is_empty() || data.len() > 8 {
+        return None;
+    }
+    Some(
+        data.iter()
+            .rev()
+            .fold(0u64, |value, byte| (value << 8) | u64::from(*byte)),
+    )
+}
+
+/// varint length in characters followed by UTF-16BE
+fn read_string_with_length(data: &[u8], offset: &mut usize) -> Option<String> {
+    let length = usize::try_from(read_varint(data, offset)?).ok()?;
+    let bytes = data.get(*offset..offset.checked_add(length.checked_mul(2)?)?)?;
+    *offset += bytes.len();
+    Some(decode_utf16_be(bytes))
+}
+
+fn decode_utf16_be(data: &[u8]) -> String {
+    let chars: Vec<u16> = data
+        .chunks_exact(2)
+        .map(|c| u16::from_be_bytes([c[0], c[1]]))
+        .collect();
+    String::from_utf16_lossy(&chars)
+}
+
+/// type byte followed by the key, numbers and dates are little endian doubles
+fn decode_chromium_key(key: &[u8], offset: &mut usize) -> Option<String> {
+    let key_type = *key.get(*offset)?;
+    *offset += 1;
+    match key_type {
+        KEY_NULL => Some("null".to_string()),
+        KEY_STRING => read_string_with_length(key, offset),
+        KEY_DATE | KEY_NUMBER => {
+            let number = f64::from_le_bytes(key.get(*offset..*offset + 8)?.try_into().ok()?);
+            *offset += 8;
+            if key_type == KEY_DATE {
+                Some(format_date(number))
+            } else {
+                Some(number.to_string())
+            }
+        }
+        KEY_ARRAY => {
+            let count = read_varint(key, offset)?;
+            let mut items = Vec::new();
+            for _ in 0..count {
+                items.push(decode_chromium_key(key, offset)?);
+            }
+            Some(format!("[{}]", items.join(", ")))
+        }
+        KEY_BINARY => {
+            let length = usize::try_from(read_varint(key, offset)?).ok()?;
+            let bytes = key.get(*offset..offset.checked_add(length)?)?;
+            *offset += length;
+            Some(bytes.iter().map(|b| format!("{b:02x}")).collect())
+        }
+        _ => None,
+    }
+}
+
+/// type byte followed by the key, strings are encoded with 1 to 3 bytes per character,
+/// numbers and dates are big endian doubles with a flipped sign, arrays and binary keys are not decoded
+fn decode_firefox_key(key: &[u8]) -> String {
+    let Some((&key_type, data)) = key.split_first() else {
+        return String::new();
+    };
+    match key_type {
+        FIREFOX_KEY_STRING => {
+            let mut chars: Vec<u16> = Vec::new();
+            let mut iter = data.iter().copied().take_while(|b| *b != 0);
+            while let Some(byte) = iter.next() {
+                let character = if byte & 0x80 == 0 {
+                    u16::from(byte) - 1
+                } else if byte & 0x40 == 0 {
+                    let value = (u16::from(byte) << 8) | u16::from(iter.next().unwrap_or(0));
+                    value.wrapping_sub(0x8000).wrapping_add(0x7f)
+                } else {
+                    let value = (u32::from(byte) << 16)
+                        | (u32::from(iter.next().unwrap_or(0)) << 8)
+                        | u32::from(iter.next().unwrap_or(0));
+                    (value >> 6) as u16
+                };
+                chars.push(character);
+            }
+            String::from_utf16_lossy(&chars)
+        }
+        FIREFOX_KEY_FLOAT | FIREFOX_KEY_DATE => {
+            let mut bytes = [0u8; 8];
+            for (target, source) in bytes.iter_mut().zip(data) {
+                *target = *source;
+            }
+            let bits = u64::from_be_bytes(bytes);
+            let sign = 1u64 << 63;
+            let bits = if bits & sign != 0 {
+                bits & !sign
+            } else {
+                bits.wrapping_neg()
+            };
+            let number = f64::from_bits(bits);
+            if key_type == FIREFOX_KEY_DATE {
+                format_date(number)
+            } else {
+                number.to_string()
+            }
+        }
+        _ => readable_strings(key),
+    }
+}
+
+/// JavaScript dates are milliseconds since 1970, formatted like the serialized timestamps
+fn format_date(milliseconds: f64) -> String {
+    convert_unix_time_micros((milliseconds * 1_000.0) as i64)
+        .map(|time| format!("{time:?}"))
+        .unwrap_or_else(|| milliseconds.to_string())
+}
+
+/// printable ASCII and UTF-16LE strings of at least MIN_STRING_LENGTH characters
+fn readable_strings(data: &[u8]) -> String {
+    let printable = |byte: u8| (0x20..0x7f).contains(&byte);
+    let mut strings: Vec<String> = Vec::new();
+    let mut offset = 0;
+    while offset < data.len() {
+        let rest = &data[offset..];
+        let utf16_length = rest
+            .chunks_exact(2)
+            .take_while(|c| printable(c[0]) && c[1] == 0)
+            .count();
+        if utf16_length >= MIN_STRING_LENGTH {
+            strings.push(
+                rest.iter()
+                    .step_by(2)
+                    .take(utf16_length)
+                    .map(|b| char::from(*b))
+                    .collect(),
+            );
+            offset += utf16_length * 2;
+            continue;
+        }
+        let ascii_length = rest.iter().take_while(|b| printable(**b)).count();
+        if ascii_length >= MIN_STRING_LENGTH {
+            strings.push(String::from_utf8_lossy(&rest[..ascii_length]).to_string());
+        }
+        offset += ascii_length.max(1);
+    }
+    strings.join(" ")
+}
+
+/// "https_www.example.com_0" -> "https://www.example.com", port 0 is the default port
+fn chromium_origin(folder: &str) -> String {
+    let Some((scheme, rest)) = folder.split_once('_') else {
+        return folder.to_string();
+    };
+    match rest.rsplit_once('_') {
+        Some((host, "0")) => format!("{scheme}://{host}"),
+        Some((host, port)) => format!("{scheme}://{host}:{port}"),
+        None => format!("{scheme}://{rest}"),
+    }
+}
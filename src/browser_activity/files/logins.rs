@@ -0,0 +1,124 @@
+// "Browsers store credentials the user chose to save, showing on which websites the user had an account." - SANS Windows Forensic Analysis Poster, Stored Credentials
+// <Chromium profile>\Login Data and Login Data For Account (SQLite): logins, WebKit time
+// <Firefox profile>\logins.json: logins, unix time in milliseconds, username and password are encrypted
+// passwords are never decrypted, only their presence is reported
+
+use std::fs;
+use std::path::Path;
+
+use common::{convert_unix_time_micros, convert_webkit_time};
+use log::error;
+use serde_json::Value;
+
+use crate::browser_activity::files::browsers::{
+    find_chromium_profiles, find_firefox_profiles, write_json, BrowserProfile, LoginEntry,
+};
+use crate::formats::sqlite::{integer_column, text_column, with_sqlite_copy};
+
+const CHROMIUM_LOGIN_FILES: [&str; 2] = ["Login Data", "Login Data For Account"];
+
+pub fn get_logins_data(input: &str, outpath: &str) -> anyhow::Result<()> {
+    print!("Working on Browser Stored Credentials: ");
+    let mut login_list: Vec<LoginEntry> = Vec::new();
+
+    for profile in find_chromium_profiles(input) {
+        for login_file in CHROMIUM_LOGIN_FILES {
+            let database = format!("{}/{login_file}", profile.path);
+            if !Path::new(&database).is_file() {
+                continue;
+            }
+            if let Err(err) = read_chromium_logins(&profile, &database, &mut login_list) {
+                error!("Failed to read {database}: {err}")
+            }
+        }
+    }
+    for profile in find_firefox_profiles(input) {
+        let path = format!("{}/logins.json", profile.path);
+        if !Path::new(&path).is_file() {
+            continue;
+        }
+        if let Err(err) = read_firefox_logins(&profile, &path, &mut login_list) {
+            error!("Failed to read {path}: {err}")
+        }
+    }
+
+    if login_list.is_empty() {
+        println!("Nothing to do here, continuing with next job.");
+        return Ok(());
+    }
+
+    write_json(&format!("{outpath}/br_logins.json"), &login_list)?;
+
+    println!("Done here!");
+    Ok(())
+}
+
+fn read_chromium_logins(
+    profile: &BrowserProfile,
+    database: &str,
+    login_list: &mut Vec<LoginEntry>,
+) -> anyhow::Result<()> {
+    with_sqlite_copy(database, |connection| {
+        let mut statement = connection.prepare("SELECT * FROM logins")?;
+        let mut rows = statement.query([])?;
+        while let Some(row) = rows.next()? {
+            login_list.push(LoginEntry {
+                user_profile: profile.user_profile.clone(),
+                browser: profile.browser.clone(),
+                browser_profile: profile.browser_profile.clone(),
+                source_file: database.to_string(),
+                origin_url: text_column(row, "origin_url").unwrap_or_default(),
+                action_url: text_column(row, "action_url").filter(|u| !u.is_empty()),
+                username: text_column(row, "username_value").filter(|u| !u.is_empty()),
+                password_present: text_column(row, "password_value").is_some_and(|p| !p.is_empty()),
+                times_used: integer_column(row, "times_used"),
+                created: integer_column(row, "date_created").and_then(convert_webkit_time),
+                last_used: integer_column(row, "date_last_used").and_then(convert_webkit_time),
+                password_changed: integer_column(row, "date_password_modified")
+                    .and_then(convert_webkit_time),
+            });
+        }
+        Ok(())
+    })
+}
+
+fn read_firefox_logins(
+    profile: &BrowserProfile,
+    path: &str,
+    login_list: &mut Vec<LoginEntry>,
+) -> anyhow::Result<()> {
+    let logins: Value = serde_json::from_slice(&fs::read(path)?)?;
+    let Some(logins) = logins.get("logins").and_then(Value::as_array) else {
+        return Ok(());
+    };
+    for login in logins {
+        let text = |name: &str| {
+            login
+                .get(name)
+                .and_then(Value::as_str)
+                .filter(|s| !s.is_empty())
+                .map(str::to_string)
+        };
+        let time = |name: &str| {
+            login
+                .get(name)
+                .and_then(Value::as_i64)
+                .and_then(|ms| convert_unix_time_micros(ms.checked_mul(1_000)?))
+        };
+        login_list.push(LoginEntry {
+            user_profile: profile.user_profile.clone(),
+            browser: profile.browser.clone(),
+            browser_profile: profile.browser_profile.clone(),
+            source_file: path.to_string(),
+            origin_url: text("hostname").unwrap_or_default(),
+            action_url: text("formSubmitURL"),
+            username: None,
+            password_present: text("encryptedPassword").is_some(),
+            times_used: login.get("timesUsed").and_then(Value::as_i64),
+            created: time("timeCreated"),
+            last_used: time("timeLastUsed"),
+            password_changed: time("timePasswordChanged"),
+        });
+    }
+    Ok(())
+}
@@ -0,0 +1,120 @@
+// "Tracks media usage (audio and video played) on visited websites, including the time watched." - SANS Windows Forensic Analysis Poster, Media History
+// <Chromium profile>\Media History (SQLite): origin, playback (watch time per url) and playbackSession (position, title, artist, ...)
+// last_updated_time_s is in seconds since 1601, the watch time in seconds, duration and position in milliseconds
+
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use common::convert_webkit_time;
+use log::error;
+use rusqlite::Connection;
+
+use crate::browser_activity::files::browsers::{
+    find_chromium_profiles, write_json, BrowserProfile, MediaHistoryEntry,
+};
+use crate::formats::sqlite::{integer_column, text_column, with_sqlite_copy};
+
+pub fn get_media_history_data(input: &str, outpath: &str) -> anyhow::Result<()> {
+    print!("Working on Chromium Media History: ");
+    let mut media_list: Vec<MediaHistoryEntry> = Vec::new();
+
+    for profile in find_chromium_profiles(input) {
+        let database = format!("{}/Media History", profile.path);
+        if !Path::new(&database).is_file() {
+            continue;
+        }
+        let result = with_sqlite_copy(&database, |connection| {
+            read_playbacks(connection, &profile, &database, &mut media_list)?;
+            read_sessions(connection, &profile, &database, &mut media_list)
+        });
+        if let Err(err) = result {
+            error!("Failed to read {database}: {err}")
+        }
+    }
+
+    if media_list.is_empty() {
+        println!("Nothing to do here, continuing with next job.");
+        return Ok(());
+    }
+
+    write_json(&format!("{outpath}/br_media_history.json"), &media_list)?;
+
+    println!("Done here!");
+    Ok(())
+}
+
+fn read_playbacks(
+    connection: &Connection,
+    profile: &BrowserProfile,
+    database: &str,
+    media_list: &mut Vec<MediaHistoryEntry>,
+) -> anyhow::Result<()> {
+    let mut statement = connection.prepare(
+        "SELECT playback.*, origin.origin AS origin_url FROM playback \
+         LEFT JOIN origin ON playback.origin_id = origin.id",
+    )?;
+    let mut rows = statement.query([])?;
+    while let Some(row) = rows.next()? {
+        media_list.push(MediaHistoryEntry {
+            user_profile: profile.user_profile.clone(),
+            browser: profile.browser.clone(),
+            browser_profile: profile.browser_profile.clone(),
+            database: database.to_string(),
+            record_type: "playback".to_string(),
+            url: text_column(row, "url").unwrap_or_default(),
+            origin: text_column(row, "origin_url"),
+            title: None,
+            artist: None,
+            album: None,
+            source_title: None,
+            watch_time_seconds: integer_column(row, "watch_time_s").map(|s| s as f64),
+            duration_seconds: None,
+            position_seconds: None,
+            has_audio: integer_column(row, "has_audio").map(|f| f != 0),
+            has_video: integer_column(row, "has_video").map(|f| f != 0),
+            last_updated: integer_column(row, "last_updated_time_s").and_then(seconds_since_1601),
+        });
+    }
+    Ok(())
+}
+
+fn read_sessions(
+    connection: &Connection,
+    profile: &BrowserProfile,
+    database: &str,
+    media_list: &mut Vec<MediaHistoryEntry>,
+) -> anyhow::Result<()> {
+    let mut statement = connection.prepare(
+        "SELECT playbackSession.*, origin.origin AS origin_url FROM playbackSession \
+         LEFT JOIN origin ON playbackSession.origin_id = origin.id",
+    )?;
+    let mut rows = statement.query([])?;
+    while let Some(row) = rows.next()? {
+        let text = |name: &str| text_column(row, name).filter(|t| !t.is_empty());
+        let milliseconds = |name: &str| integer_column(row, name).map(|ms| ms as f64 / 1_000.0);
+        media_list.push(MediaHistoryEntry {
+            user_profile: profile.user_profile.clone(),
+            browser: profile.browser.clone(),
+            browser_profile: profile.browser_profile.clone(),
+            database: database.to_string(),
+            record_type: "session".to_string(),
+            url: text_column(row, "url").unwrap_or_default(),
+            origin: text("origin_url"),
+            title: text("title"),
+            artist: text("artist"),
+            album: text("album"),
+            source_title: text("source_title"),
+            watch_time_seconds: None,
+            duration_seconds: milliseconds("duration_ms"),
+            position_seconds: milliseconds("position_ms"),
+            has_audio: None,
+            has_video: None,
+            last_updated: integer_column(row, "last_updated_time_s").and_then(seconds_since_1601),
+        });
+    }
+    Ok(())
+}
+
+fn seconds_since_1601(seconds: i64) -> Option<DateTime<Utc>> {
+    convert_webkit_time(seconds.checked_mul(1_000_000)?)
+}
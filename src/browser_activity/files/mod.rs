@@ -0,0 +1,17 @@
+pub mod autofill;
+pub mod browsers;
+pub mod cache;
+pub mod chromium_history;
+pub mod chromium_sessions;
+pub mod cookies;
+pub mod extensions;
+pub mod firefox_formhistory;
+pub mod firefox_places;
+pub mod firefox_sessionstore;
+pub mod html5_filesystem;
+pub mod indexeddb;
+pub mod logins;
+pub mod media_history;
+pub mod preferences;
+pub mod url_parameters;
+pub mod web_storage;
@@ -0,0 +1,168 @@
+// "Configuration information for the browser, like the download directory, the startup pages and the synchronized account." - SANS Windows Forensic Analysis Poster, Browser Preferences
+// <Chromium profile>\Preferences (JSON)
+// <Firefox profile>\prefs.js: user_pref("name", value); lines, only values that differ from the defaults are stored
+// <Firefox profile>\search.json.mozlz4: the default search engine of newer versions
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use log::error;
+use serde_json::Value;
+
+use crate::browser_activity::files::browsers::{
+    find_chromium_profiles, find_firefox_profiles, read_json, write_json, BrowserProfile,
+    PreferencesEntry,
+};
+use crate::formats::mozlz4::read_mozlz4;
+
+pub fn get_preferences_data(input: &str, outpath: &str) -> anyhow::Result<()> {
+    print!("Working on Browser Preferences: ");
+    let mut preferences_list: Vec<PreferencesEntry> = Vec::new();
+
+    for profile in find_chromium_profiles(input) {
+        let path = format!("{}/Preferences", profile.path);
+        if !Path::new(&path).is_file() {
+            continue;
+        }
+        match read_chromium_preferences(&profile, &path) {
+            Ok(preferences) => preferences_list.push(preferences),
+            Err(err) => error!("Failed to read {path}: {err}"),
+        }
+    }
+    for profile in find_firefox_profiles(input) {
+        let path = format!("{}/prefs.js", profile.path);
+        if !Path::new(&path).is_file() {
+            continue;
+        }
+        match read_firefox_preferences(&profile, &path) {
+            Ok(preferences) => preferences_list.push(preferences),
+            Err(err) => error!("Failed to read {path}: {err}"),
+        }
+    }
+
+    if preferences_list.is_empty() {
+        println!("Nothing to do here, continuing with next job.");
+        return Ok(());
+    }
+
+    write_json(&format!("{outpath}/br_preferences.json"), &preferences_list)?;
+
+    println!("Done here!");
+    Ok(())
+}
+
+fn read_chromium_preferences(
+    profile: &BrowserProfile,
+    path: &str,
+) -> anyhow::Result<PreferencesEntry> {
+    let preferences = read_json(path)?;
+    let text = |pointers: &[&str]| {
+        pointers
+            .iter()
+            .find_map(|pointer| preferences.pointer(pointer).and_then(Value::as_str))
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+    };
+    Ok(PreferencesEntry {
+        user_profile: profile.user_profile.clone(),
+        browser: profile.browser.clone(),
+        browser_profile: profile.browser_profile.clone(),
+        source_file: path.to_string(),
+        download_directory: text(&["/download/default_directory"]),
+        last_download_directory: text(&[
+            "/savefile/default_directory",
+            "/selectfile/last_directory",
+        ]),
+        sync_account: text(&[
+            "/account_info/0/email",
+            "/google/services/last_username",
+            "/google/services/last_signed_in_username",
+        ]),
+        homepage: text(&["/homepage"]),
+        startup_urls: preferences
+            .pointer("/session/startup_urls")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .map(str::to_string)
+            .collect(),
+        search_engine: text(&[
+            "/default_search_provider_data/template_url_data/short_name",
+            "/default_search_provider/name",
+        ]),
+    })
+}
+
+fn read_firefox_preferences(
+    profile: &BrowserProfile,
+    path: &str,
+) -> anyhow::Result<PreferencesEntry> {
+    let prefs = parse_prefs_js(&String::from_utf8_lossy(&fs::read(path)?));
+    let text = |names: &[&str]| {
+        names
+            .iter()
+            .find_map(|name| prefs.get(*name).and_then(Value::as_str))
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+    };
+    let homepage = text(&["browser.startup.homepage"]);
+    // the search engine moved from prefs.js to search.json.mozlz4
+    let search_engine = text(&[
+        "browser.search.defaultenginename",
+        "browser.urlbar.placeholderName",
+    ])
+    .or_else(|| {
+        let search = read_mozlz4(&format!("{}/search.json.mozlz4", profile.path)).ok()?;
+        let search: Value = serde_json::from_slice(&search).ok()?;
+        ["/metaData/current", "/metaData/defaultEngineId"]
+            .iter()
+            .find_map(|pointer| search.pointer(pointer).and_then(Value::as_str))
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+    });
+    Ok(PreferencesEntry {
+        user_profile: profile.user_profile.clone(),
+        browser: profile.browser.clone(),
+        browser_profile: profile.browser_profile.clone(),
+        source_file: path.to_string(),
+        download_directory: text(&["browser.download.dir"]),
+        last_download_directory: text(&["browser.download.lastDir"]),
+        sync_account: text(&["services.sync.username"]),
+        // multiple start pages are separated by "|"
+        startup_urls: homepage
+            .iter()
+            .flat_map(|h| h.split('|'))
+            .map(str::to_string)
+            .collect(),
+        homepage,
+        search_engine,
+    })
+}
+
+/// name and value of all user_pref lines, the arguments are valid JSON
+fn parse_prefs_js(prefs_js: &str) -> HashMap<String, Value> {
+    let mut prefs = HashMap::new();
+    for line in prefs_js.lines() {
+        let Some(arguments) = line
+            .trim()
+            .strip_prefix("user_pref(")
+            .and_then(|l| l.strip_suffix(");"))
+        else {
+            continue;
+        };
+        let Ok(Value::Array(mut arguments)) = serde_json::from_str(&format!("[{arguments}]"))
+        else {
+            continue;
+        };
+        if arguments.len() != 2 {
+            continue;
+        }
+        let value = arguments.pop().unwrap_or_default();
+        if let Some(name) = arguments.pop().as_ref().and_then(Value::as_str) {
+            prefs.insert(name.to_string(), value);
+        }
+    }
+    prefs
+}
@@ -0,0 +1,286 @@
+// "URL parameters can show what the user searched for, the locations they looked at on maps and how they got to a website." - SANS Windows Forensic Analysis Poster, Browser URL Parameters
+// every visit of the Chromium and Firefox history is analyzed:
+// search terms of Google, Bing and DuckDuckGo, the Google "ei" parameter (u32 unix time in the first 4 bytes of base64url),
+// coordinates of Google Maps ("@lat,lng" and "!3dlat!4dlng"), Bing Maps ("cp=lat~lng") and OpenStreetMap ("#map=zoom/lat/lng")
+// and known tracking parameters (campaigns and click ids of ads)
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use common::convert_unix_time;
+use log::error;
+use regex::Regex;
+
+use crate::browser_activity::files::browsers::{
+    find_chromium_profiles, find_firefox_profiles, write_json, HistoryEntry, UrlParameterEntry,
+};
+use crate::browser_activity::files::{chromium_history, firefox_places};
+use crate::formats::sqlite::with_sqlite_copy;
+
+// name, label of the host, search parameter and paths of the result pages (empty: all)
+const SEARCH_ENGINES: [(&str, &str, &str, &[&str]); 3] = [
+    (
+        "Google",
+        "google",
+        "q",
+        &["/search", "/webhp", "/complete/search"],
+    ),
+    ("Bing", "bing", "q", &["/search"]),
+    ("DuckDuckGo", "duckduckgo", "q", &[]),
+];
+const TRACKING_PARAMETERS: [&str; 22] = [
+    "utm_source",
+    "utm_medium",
+    "utm_campaign",
+    "utm_term",
+    "utm_content",
+    "utm_id",
+    "gclid",
+    "gclsrc",
+    "gbraid",
+    "wbraid",
+    "dclid",
+    "fbclid",
+    "msclkid",
+    "yclid",
+    "twclid",
+    "ttclid",
+    "li_fat_id",
+    "igshid",
+    "mc_cid",
+    "mc_eid",
+    "_ga",
+    "_gl",
+];
+// parameters with "lat,lng" (Bing: "lat~lng") on map websites
+const COORDINATE_PARAMETERS: [&str; 5] = ["ll", "sll", "center", "q", "cp"];
+
+pub fn get_url_parameters_data(input: &str, outpath: &str) -> anyhow::Result<()> {
+    print!("Working on Browser URL Parameters: ");
+    let mut history_list: Vec<HistoryEntry> = Vec::new();
+
+    for profile in find_chromium_profiles(input) {
+        let database = format!("{}/History", profile.path);
+        if !Path::new(&database).is_file() {
+            continue;
+        }
+        let result = with_sqlite_copy(&database, |connection| {
+            chromium_history::read_history(connection, &profile, &database, &mut history_list)
+        });
+        if let Err(err) = result {
+            error!("Failed to read {database}: {err}")
+        }
+    }
+    for profile in find_firefox_profiles(input) {
+        let database = format!("{}/places.sqlite", profile.path);
+        if !Path::new(&database).is_file() {
+            continue;
+        }
+        let result = with_sqlite_copy(&database, |connection| {
+            firefox_places::read_history(connection, &profile, &database, &mut history_list)
+        });
+        if let Err(err) = result {
+            error!("Failed to read {database}: {err}")
+        }
+    }
+
+    let patterns = CoordinatePatterns::new()?;
+    let parameter_list: Vec<UrlParameterEntry> = history_list
+        .into_iter()
+        .filter_map(|entry| analyze_url(entry, &patterns))
+        .collect();
+
+    if parameter_list.is_empty() {
+        println!("Nothing to do here, continuing with next job.");
+        return Ok(());
+    }
+
+    write_json(
+        &format!("{outpath}/br_url_parameters.json"),
+        &parameter_list,
+    )?;
+
+    println!("Done here!");
+    Ok(())
+}
+
+struct CoordinatePatterns {
+    // Google Maps place, more precise than the map center
+    place: Regex,
+    map_center: Regex,
+    osm: Regex,
+    pair: Regex,
+}
+
+impl CoordinatePatterns {
+    fn new() -> anyhow::Result<Self> {
+        Ok(CoordinatePatterns {
+            place: Regex::new(r"!3d(-?\d+\.\d+)!4d(-?\d+\.\d+)")?,
+            map_center: Regex::new(r"@(-?\d+\.\d+),(-?\d+\.\d+)")?,
+            osm: Regex::new(r"map=\d+/(-?\d+\.\d+)/(-?\d+\.\d+)")?,
+            pair: Regex::new(r"^\s*(-?\d+\.\d+)\s*[,~]\s*(-?\d+\.\d+)\s*$")?,
+        })
+    }
+}
+
+/// None if the url has no search terms, coordinates or tracking parameters
+fn analyze_url(entry: HistoryEntry, patterns: &CoordinatePatterns) -> Option<UrlParameterEntry> {
+    let (host, path, query, fragment) = split_url(&entry.url)?;
+    let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+    for pair in query.split('&').chain(fragment.split('&')) {
+        if let Some((name, value)) = pair.split_once('=') {
+            parameters.insert(percent_decode(name), percent_decode(value));
+        }
+    }
+    let host_labels: Vec<&str> = host.split('.').collect();
+
+    let mut search_engine = None;
+    let mut search_terms = None;
+    for (name, label, parameter, paths) in SEARCH_ENGINES {
+        if !host_labels.contains(&label) {
+            continue;
+        }
+        if !paths.is_empty() && !paths.iter().any(|p| path.starts_with(p)) {
+            continue;
+        }
+        if let Some(terms) = parameters.get(parameter).filter(|t| !t.is_empty()) {
+            search_engine = Some(name.to_string());
+            search_terms = Some(terms.clone());
+        }
+    }
+    let search_time = if search_engine.as_deref() == Some("Google") {
+        parameters.get("ei").and_then(|ei| google_ei_time(ei))
+    } else {
+        None
+    };
+
+    // the map websites, a plain "lat,lng" elsewhere is not a location
+    let is_map = host_labels.iter().any(|l| l.contains("map")) || path.starts_with("/maps");
+    let mut coordinates = None;
+    if is_map {
+        // Google Maps searches and places are in the path
+        for prefix in ["/maps/search/", "/maps/place/"] {
+            if let Some(terms) = path.strip_prefix(prefix) {
+                let terms = terms.split('/').next().unwrap_or_default();
+                if search_terms.is_none() && !terms.is_empty() && !terms.starts_with('@') {
+                    search_engine = Some("Google Maps".to_string());
+                    search_terms = Some(percent_decode(terms));
+                }
+            }
+        }
+        let parameter_coordinates = COORDINATE_PARAMETERS
+            .iter()
+            .filter_map(|name| parameters.get(*name))
+            .find_map(|value| captured_pair(&patterns.pair, value));
+        coordinates = captured_pair(&patterns.place, path)
+            .or_else(|| captured_pair(&patterns.map_center, path))
+            .or_else(|| captured_pair(&patterns.osm, fragment))
+            .or(parameter_coordinates);
+    }
+
+    let tracking_parameters: BTreeMap<String, String> = parameters
+        .iter()
+        .filter(|(name, _)| TRACKING_PARAMETERS.contains(&name.to_lowercase().as_str()))
+        .map(|(name, value)| (name.clone(), value.clone()))
+        .collect();
+
+    if search_terms.is_none() && coordinates.is_none() && tracking_parameters.is_empty() {
+        return None;
+    }
+    Some(UrlParameterEntry {
+        user_profile: entry.user_profile,
+        browser: entry.browser,
+        browser_profile: entry.browser_profile,
+        database: entry.database,
+        visit_time: entry.visit_time,
+        search_engine,
+        search_terms,
+        search_time,
+        latitude: coordinates.map(|(latitude, _)| latitude),
+        longitude: coordinates.map(|(_, longitude)| longitude),
+        tracking_parameters,
+        parameters,
+        url: entry.url,
+    })
+}
+
+/// host, path, query and fragment of an url, None if it has no host
+fn split_url(url: &str) -> Option<(&str, &str, &str, &str)> {
+    let (_, rest) = url.split_once("://")?;
+    let (rest, fragment) = rest.split_once('#').unwrap_or((rest, ""));
+    let (rest, query) = rest.split_once('?').unwrap_or((rest, ""));
+    let (host, path) = match rest.find('/') {
+        Some(index) => rest.split_at(index),
+        None => (rest, "/"),
+    };
+    // user info and port are not part of the host name
+    let host = host.rsplit('@').next().unwrap_or(host);
+    let host = host.split(':').next().unwrap_or(host);
+    Some((host, path, query, fragment))
+}
+
+/// "+" is a space, "%xx" a byte of UTF-8
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'+' => decoded.push(b' '),
+            b'%' => {
+                let byte = text
+                    .get(index + 1..index + 3)
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+                if let Some(byte) = byte {
+                    decoded.push(byte);
+                    index += 2;
+                } else {
+                    decoded.push(b'%');
+                }
+            }
+            byte => decoded.push(byte),
+        }
+        index += 1;
+    }
+    String::from_utf8_lossy(&decoded).to_string()
+}
+
+fn captured_pair(pattern: &Regex, text: &str) -> Option<(f64, f64)> {
+    let captures = pattern.captures(text)?;
+    let latitude: f64 = captures.get(1)?.as_str().parse().ok()?;
+    let longitude: f64 = captures.get(2)?.as_str().parse().ok()?;
+    ((-90.0..=90.0).contains(&latitude) && (-180.0..=180.0).contains(&longitude))
+        .then_some((latitude, longitude))
+}
+
+/// the first 4 bytes of the base64url decoded "ei" parameter are the unix time in seconds
+fn google_ei_time(ei: &str) -> Option<DateTime<Utc>> {
+    let bytes = base64url_decode(ei)?;
+    let seconds = u32::from_le_bytes(bytes.get(0..4)?.try_into().ok()?);
+    convert_unix_time(i64::from(seconds))
+}
+
+fn base64url_decode(text: &str) -> Option<Vec<u8>> {
+    let mut decoded = Vec::new();
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+    for c in text.trim_end_matches('=').bytes() {
+        let value = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'-' | b'+' => 62,
+            b'_' | b'/' => 63,
+            _ => return None,
+        };
+        buffer = (buffer << 6) | u32::from(value);
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            decoded.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    Some(decoded)
+}
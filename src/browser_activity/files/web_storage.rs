@@ -0,0 +1,254 @@
+// "HTML5 Web Storage is considered to be a super cookie, websites can store data that persists across browser sessions." - SANS Windows Forensic Analysis Poster, HTML5 Web Storage
+// <Chromium profile>\Local Storage\leveldb (LevelDB): "_<origin>\0<key>" -> value, key and value start with an encoding byte (0 UTF-16LE, 1 Latin-1)
+// <Chromium profile>\Session Storage (LevelDB): "namespace-<guid>-<origin>" -> map id, "map-<map id>-<key>" -> UTF-16LE value
+// <Firefox profile>\webappsstore.sqlite (older versions): webappsstore2 with the reversed host as origin key
+// <Firefox profile>\storage\default\<origin>\ls\data.sqlite (newer versions): data with (Snappy compressed) UTF-8 values
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use log::error;
+use rusqlite::types::ValueRef;
+
+use crate::browser_activity::files::browsers::{
+    find_chromium_profiles, find_firefox_profiles, firefox_storage_origins, write_json,
+    BrowserProfile, WebStorageEntry,
+};
+use crate::formats::leveldb::read_leveldb;
+use crate::formats::sqlite::{integer_column, text_column, with_sqlite_copy};
+
+const LOCAL_STORAGE: &str = "Local Storage";
+const SESSION_STORAGE: &str = "Session Storage";
+
+pub fn get_web_storage_data(input: &str, outpath: &str) -> anyhow::Result<()> {
+    print!("Working on HTML5 Web Storage: ");
+    let mut storage_list: Vec<WebStorageEntry> = Vec::new();
+
+    for profile in find_chromium_profiles(input) {
+        let folder = format!("{}/Local Storage/leveldb", profile.path);
+        if Path::new(&folder).is_dir() {
+            if let Err(err) = read_chromium_local_storage(&profile, &folder, &mut storage_list) {
+                error!("Failed to read {folder}: {err}")
+            }
+        }
+        let folder = format!("{}/Session Storage", profile.path);
+        if Path::new(&folder).is_dir() {
+            if let Err(err) = read_chromium_session_storage(&profile, &folder, &mut storage_list) {
+                error!("Failed to read {folder}: {err}")
+            }
+        }
+    }
+    for profile in find_firefox_profiles(input) {
+        let database = format!("{}/webappsstore.sqlite", profile.path);
+        if Path::new(&database).is_file() {
+            if let Err(err) = read_firefox_webappsstore(&profile, &database, &mut storage_list) {
+                error!("Failed to read {database}: {err}")
+            }
+        }
+        for (origin, origin_path) in firefox_storage_origins(&profile) {
+            let database = format!("{origin_path}/ls/data.sqlite");
+            if !Path::new(&database).is_file() {
+                continue;
+            }
+            if let Err(err) =
+                read_firefox_local_storage(&profile, &origin, &database, &mut storage_list)
+            {
+                error!("Failed to read {database}: {err}")
+            }
+        }
+    }
+
+    if storage_list.is_empty() {
+        println!("Nothing to do here, continuing with next job.");
+        return Ok(());
+    }
+
+    write_json(&format!("{outpath}/br_web_storage.json"), &storage_list)?;
+
+    println!("Done here!");
+    Ok(())
+}
+
+fn read_chromium_local_storage(
+    profile: &BrowserProfile,
+    folder: &str,
+    storage_list: &mut Vec<WebStorageEntry>,
+) -> anyhow::Result<()> {
+    for record in read_leveldb(folder)? {
+        // META:<origin>, METAACCESS:<origin> and VERSION are metadata
+        let Some(key) = record.key.strip_prefix(b"_") else {
+            continue;
+        };
+        let Some(separator) = key.iter().position(|b| *b == 0) else {
+            continue;
+        };
+        storage_list.push(WebStorageEntry {
+            user_profile: profile.user_profile.clone(),
+            browser: profile.browser.clone(),
+            browser_profile: profile.browser_profile.clone(),
+            source_file: record.source_file,
+            storage_type: LOCAL_STORAGE.to_string(),
+            origin: String::from_utf8_lossy(&key[..separator]).to_string(),
+            database: None,
+            key: decode_prefixed_string(&key[separator + 1..]),
+            value: decode_prefixed_string(&record.value),
+            live: record.live,
+        });
+    }
+    Ok(())
+}
+
+fn read_chromium_session_storage(
+    profile: &BrowserProfile,
+    folder: &str,
+    storage_list: &mut Vec<WebStorageEntry>,
+) -> anyhow::Result<()> {
+    let records = read_leveldb(folder)?;
+
+    // the namespace records map the origins to their map ids
+    let mut map_origins: HashMap<String, String> = HashMap::new();
+    for record in &records {
+        let key = String::from_utf8_lossy(&record.key);
+        let Some(namespace) = key.strip_prefix("namespace-") else {
+            continue;
+        };
+        // the namespace id is a guid with "_" instead of "-"
+        let Some((_, origin)) = namespace.split_once('-') else {
+            continue;
+        };
+        map_origins.insert(
+            String::from_utf8_lossy(&record.value).to_string(),
+            origin.to_string(),
+        );
+    }
+
+    for record in records {
+        let Some(map) = record.key.strip_prefix(b"map-") else {
+            continue;
+        };
+        let Some(separator) = map.iter().position(|b| *b == b'-') else {
+            continue;
+        };
+        let map_id = String::from_utf8_lossy(&map[..separator]).to_string();
+        storage_list.push(WebStorageEntry {
+            user_profile: profile.user_profile.clone(),
+            browser: profile.browser.clone(),
+            browser_profile: profile.browser_profile.clone(),
+            source_file: record.source_file,
+            storage_type: SESSION_STORAGE.to_string(),
+            origin: map_origins.get(&map_id).cloned().unwrap_or_default(),
+            database: None,
+            key: String::from_utf8_lossy(&map[separator + 1..]).to_string(),
+            value: decode_utf16_le(&record.value),
+            live: record.live,
+        });
+    }
+    Ok(())
+}
+
+fn read_firefox_webappsstore(
+    profile: &BrowserProfile,
+    database: &str,
+    storage_list: &mut Vec<WebStorageEntry>,
+) -> anyhow::Result<()> {
+    with_sqlite_copy(database, |connection| {
+        let mut statement = connection.prepare("SELECT * FROM webappsstore2")?;
+        let mut rows = statement.query([])?;
+        while let Some(row) = rows.next()? {
+            // the origin key replaced the scope in newer versions
+            let origin_key = text_column(row, "originKey")
+                .or_else(|| text_column(row, "scope"))
+                .unwrap_or_default();
+            storage_list.push(WebStorageEntry {
+                user_profile: profile.user_profile.clone(),
+                browser: profile.browser.clone(),
+                browser_profile: profile.browser_profile.clone(),
+                source_file: database.to_string(),
+                storage_type: LOCAL_STORAGE.to_string(),
+                origin: reversed_origin(&origin_key),
+                database: None,
+                key: text_column(row, "key").unwrap_or_default(),
+                value: text_column(row, "value").unwrap_or_default(),
+                live: true,
+            });
+        }
+        Ok(())
+    })
+}
+
+fn read_firefox_local_storage(
+    profile: &BrowserProfile,
+    origin: &str,
+    database: &str,
+    storage_list: &mut Vec<WebStorageEntry>,
+) -> anyhow::Result<()> {
+    with_sqlite_copy(database, |connection| {
+        let mut statement = connection.prepare("SELECT * FROM data")?;
+        let mut rows = statement.query([])?;
+        while let Some(row) = rows.next()? {
+            let value = match row.get_ref("value") {
+                Ok(ValueRef::Blob(value)) | Ok(ValueRef::Text(value)) => value.to_vec(),
+                _ => Vec::new(),
+            };
+            // "compressed" was renamed to "compression_type" in newer versions
+            let compressed = integer_column(row, "compression_type")
+                .or_else(|| integer_column(row, "compressed"))
+                .is_some_and(|c| c != 0);
+            let value = if compressed {
+                snap::raw::Decoder::new()
+                    .decompress_vec(&value)
+                    .unwrap_or(value)
+            } else {
+                value
+            };
+            storage_list.push(WebStorageEntry {
+                user_profile: profile.user_profile.clone(),
+                browser: profile.browser.clone(),
+                browser_profile: profile.browser_profile.clone(),
+                source_file: database.to_string(),
+                storage_type: LOCAL_STORAGE.to_string(),
+                origin: origin.to_string(),
+                database: None,
+                key: text_column(row, "key").unwrap_or_default(),
+                value: String::from_utf8_lossy(&value).to_string(),
+                live: true,
+            });
+        }
+        Ok(())
+    })
+}
+
+/// Chromium strings that start with an encoding byte, 0 is UTF-16LE and 1 Latin-1
+fn decode_prefixed_string(data: &[u8]) -> String {
+    match data.split_first() {
+        Some((0, text)) => decode_utf16_le(text),
+        Some((1, text)) => text.iter().map(|b| char::from(*b)).collect(),
+        _ => String::from_utf8_lossy(data).to_string(),
+    }
+}
+
+fn decode_utf16_le(data: &[u8]) -> String {
+    let chars: Vec<u16> = data
+        .chunks_exact(2)
+        .map(|c| u16::from_le_bytes([c[0], c[1]]))
+        .collect();
+    String::from_utf16_lossy(&chars)
+}
+
+/// "moc.elpmaxe.:https:443" -> "https://example.com:443"
+fn reversed_origin(origin_key: &str) -> String {
+    let mut parts = origin_key.splitn(3, ':');
+    let host: String = parts
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .rev()
+        .collect::<String>()
+        .trim_start_matches('.')
+        .to_string();
+    match (parts.next(), parts.next()) {
+        (Some(scheme), Some(port)) => format!("{scheme}://{host}:{port}"),
+        (Some(scheme), None) => format!("{scheme}://{host}"),
+        _ => host,
+    }
+}
@@ -0,0 +1,75 @@
+// FilesOnly Processing Mode for Browser Activity
+use log::error;
+
+use crate::browser_activity::files::autofill::get_autofill_data;
+use crate::browser_activity::files::cache::get_browser_cache_data;
+use crate::browser_activity::files::chromium_history::get_chromium_history_data;
+use crate::browser_activity::files::chromium_sessions::get_chromium_sessions_data;
+use crate::browser_activity::files::cookies::get_cookies_data;
+use crate::browser_activity::files::extensions::get_extensions_data;
+use crate::browser_activity::files::firefox_formhistory::get_firefox_formhistory_data;
+use crate::browser_activity::files::firefox_places::get_firefox_places_data;
+use crate::browser_activity::files::firefox_sessionstore::get_firefox_sessionstore_data;
+use crate::browser_activity::files::html5_filesystem::get_html5_filesystem_data;
+use crate::browser_activity::files::indexeddb::get_indexeddb_data;
+use crate::browser_activity::files::logins::get_logins_data;
+use crate::browser_activity::files::media_history::get_media_history_data;
+use crate::browser_activity::files::preferences::get_preferences_data;
+use crate::browser_activity::files::url_parameters::get_url_parameters_data;
+use crate::browser_activity::files::web_storage::get_web_storage_data;
+
+pub fn get_browseractivity_file_data(
+    input: &str,
+    outpath: &str,
+    export_cache: bool,
+) -> anyhow::Result<()> {
+    if let Err(err) = get_chromium_history_data(input, outpath) {
+        error!("Failed to get Chromium History: {err}")
+    }
+    if let Err(err) = get_firefox_places_data(input, outpath) {
+        error!("Failed to get Firefox History: {err}")
+    }
+    if let Err(err) = get_firefox_formhistory_data(input, outpath) {
+        error!("Failed to get Firefox Form History: {err}")
+    }
+    if let Err(err) = get_firefox_sessionstore_data(input, outpath) {
+        error!("Failed to get Firefox Session Restore: {err}")
+    }
+    if let Err(err) = get_cookies_data(input, outpath) {
+        error!("Failed to get Browser Cookies: {err}")
+    }
+    if let Err(err) = get_autofill_data(input, outpath) {
+        error!("Failed to get Browser Autofill: {err}")
+    }
+    if let Err(err) = get_logins_data(input, outpath) {
+        error!("Failed to get Browser Stored Credentials: {err}")
+    }
+    if let Err(err) = get_browser_cache_data(input, outpath, export_cache) {
+        error!("Failed to get Browser Cache: {err}")
+    }
+    if let Err(err) = get_chromium_sessions_data(input, outpath) {
+        error!("Failed to get Chromium Session Restore: {err}")
+    }
+    if let Err(err) = get_extensions_data(input, outpath) {
+        error!("Failed to get Browser Extensions: {err}")
+    }
+    if let Err(err) = get_preferences_data(input, outpath) {
+        error!("Failed to get Browser Preferences: {err}")
+    }
+    if let Err(err) = get_web_storage_data(input, outpath) {
+        error!("Failed to get HTML5 Web Storage: {err}")
+    }
+    if let Err(err) = get_indexeddb_data(input, outpath) {
+        error!("Failed to get IndexedDB: {err}")
+    }
+    if let Err(err) = get_html5_filesystem_data(input, outpath) {
+        error!("Failed to get HTML5 FileSystem: {err}")
+    }
+    if let Err(err) = get_media_history_data(input, outpath) {
+        error!("Failed to get Chromium Media History: {err}")
+    }
+    if let Err(err) = get_url_parameters_data(input, outpath) {
+        error!("Failed to get Browser URL Parameters: {err}")
+    }
+    Ok(())
+}
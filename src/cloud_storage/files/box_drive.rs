@@ -0,0 +1,147 @@
+// "Box Drive presents the Box cloud storage as virtual file system, files are only cached locally when they are opened or marked for offline use." - SANS Windows Forensic Analysis Poster, Box Drive
+// <user profile>\AppData\Local\Box\Box\data\streemsfs.db (SQLite): fsnodes (inodeId, parentInodeId, name, created/modified/accessed
+// timestamps) and cachefiles (size of the locally cached content)
+// <user profile>\AppData\Local\Box\Box\data\sync.db (SQLite): box_item with the Box id, parent, name, size and SHA1 of the cloud file
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use common::node_path;
+use rusqlite::{Connection, Row};
+
+use crate::cloud_storage::files::cloud_files::{
+    find_user_folders, read_database, unix_time, CloudFileEntry,
+};
+use crate::formats::sqlite::{integer_column, text_column};
+
+const PROVIDER: &str = "Box Drive";
+
+/// the databases of all users
+pub fn read_box_drive(input: &str, file_list: &mut Vec<CloudFileEntry>) -> anyhow::Result<()> {
+    for (user_profile, data) in find_user_folders(input, "AppData/Local/Box/Box/data") {
+        let database = Path::new(&data).join("streemsfs.db");
+        if database.is_file() {
+            read_database(&database, |connection, database| {
+                read_streemsfs(connection, &user_profile, database, file_list)
+            });
+        }
+        let database = Path::new(&data).join("sync.db");
+        if database.is_file() {
+            read_database(&database, |connection, database| {
+                read_sync(connection, &user_profile, database, file_list)
+            });
+        }
+    }
+    Ok(())
+}
+
+fn read_streemsfs(
+    connection: &Connection,
+    user_profile: &str,
+    database: &str,
+    file_list: &mut Vec<CloudFileEntry>,
+) -> anyhow::Result<()> {
+    // size of the cached content by inode, only known for cached files
+    let mut cached_sizes: HashMap<i64, i64> = HashMap::new();
+    if let Ok(mut statement) = connection.prepare("SELECT * FROM cachefiles") {
+        let mut rows = statement.query([])?;
+        while let Some(row) = rows.next()? {
+            if let (Some(inode), Some(size)) =
+                (integer_column(row, "inodeId"), integer_column(row, "size"))
+            {
+                cached_sizes.insert(inode, size);
+            }
+        }
+    }
+
+    let mut items = Vec::new();
+    let mut nodes: HashMap<i64, (Option<i64>, String)> = HashMap::new();
+    let mut statement = connection.prepare("SELECT * FROM fsnodes")?;
+    let mut rows = statement.query([])?;
+    while let Some(row) = rows.next()? {
+        let inode = integer_column(row, "inodeId").unwrap_or_default();
+        let name = text_column(row, "name").unwrap_or_default();
+        nodes.insert(inode, (integer_column(row, "parentInodeId"), name.clone()));
+        let time = |name: &str| integer_column(row, name).and_then(unix_time);
+        items.push((
+            inode,
+            CloudFileEntry {
+                user_profile: user_profile.to_string(),
+                provider: PROVIDER.to_string(),
+                account: None,
+                source_file: database.to_string(),
+                item_type: None,
+                cloud_id: text_column(row, "boxId"),
+                name,
+                path: String::new(),
+                size: cached_sizes.get(&inode).copied(),
+                hashes: BTreeMap::new(),
+                created: time("createdAtTimestamp"),
+                modified: time("modifiedAtTimestamp"),
+                accessed: time("accessedAtTimestamp"),
+                trashed: None,
+            },
+        ));
+    }
+    for (inode, mut entry) in items {
+        entry.path = node_path(&nodes, &inode).join("/");
+        file_list.push(entry);
+    }
+    Ok(())
+}
+
+fn read_sync(
+    connection: &Connection,
+    user_profile: &str,
+    database: &str,
+    file_list: &mut Vec<CloudFileEntry>,
+) -> anyhow::Result<()> {
+    let mut items = Vec::new();
+    let mut nodes: HashMap<String, (Option<String>, String)> = HashMap::new();
+    let mut statement = connection.prepare("SELECT * FROM box_item")?;
+    let mut rows = statement.query([])?;
+    while let Some(row) = rows.next()? {
+        let box_id = first_text(row, &["box_id", "id"]).unwrap_or_default();
+        let name = text_column(row, "name").unwrap_or_default();
+        nodes.insert(
+            box_id.clone(),
+            (
+                first_text(row, &["parent_item_id", "parent_box_id", "parent_id"]),
+                name.clone(),
+            ),
+        );
+        let mut hashes = BTreeMap::new();
+        if let Some(sha1) = text_column(row, "checksum").filter(|h| !h.is_empty()) {
+            hashes.insert("sha1".to_string(), sha1);
+        }
+        let time = |name: &str| integer_column(row, name).and_then(unix_time);
+        items.push((
+            box_id.clone(),
+            CloudFileEntry {
+                user_profile: user_profile.to_string(),
+                provider: PROVIDER.to_string(),
+                account: None,
+                source_file: database.to_string(),
+                item_type: text_column(row, "item_type"),
+                cloud_id: Some(box_id),
+                name,
+                path: String::new(),
+                size: integer_column(row, "size"),
+                hashes,
+                created: time("content_created_at"),
+                modified: time("content_updated_at"),
+                accessed: None,
+                trashed: None,
+            },
+        ));
+    }
+    for (box_id, mut entry) in items {
+        entry.path = node_path(&nodes, &box_id).join("/");
+        file_list.push(entry);
+    }
+    Ok(())
+}
+
+fn first_text(row: &Row, names: &[&str]) -> Option<String> {
+    names.iter().find_map(|name| text_column(row, name))
+}
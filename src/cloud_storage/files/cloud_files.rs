@@ -0,0 +1,107 @@
+// "Cloud storage clients keep a local database of the files stored in the cloud, including files that were never downloaded." - SANS Windows Forensic Analysis Poster, Cloud Storage
+// unified file inventory of Google Drive for Desktop, Dropbox and Box Drive, one entry per file or folder
+// the OneDrive inventory (cs_onedrive_sync.json) has its own, more detailed output
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use common::{
+    convert_unix_time, convert_unix_time_micros, file_name, find_file_case_insensitive,
+    find_user_profiles,
+};
+use log::error;
+use rusqlite::Connection;
+use serde::Serialize;
+
+use crate::cloud_storage::files::{box_drive, dropbox, google_drive};
+use crate::formats::sqlite::with_sqlite_copy;
+
+#[derive(Debug, Serialize)]
+pub struct CloudFileEntry {
+    pub user_profile: String,
+    pub provider: String,
+    pub account: Option<String>,
+    pub source_file: String,
+    pub item_type: Option<String>,
+    pub cloud_id: Option<String>,
+    pub name: String,
+    // path below the root of the cloud storage, separated by "/"
+    pub path: String,
+    pub size: Option<i64>,
+    // algorithm -> hash, only when the client stores them
+    pub hashes: BTreeMap<String, String>,
+    pub created: Option<DateTime<Utc>>,
+    pub modified: Option<DateTime<Utc>>,
+    pub accessed: Option<DateTime<Utc>>,
+    pub trashed: Option<bool>,
+}
+
+pub fn get_cloud_files_data(input: &str, outpath: &str) -> anyhow::Result<()> {
+    print!("Working on Cloud Storage Files: ");
+    let mut file_list: Vec<CloudFileEntry> = Vec::new();
+
+    if let Err(err) = google_drive::read_google_drive(input, &mut file_list) {
+        error!("Failed to get Google Drive for Desktop: {err}")
+    }
+    if let Err(err) = dropbox::read_dropbox(input, &mut file_list) {
+        error!("Failed to get Dropbox: {err}")
+    }
+    if let Err(err) = box_drive::read_box_drive(input, &mut file_list) {
+        error!("Failed to get Box Drive: {err}")
+    }
+
+    if file_list.is_empty() {
+        println!("Nothing to do here, continuing with next job.");
+        return Ok(());
+    }
+
+    let file = File::create(format!("{outpath}/cs_cloud_files.json"))?;
+    let mut writer = BufWriter::new(file);
+    serde_json::to_writer(&mut writer, &file_list)?;
+    writer.flush()?;
+
+    println!("Done here!");
+    Ok(())
+}
+
+/// user profile and account a database belongs to
+pub struct Source<'a> {
+    pub user_profile: &'a str,
+    pub account: &'a str,
+}
+
+/// reads a copy of the database, errors are only logged so the other databases are still read
+pub fn read_database<F>(database: &Path, f: F)
+where
+    F: FnOnce(&Connection, &str) -> anyhow::Result<()>,
+{
+    let database = database.to_string_lossy().to_string();
+    if let Err(err) = with_sqlite_copy(&database, |connection| f(connection, &database)) {
+        error!("Failed to read {database}: {err}")
+    }
+}
+
+/// user profile name and the folder below it, for every user that has it
+pub fn find_user_folders(input: &str, relative: &str) -> Vec<(String, String)> {
+    find_user_profiles(input)
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|user_profile| {
+            let folder = find_file_case_insensitive(&user_profile, relative)?;
+            let name = file_name(Path::new(&user_profile));
+            Some((name, folder))
+        })
+        .collect()
+}
+
+/// unix time in seconds or milliseconds, the clients use both
+pub fn unix_time(time: i64) -> Option<DateTime<Utc>> {
+    match time {
+        0 => None,
+        t if t > 100_000_000_000 => convert_unix_time_micros(t.checked_mul(1_000)?),
+        t => convert_unix_time(t),
+    }
+}
@@ -0,0 +1,240 @@
+// "Dropbox encrypts most of its databases, but the account configuration and some newer databases are readable." - SANS Windows Forensic Analysis Poster, Dropbox
+// <user profile>\AppData\Local\Dropbox\info.json (older versions: AppData\Roaming): path and type (personal/business) of every account
+// <user profile>\AppData\Local\Dropbox\instance<N>\sync\nucleus.sqlite3 (SQLite): local_tree, synced_tree and remote_tree,
+// the values are binary (protobuf), the Dropbox path is read as the first string starting with "/"
+// <user profile>\AppData\Local\Dropbox\instance<N>\aggregation.dbx (SQLite, not encrypted): snapshot with JSON values (recent files, ...)
+// the encrypted databases (filecache.dbx, config.dbx) are skipped
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use common::find_file_case_insensitive;
+use log::error;
+use rusqlite::types::ValueRef;
+use rusqlite::Connection;
+use serde_json::Value;
+
+use crate::cloud_storage::files::cloud_files::{
+    find_user_folders, read_database, unix_time, CloudFileEntry, Source,
+};
+use crate::formats::sqlite::text_column;
+
+const PROVIDER: &str = "Dropbox";
+const NUCLEUS_TABLES: [&str; 3] = ["local_tree", "synced_tree", "remote_tree"];
+// names of the JSON fields of aggregation.dbx, the first one found is used
+const PATH_FIELDS: [&str; 3] = ["path", "server_path", "display_path"];
+const ID_FIELDS: [&str; 2] = ["id", "file_id"];
+const SIZE_FIELDS: [&str; 2] = ["size", "bytes"];
+const TIME_FIELDS: [&str; 3] = ["timestamp", "server_modified", "client_modified"];
+
+/// all instances of all users
+pub fn read_dropbox(input: &str, file_list: &mut Vec<CloudFileEntry>) -> anyhow::Result<()> {
+    for (user_profile, dropbox) in find_user_folders(input, "AppData/Local/Dropbox") {
+        let mut accounts = read_accounts(&format!("{dropbox}/info.json"));
+        if accounts.is_empty() {
+            // AppData/Local/Dropbox -> AppData/Roaming/Dropbox of the same profile
+            let roaming = Path::new(&dropbox)
+                .parent()
+                .and_then(Path::parent)
+                .and_then(|app_data| {
+                    find_file_case_insensitive(&app_data.to_string_lossy(), "Roaming/Dropbox")
+                });
+            if let Some(roaming) = roaming {
+                accounts = read_accounts(&format!("{roaming}/info.json"));
+            }
+        }
+
+        let Ok(entries) = fs::read_dir(&dropbox) else {
+            continue;
+        };
+        let instances = entries.flatten().map(|e| e.path()).filter(|p| {
+            p.is_dir()
+                && p.file_name()
+                    .is_some_and(|n| n.to_string_lossy().starts_with("instance"))
+        });
+        for instance in instances {
+            let instance_name = instance
+                .file_name()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_default();
+            // the instances can only be matched to the accounts if there is just one
+            let account = match accounts.as_slice() {
+                [account] => account.clone(),
+                _ => instance_name,
+            };
+            let source = Source {
+                user_profile: &user_profile,
+                account: &account,
+            };
+            let database = instance.join("sync").join("nucleus.sqlite3");
+            if database.is_file() {
+                read_database(&database, |connection, database| {
+                    read_nucleus(connection, &source, database, file_list)
+                });
+            }
+            let database = instance.join("aggregation.dbx");
+            if database.is_file() {
+                read_database(&database, |connection, database| {
+                    read_aggregation(connection, &source, database, file_list)
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+/// "<type>: <path>" of every account in info.json
+fn read_accounts(info_json: &str) -> Vec<String> {
+    if !Path::new(info_json).is_file() {
+        return Vec::new();
+    }
+    let info = match fs::read(info_json)
+        .map_err(anyhow::Error::from)
+        .and_then(|data| Ok(serde_json::from_slice::<Value>(&data)?))
+    {
+        Ok(info) => info,
+        Err(err) => {
+            error!("Failed to read {info_json}: {err}");
+            return Vec::new();
+        }
+    };
+    info.as_object()
+        .map(|accounts| {
+            accounts
+                .iter()
+                .map(|(account_type, account)| {
+                    let path = account
+                        .get("path")
+                        .and_then(Value::as_str)
+                        .unwrap_or_default();
+                    format!("{account_type}: {path}")
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn read_nucleus(
+    connection: &Connection,
+    source: &Source,
+    database: &str,
+    file_list: &mut Vec<CloudFileEntry>,
+) -> anyhow::Result<()> {
+    for table in NUCLEUS_TABLES {
+        let mut statement = match connection.prepare(&format!("SELECT * FROM {table}")) {
+            Ok(statement) => statement,
+            // the tables differ between the versions
+            Err(_) => continue,
+        };
+        let mut rows = statement.query([])?;
+        while let Some(row) = rows.next()? {
+            let value = match row.get_ref("value") {
+                Ok(ValueRef::Blob(value)) | Ok(ValueRef::Text(value)) => value.to_vec(),
+                _ => continue,
+            };
+            let Some(path) = dropbox_path(&value) else {
+                continue;
+            };
+            file_list.push(CloudFileEntry {
+                user_profile: source.user_profile.to_string(),
+                provider: PROVIDER.to_string(),
+                account: Some(source.account.to_string()),
+                source_file: format!("{database} ({table})"),
+                item_type: None,
+                cloud_id: text_column(row, "key"),
+                name: path.rsplit('/').next().unwrap_or_default().to_string(),
+                path,
+                size: None,
+                hashes: BTreeMap::new(),
+                created: None,
+                modified: None,
+                accessed: None,
+                trashed: None,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// the first printable UTF-8 run starting with "/"
+fn dropbox_path(value: &[u8]) -> Option<String> {
+    let start = value.iter().position(|b| *b == b'/')?;
+    let length = value[start..]
+        .iter()
+        .take_while(|b| **b >= 0x20 && **b != 0x7f)
+        .count();
+    let path = String::from_utf8_lossy(&value[start..start + length]).to_string();
+    (path.len() > 1).then_some(path)
+}
+
+fn read_aggregation(
+    connection: &Connection,
+    source: &Source,
+    database: &str,
+    file_list: &mut Vec<CloudFileEntry>,
+) -> anyhow::Result<()> {
+    let mut statement = connection.prepare("SELECT * FROM snapshot")?;
+    let mut rows = statement.query([])?;
+    while let Some(row) = rows.next()? {
+        let key = text_column(row, "key").unwrap_or_default();
+        let Some(value) =
+            text_column(row, "value").and_then(|value| serde_json::from_str::<Value>(&value).ok())
+        else {
+            continue;
+        };
+        let mut files = Vec::new();
+        collect_files(&value, &mut files);
+        for file in files {
+            let Some(path) = first_field(file, &PATH_FIELDS).and_then(Value::as_str) else {
+                continue;
+            };
+            file_list.push(CloudFileEntry {
+                user_profile: source.user_profile.to_string(),
+                provider: PROVIDER.to_string(),
+                account: Some(source.account.to_string()),
+                source_file: format!("{database} ({key})"),
+                item_type: None,
+                cloud_id: first_field(file, &ID_FIELDS).map(|id| match id {
+                    Value::String(id) => id.clone(),
+                    id => id.to_string(),
+                }),
+                name: path.rsplit('/').next().unwrap_or_default().to_string(),
+                path: path.to_string(),
+                size: first_field(file, &SIZE_FIELDS).and_then(Value::as_i64),
+                hashes: BTreeMap::new(),
+                created: None,
+                modified: first_field(file, &TIME_FIELDS)
+                    .and_then(Value::as_f64)
+                    .and_then(|time| unix_time(time as i64)),
+                accessed: None,
+                trashed: None,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// all JSON objects with a path field, nested at any depth
+fn collect_files<'a>(value: &'a Value, files: &mut Vec<&'a Value>) {
+    match value {
+        Value::Object(object) => {
+            if first_field(value, &PATH_FIELDS).is_some_and(Value::is_string) {
+                files.push(value);
+            }
+            for child in object.values() {
+                collect_files(child, files);
+            }
+        }
+        Value::Array(array) => {
+            for child in array {
+                collect_files(child, files);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn first_field<'a>(value: &'a Value, names: &[&str]) -> Option<&'a Value> {
+    names.iter().find_map(|name| value.get(*name))
+}
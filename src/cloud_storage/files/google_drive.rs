@@ -0,0 +1,162 @@
+// "Google Drive for Desktop (DriveFS) streams files to a virtual drive, its metadata database lists all files of the account." - SANS Windows Forensic Analysis Poster, Google Drive for Desktop
+// <user profile>\AppData\Local\Google\DriveFS\<account id>\metadata_sqlite_db (SQLite):
+// items (stable_id, id, local_title, is_folder, file_size, modified_date, viewed_by_me_date in unix milliseconds) and stable_parents
+// <user profile>\AppData\Local\Google\DriveFS\<account id>\mirror_metadata_sqlite.db (SQLite, mirrored folders):
+// mirror_item with local and cloud file name, size, MD5 and modification time
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+
+use common::node_path;
+use rusqlite::Connection;
+
+use crate::cloud_storage::files::cloud_files::{
+    find_user_folders, read_database, unix_time, CloudFileEntry, Source,
+};
+use crate::formats::sqlite::{integer_column, text_column};
+
+const PROVIDER: &str = "Google Drive for Desktop";
+
+/// all accounts of all users
+pub fn read_google_drive(input: &str, file_list: &mut Vec<CloudFileEntry>) -> anyhow::Result<()> {
+    for (user_profile, drivefs) in find_user_folders(input, "AppData/Local/Google/DriveFS") {
+        let Ok(entries) = fs::read_dir(&drivefs) else {
+            continue;
+        };
+        let account_folders = entries.flatten().map(|e| e.path()).filter(|p| p.is_dir());
+        for account_folder in account_folders {
+            let account = account_folder
+                .file_name()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let source = Source {
+                user_profile: &user_profile,
+                account: &account,
+            };
+            let database = account_folder.join("metadata_sqlite_db");
+            if database.is_file() {
+                read_database(&database, |connection, database| {
+                    read_metadata(connection, &source, database, file_list)
+                });
+            }
+            let database = account_folder.join("mirror_metadata_sqlite.db");
+            if database.is_file() {
+                read_database(&database, |connection, database| {
+                    read_mirror_metadata(connection, &source, database, file_list)
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+fn read_metadata(
+    connection: &Connection,
+    source: &Source,
+    database: &str,
+    file_list: &mut Vec<CloudFileEntry>,
+) -> anyhow::Result<()> {
+    // an item can have several parents, the first one is used for the path
+    let mut parents: HashMap<i64, i64> = HashMap::new();
+    let mut statement = connection.prepare("SELECT * FROM stable_parents")?;
+    let mut rows = statement.query([])?;
+    while let Some(row) = rows.next()? {
+        if let (Some(item), Some(parent)) = (
+            integer_column(row, "item_stable_id"),
+            integer_column(row, "parent_stable_id"),
+        ) {
+            parents.entry(item).or_insert(parent);
+        }
+    }
+
+    let mut items = Vec::new();
+    let mut nodes: HashMap<i64, (Option<i64>, String)> = HashMap::new();
+    let mut statement = connection.prepare("SELECT * FROM items")?;
+    let mut rows = statement.query([])?;
+    while let Some(row) = rows.next()? {
+        let stable_id = integer_column(row, "stable_id").unwrap_or_default();
+        let name = text_column(row, "local_title").unwrap_or_default();
+        nodes.insert(stable_id, (parents.get(&stable_id).copied(), name.clone()));
+        let is_folder = integer_column(row, "is_folder").map(|f| f != 0);
+        items.push((
+            stable_id,
+            CloudFileEntry {
+                user_profile: source.user_profile.to_string(),
+                provider: PROVIDER.to_string(),
+                account: Some(source.account.to_string()),
+                source_file: database.to_string(),
+                item_type: is_folder.map(|f| if f { "folder" } else { "file" }.to_string()),
+                cloud_id: text_column(row, "id"),
+                name,
+                path: String::new(),
+                size: integer_column(row, "file_size"),
+                hashes: BTreeMap::new(),
+                created: None,
+                modified: integer_column(row, "modified_date").and_then(unix_time),
+                accessed: integer_column(row, "viewed_by_me_date").and_then(unix_time),
+                trashed: integer_column(row, "trashed").map(|t| t != 0),
+            },
+        ));
+    }
+    for (stable_id, mut entry) in items {
+        entry.path = node_path(&nodes, &stable_id).join("/");
+        file_list.push(entry);
+    }
+    Ok(())
+}
+
+fn read_mirror_metadata(
+    connection: &Connection,
+    source: &Source,
+    database: &str,
+    file_list: &mut Vec<CloudFileEntry>,
+) -> anyhow::Result<()> {
+    let mut items = Vec::new();
+    let mut nodes: HashMap<i64, (Option<i64>, String)> = HashMap::new();
+    let mut statement = connection.prepare("SELECT * FROM mirror_item")?;
+    let mut rows = statement.query([])?;
+    while let Some(row) = rows.next()? {
+        let local_id = integer_column(row, "local_stable_id").unwrap_or_default();
+        let name = text_column(row, "local_filename")
+            .or_else(|| text_column(row, "cloud_filename"))
+            .unwrap_or_default();
+        nodes.insert(
+            local_id,
+            (integer_column(row, "parent_local_stable_id"), name.clone()),
+        );
+        let mut hashes = BTreeMap::new();
+        if let Some(md5) = text_column(row, "cloud_md5_checksum")
+            .or_else(|| text_column(row, "local_md5_checksum"))
+            .filter(|h| !h.is_empty())
+        {
+            hashes.insert("md5".to_string(), md5);
+        }
+        items.push((
+            local_id,
+            CloudFileEntry {
+                user_profile: source.user_profile.to_string(),
+                provider: PROVIDER.to_string(),
+                account: Some(source.account.to_string()),
+                source_file: database.to_string(),
+                item_type: None,
+                cloud_id: text_column(row, "stable_id"),
+                name,
+                path: String::new(),
+                size: integer_column(row, "cloud_size")
+                    .or_else(|| integer_column(row, "local_size")),
+                hashes,
+                created: None,
+                modified: integer_column(row, "cloud_mtime_ms")
+                    .or_else(|| integer_column(row, "local_mtime_ms"))
+                    .and_then(unix_time),
+                accessed: None,
+                trashed: None,
+            },
+        ));
+    }
+    for (local_id, mut entry) in items {
+        entry.path = node_path(&nodes, &local_id).join("/");
+        file_list.push(entry);
+    }
+    Ok(())
+}
@@ -0,0 +1,7 @@
+pub mod box_drive;
+pub mod cloud_files;
+pub mod dropbox;
+pub mod google_drive;
+pub mod onedrive;
+pub mod onedrive_logs;
+pub mod onedrive_sync;
@@ -0,0 +1,59 @@
+// OneDrive keeps its settings and logs per account below the user profile
+// <user profile>\AppData\Local\Microsoft\OneDrive\settings\<Personal|Business1|...>
+// <user profile>\AppData\Local\Microsoft\OneDrive\logs\<Personal|Business1|Common|...>
+
+use std::fs;
+use std::path::Path;
+
+use common::{file_name, find_file_case_insensitive, find_user_profiles};
+
+const ONEDRIVE_FOLDER: &str = "AppData/Local/Microsoft/OneDrive";
+
+/// settings or logs folder of one OneDrive account
+pub struct OneDriveFolder {
+    pub user_profile: String,
+    pub account: String,
+    pub path: String,
+}
+
+/// all account folders below "settings" or "logs" of all users
+pub fn find_onedrive_folders(input: &str, kind: &str) -> Vec<OneDriveFolder> {
+    let mut folders = Vec::new();
+    for user_profile in find_user_profiles(input).unwrap_or_default() {
+        let Some(path) =
+            find_file_case_insensitive(&user_profile, &format!("{ONEDRIVE_FOLDER}/{kind}"))
+        else {
+            continue;
+        };
+        let Ok(entries) = fs::read_dir(&path) else {
+            continue;
+        };
+        for account_path in entries.flatten().map(|e| e.path()).filter(|p| p.is_dir()) {
+            folders.push(OneDriveFolder {
+                user_profile: file_name(Path::new(&user_profile)),
+                account: file_name(&account_path),
+                path: account_path.to_string_lossy().to_string(),
+            });
+        }
+    }
+    folders
+}
+
+/// files of a folder whose names end with one of the extensions, ignoring case
+pub fn files_with_extensions(folder: &str, extensions: &[&str]) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(folder) else {
+        return Vec::new();
+    };
+    let mut files: Vec<String> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_file())
+        .filter(|p| {
+            let name = file_name(p).to_lowercase();
+            extensions.iter().any(|extension| name.ends_with(extension))
+        })
+        .map(|p| p.to_string_lossy().to_string())
+        .collect();
+    files.sort();
+    files
+}
@@ -0,0 +1,278 @@
+// "OneDrive logs record the sync activity of the client, including file uploads, downloads, renames and deletions." - SANS Windows Forensic Analysis Poster, OneDrive
+// <user profile>\AppData\Local\Microsoft\OneDrive\logs\<Personal|Business1|Common|...>\*.odl, *.odlgz (gzip), *.odlsent, *.aodl
+// file header (0x100 bytes): "EBFGONED", version (u32), OneDrive and Windows version
+// every block starts with CC DD EE FF 00 00 00 00 and a timestamp (unix time in milliseconds), version 2 blocks have a 56 byte header,
+// version 3 blocks a 36 byte header and optional context data, the data is the code file, flags, the function and the parameters
+// file and folder names in the parameters are obfuscated, ObfuscationStringMap.txt of the same folder maps them back
+
+use std::collections::HashMap;
+use std::fs;
+use std::fs::File;
+use std::io::{BufWriter, Read, Write};
+use std::path::Path;
+
+use anyhow::anyhow;
+use chrono::{DateTime, Utc};
+use common::{convert_unix_time_micros, read_u16_le, read_u32_le, read_u64_le};
+use flate2::read::MultiGzDecoder;
+use log::error;
+use serde::Serialize;
+
+use crate::cloud_storage::files::onedrive::{files_with_extensions, find_onedrive_folders};
+
+const ODL_SIGNATURE: &[u8] = b"EBFGONED";
+const ODL_HEADER_SIZE: usize = 0x100;
+const BLOCK_SIGNATURE: [u8; 8] = [0xCC, 0xDD, 0xEE, 0xFF, 0x00, 0x00, 0x00, 0x00];
+const BLOCK_HEADER_SIZE_V2: usize = 56;
+const BLOCK_HEADER_SIZE_V3: usize = 36;
+const GZIP_SIGNATURE: &[u8] = &[0x1F, 0x8B];
+const LOG_EXTENSIONS: [&str; 4] = [".odl", ".odlgz", ".odlsent", ".aodl"];
+const OBFUSCATION_MAP: &str = "ObfuscationStringMap.txt";
+// characters between the obfuscated words of a parameter
+const SEPARATORS: [char; 17] = [
+    '\\', '/', '.', ':', ';', ',', '|', '"', '\'', '(', ')', '[', ']', '{', '}', '=', '&',
+];
+
+#[derive(Debug, Serialize)]
+struct OneDriveLogEntry {
+    user_profile: String,
+    log_folder: String,
+    log_file: String,
+    odl_version: u32,
+    timestamp: Option<DateTime<Utc>>,
+    code_file: Option<String>,
+    function: Option<String>,
+    parameters: Vec<String>,
+}
+
+pub fn get_onedrive_logs_data(input: &str, outpath: &str) -> anyhow::Result<()> {
+    print!("Working on OneDrive Logs: ");
+    let mut log_list: Vec<OneDriveLogEntry> = Vec::new();
+
+    for folder in find_onedrive_folders(input, "logs") {
+        let map_file = format!("{}/{OBFUSCATION_MAP}", folder.path);
+        let obfuscation_map = if Path::new(&map_file).is_file() {
+            read_obfuscation_map(&map_file).unwrap_or_else(|err| {
+                error!("Failed to read {map_file}, names stay obfuscated: {err}");
+                HashMap::new()
+            })
+        } else {
+            HashMap::new()
+        };
+        for log_file in files_with_extensions(&folder.path, &LOG_EXTENSIONS) {
+            let result = read_odl_file(&log_file, &obfuscation_map, |odl_version, block| {
+                log_list.push(OneDriveLogEntry {
+                    user_profile: folder.user_profile.clone(),
+                    log_folder: folder.account.clone(),
+                    log_file: log_file.clone(),
+                    odl_version,
+                    timestamp: block.timestamp,
+                    code_file: block.code_file,
+                    function: block.function,
+                    parameters: block.parameters,
+                })
+            });
+            if let Err(err) = result {
+                error!("Failed to read {log_file}: {err}")
+            }
+        }
+    }
+
+    if log_list.is_empty() {
+        println!("Nothing to do here, continuing with next job.");
+        return Ok(());
+    }
+
+    let file = File::create(format!("{outpath}/cs_onedrive_logs.json"))?;
+    let mut writer = BufWriter::new(file);
+    serde_json::to_writer(&mut writer, &log_list)?;
+    writer.flush()?;
+
+    println!("Done here!");
+    Ok(())
+}
+
+struct LogBlock {
+    timestamp: Option<DateTime<Utc>>,
+    code_file: Option<String>,
+    function: Option<String>,
+    parameters: Vec<String>,
+}
+
+/// calls `f` with the version and every block of the log file
+fn read_odl_file<F>(
+    log_file: &str,
+    obfuscation_map: &HashMap<String, String>,
+    mut f: F,
+) -> anyhow::Result<()>
+where
+    F: FnMut(u32, LogBlock),
+{
+    let mut data = fs::read(log_file)?;
+    if data.starts_with(GZIP_SIGNATURE) {
+        data = gunzip(&data)?;
+    }
+    if !data.starts_with(ODL_SIGNATURE) {
+        return Err(anyhow!("No ODL signature found!"));
+    }
+    let odl_version = read_u32_le(&data, 8).unwrap_or_default();
+    let mut body = data.get(ODL_HEADER_SIZE..).unwrap_or_default().to_vec();
+    // .odlgz files have an uncompressed header
+    if body.starts_with(GZIP_SIGNATURE) {
+        body = gunzip(&body)?;
+    }
+    let header_size = if odl_version >= 3 {
+        BLOCK_HEADER_SIZE_V3
+    } else {
+        BLOCK_HEADER_SIZE_V2
+    };
+
+    let mut offset = 0;
+    while let Some(start) = find_block(&body, offset) {
+        let (timestamp, data_offset, data_length) = if odl_version >= 3 {
+            let context_length = usize::from(read_u16_le(&body, start + 8).unwrap_or_default());
+            let has_context = read_u16_le(&body, start + 10).unwrap_or_default() != 0;
+            let data_offset = start + header_size + if has_context { context_length } else { 0 };
+            (
+                read_u64_le(&body, start + 12),
+                data_offset,
+                read_u32_le(&body, start + 28),
+            )
+        } else {
+            (
+                read_u64_le(&body, start + 8),
+                start + header_size,
+                read_u32_le(&body, start + 0x30),
+            )
+        };
+        let data_length = data_length.unwrap_or_default() as usize;
+        let Some(block_data) = body.get(data_offset..data_offset.saturating_add(data_length))
+        else {
+            // truncated block, the rest is searched for the next signature
+            offset = start + BLOCK_SIGNATURE.len();
+            continue;
+        };
+        let mut block = read_block_data(block_data);
+        block.timestamp = timestamp
+            .and_then(|ms| i64::try_from(ms).ok())
+            .and_then(|ms| convert_unix_time_micros(ms.checked_mul(1_000)?));
+        block.parameters = block
+            .parameters
+            .iter()
+            .map(|parameter| deobfuscate(parameter, obfuscation_map))
+            .collect();
+        f(odl_version, block);
+        offset = (data_offset + data_length).max(start + BLOCK_SIGNATURE.len());
+    }
+    Ok(())
+}
+
+fn gunzip(data: &[u8]) -> anyhow::Result<Vec<u8>> {
+    let mut decompressed = Vec::new();
+    MultiGzDecoder::new(data).read_to_end(&mut decompressed)?;
+    Ok(decompressed)
+}
+
+fn find_block(body: &[u8], offset: usize) -> Option<usize> {
+    let position = body
+        .get(offset..)?
+        .windows(BLOCK_SIGNATURE.len())
+        .position(|w| w == BLOCK_SIGNATURE)?;
+    Some(offset + position)
+}
+
+/// code file (u32 length + name), flags (u32), function (u32 length + name) and the parameters
+fn read_block_data(data: &[u8]) -> LogBlock {
+    let code_file = read_length_prefixed(data, 0);
+    let function = code_file
+        .as_ref()
+        .and_then(|code_file| read_length_prefixed(data, 4 + code_file.len() + 4));
+    let parameters_offset = match (&code_file, &function) {
+        (Some(code_file), Some(function)) => 4 + code_file.len() + 4 + 4 + function.len(),
+        // unknown layout, all strings of the block are parameters
+        _ => 0,
+    };
+    LogBlock {
+        timestamp: None,
+        parameters: extract_strings(data.get(parameters_offset..).unwrap_or_default()),
+        code_file,
+        function,
+    }
+}
+
+/// u32 length and printable characters
+fn read_length_prefixed(data: &[u8], offset: usize) -> Option<String> {
+    let length = read_u32_le(data, offset)? as usize;
+    let text = data.get(offset + 4..(offset + 4).checked_add(length)?)?;
+    if length == 0 || !is_printable(text) {
+        return None;
+    }
+    Some(String::from_utf8_lossy(text).to_string())
+}
+
+/// the parameters are length prefixed strings mixed with numbers and other binary data
+fn extract_strings(data: &[u8]) -> Vec<String> {
+    let mut strings = Vec::new();
+    let mut offset = 0;
+    while offset + 4 < data.len() {
+        match read_length_prefixed(data, offset) {
+            Some(text) => {
+                offset += 4 + text.len();
+                strings.push(text);
+            }
+            None => offset += 1,
+        }
+    }
+    strings
+}
+
+fn is_printable(text: &[u8]) -> bool {
+    match std::str::from_utf8(text) {
+        Ok(text) => text
+            .chars()
+            .all(|c| !c.is_control() || c == '\t' || c == '\r' || c == '\n'),
+        Err(_) => false,
+    }
+}
+
+/// "<obfuscated>\t<original>" per line, UTF-16LE (with byte order mark) or UTF-8
+fn read_obfuscation_map(map_file: &str) -> anyhow::Result<HashMap<String, String>> {
+    let data = fs::read(map_file)?;
+    let text = match data.strip_prefix(b"\xFF\xFE") {
+        Some(utf16) => {
+            let chars: Vec<u16> = utf16
+                .chunks_exact(2)
+                .map(|c| u16::from_le_bytes([c[0], c[1]]))
+                .collect();
+            String::from_utf16_lossy(&chars)
+        }
+        None => {
+            String::from_utf8_lossy(data.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(&data)).to_string()
+        }
+    };
+    Ok(text
+        .lines()
+        .filter_map(|line| line.split_once('\t'))
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect())
+}
+
+/// replaces every obfuscated word of the parameter, the separators are kept
+fn deobfuscate(parameter: &str, obfuscation_map: &HashMap<String, String>) -> String {
+    if obfuscation_map.is_empty() {
+        return parameter.to_string();
+    }
+    let mut result = String::with_capacity(parameter.len());
+    let mut word = String::new();
+    for c in parameter.chars() {
+        if SEPARATORS.contains(&c) || c.is_whitespace() {
+            result.push_str(obfuscation_map.get(&word).unwrap_or(&word));
+            word.clear();
+            result.push(c);
+        } else {
+            word.push(c);
+        }
+    }
+    result.push_str(obfuscation_map.get(&word).unwrap_or(&word));
+    result
+}
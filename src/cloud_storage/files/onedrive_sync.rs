@@ -0,0 +1,208 @@
+// "OneDrive keeps a database of the files and folders it syncs, including files that were never downloaded (Files On-Demand) or were deleted locally." - SANS Windows Forensic Analysis Poster, OneDrive
+// <user profile>\AppData\Local\Microsoft\OneDrive\settings\<account>\SyncEngineDatabase.db (SQLite, newer versions):
+// od_ClientFolder_Records and od_ClientFile_Records with resourceID, parentResourceID and name, lastChange in unix time
+// <user profile>\AppData\Local\Microsoft\OneDrive\settings\Personal\<CID>.dat (older versions): binary records with
+// the resource id ("<CID>!<number>"), the resource id of the parent and the UTF-16 name, the layout changes with every version
+
+use std::collections::HashMap;
+use std::fs;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use common::{convert_unix_time, node_path, read_utf16_string};
+use log::error;
+use regex::bytes::Regex;
+use rusqlite::types::ValueRef;
+use rusqlite::{Connection, Row};
+use serde::Serialize;
+
+use crate::cloud_storage::files::onedrive::{
+    files_with_extensions, find_onedrive_folders, OneDriveFolder,
+};
+use crate::formats::sqlite::{integer_column, text_column, with_sqlite_copy};
+
+// names of the .dat records start shortly after the parent resource id
+const DAT_NAME_SEARCH_LENGTH: usize = 0x100;
+
+#[derive(Debug, Serialize)]
+struct OneDriveSyncEntry {
+    user_profile: String,
+    account: String,
+    source_file: String,
+    // None for the .dat records, they do not tell files and folders apart
+    item_type: Option<String>,
+    resource_id: String,
+    parent_resource_id: Option<String>,
+    name: String,
+    // path below the OneDrive folder (or the SharePoint library)
+    path: String,
+    size: Option<i64>,
+    last_change: Option<DateTime<Utc>>,
+    disk_created: Option<DateTime<Utc>>,
+    disk_last_access: Option<DateTime<Utc>>,
+    hash: Option<String>,
+    shared: Option<bool>,
+}
+
+pub fn get_onedrive_sync_data(input: &str, outpath: &str) -> anyhow::Result<()> {
+    print!("Working on OneDrive Sync Databases: ");
+    let mut sync_list: Vec<OneDriveSyncEntry> = Vec::new();
+
+    for folder in find_onedrive_folders(input, "settings") {
+        let database = format!("{}/SyncEngineDatabase.db", folder.path);
+        if Path::new(&database).is_file() {
+            let result = with_sqlite_copy(&database, |connection| {
+                read_sync_engine_database(connection, &folder, &database, &mut sync_list)
+            });
+            if let Err(err) = result {
+                error!("Failed to read {database}: {err}")
+            }
+        }
+        for dat_file in files_with_extensions(&folder.path, &[".dat", ".dat.previous"]) {
+            if let Err(err) = read_dat_file(&folder, &dat_file, &mut sync_list) {
+                error!("Failed to read {dat_file}: {err}")
+            }
+        }
+    }
+
+    if sync_list.is_empty() {
+        println!("Nothing to do here, continuing with next job.");
+        return Ok(());
+    }
+
+    let file = File::create(format!("{outpath}/cs_onedrive_sync.json"))?;
+    let mut writer = BufWriter::new(file);
+    serde_json::to_writer(&mut writer, &sync_list)?;
+    writer.flush()?;
+
+    println!("Done here!");
+    Ok(())
+}
+
+fn read_sync_engine_database(
+    connection: &Connection,
+    folder: &OneDriveFolder,
+    database: &str,
+    sync_list: &mut Vec<OneDriveSyncEntry>,
+) -> anyhow::Result<()> {
+    let mut entries = Vec::new();
+    for (table, item_type, name_column) in [
+        ("od_ClientFolder_Records", "folder", "folderName"),
+        ("od_ClientFile_Records", "file", "fileName"),
+    ] {
+        let mut statement = connection.prepare(&format!("SELECT * FROM {table}"))?;
+        let mut rows = statement.query([])?;
+        while let Some(row) = rows.next()? {
+            let time = |name: &str| integer_column(row, name).and_then(convert_unix_time);
+            entries.push(OneDriveSyncEntry {
+                user_profile: folder.user_profile.clone(),
+                account: folder.account.clone(),
+                source_file: database.to_string(),
+                item_type: Some(item_type.to_string()),
+                resource_id: text_column(row, "resourceID").unwrap_or_default(),
+                parent_resource_id: text_column(row, "parentResourceID"),
+                name: text_column(row, name_column).unwrap_or_default(),
+                path: String::new(),
+                size: integer_column(row, "size"),
+                last_change: time("lastChange"),
+                disk_created: time("diskCreationTime"),
+                disk_last_access: time("diskLastAccessTime"),
+                hash: hash_column(row, "localHashDigest")
+                    .or_else(|| hash_column(row, "localQuickXorHash")),
+                shared: integer_column(row, "sharedItem").map(|s| s != 0),
+            });
+        }
+    }
+    add_paths(&mut entries);
+    sync_list.append(&mut entries);
+    Ok(())
+}
+
+/// the hashes are stored as text (base64) or as blob depending on the version
+fn hash_column(row: &Row, name: &str) -> Option<String> {
+    match row.get_ref(name).ok()? {
+        ValueRef::Text(t) if !t.is_empty() => Some(String::from_utf8_lossy(t).to_string()),
+        ValueRef::Blob(b) if !b.is_empty() => {
+            Some(b.iter().map(|byte| format!("{byte:02x}")).collect())
+        }
+        _ => None,
+    }
+}
+
+/// heuristic: a resource id directly followed by a second one is a record (item and parent)
+fn read_dat_file(
+    folder: &OneDriveFolder,
+    dat_file: &str,
+    sync_list: &mut Vec<OneDriveSyncEntry>,
+) -> anyhow::Result<()> {
+    let data = fs::read(dat_file)?;
+    let resource_id = Regex::new(r"(?-u)[0-9A-Fa-f]{16}![0-9]+(?:\.[0-9]+)?")?;
+    let matches: Vec<_> = resource_id.find_iter(&data).collect();
+
+    let mut entries = Vec::new();
+    let mut index = 0;
+    while index + 1 < matches.len() {
+        let (item, parent) = (matches[index], matches[index + 1]);
+        // both ids are stored in fixed size fields of 39 bytes
+        if parent.start() - item.end() > 0x40 {
+            index += 1;
+            continue;
+        }
+        let Some(name) = read_dat_name(&data, parent.end()) else {
+            index += 1;
+            continue;
+        };
+        entries.push(OneDriveSyncEntry {
+            user_profile: folder.user_profile.clone(),
+            account: folder.account.clone(),
+            source_file: dat_file.to_string(),
+            item_type: None,
+            resource_id: String::from_utf8_lossy(item.as_bytes()).to_string(),
+            parent_resource_id: Some(String::from_utf8_lossy(parent.as_bytes()).to_string()),
+            name,
+            path: String::new(),
+            size: None,
+            last_change: None,
+            disk_created: None,
+            disk_last_access: None,
+            hash: None,
+            shared: None,
+        });
+        index += 2;
+    }
+    add_paths(&mut entries);
+    sync_list.append(&mut entries);
+    Ok(())
+}
+
+/// the first UTF-16 string after the parent resource id (names start with a printable character)
+fn read_dat_name(data: &[u8], start: usize) -> Option<String> {
+    let end = data.len().min(start + DAT_NAME_SEARCH_LENGTH);
+    let offset = (start..end.saturating_sub(1))
+        .find(|&offset| data[offset] >= 0x20 && data[offset] < 0x7f && data[offset + 1] == 0)?;
+    read_utf16_string(data, offset).filter(|name| !name.is_empty())
+}
+
+/// path of every entry from the names of its parents, separated by "\"
+fn add_paths(entries: &mut [OneDriveSyncEntry]) {
+    let names: HashMap<String, (Option<String>, String)> = entries
+        .iter()
+        .map(|e| {
+            (
+                e.resource_id.clone(),
+                (e.parent_resource_id.clone(), e.name.clone()),
+            )
+        })
+        .collect();
+    for entry in entries.iter_mut() {
+        let mut parts = entry
+            .parent_resource_id
+            .as_ref()
+            .map(|parent| node_path(&names, parent))
+            .unwrap_or_default();
+        parts.push(&entry.name);
+        entry.path = parts.join("\\");
+    }
+}
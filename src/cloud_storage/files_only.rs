@@ -0,0 +1,19 @@
+// FilesOnly Processing Mode for Cloud Storage
+use log::error;
+
+use crate::cloud_storage::files::cloud_files::get_cloud_files_data;
+use crate::cloud_storage::files::onedrive_logs::get_onedrive_logs_data;
+use crate::cloud_storage::files::onedrive_sync::get_onedrive_sync_data;
+
+pub fn get_cloudstorage_file_data(input: &str, outpath: &str) -> anyhow::Result<()> {
+    if let Err(err) = get_onedrive_sync_data(input, outpath) {
+        error!("Failed to get OneDrive Sync Data: {err}")
+    }
+    if let Err(err) = get_onedrive_logs_data(input, outpath) {
+        error!("Failed to get OneDrive Logs: {err}")
+    }
+    if let Err(err) = get_cloud_files_data(input, outpath) {
+        error!("Failed to get Cloud Storage Files: {err}")
+    }
+    Ok(())
+}
@@ -0,0 +1 @@
+pub mod onedrive_accounts;
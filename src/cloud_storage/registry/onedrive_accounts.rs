@@ -0,0 +1,141 @@
+// NTUSER.DAT
+// "OneDrive is installed by default on Windows 8+ systems, the account configuration shows which accounts were used and where the files are synced to." - SANS Windows Forensic Analysis Poster, OneDrive
+// NTUSER.DAT\Software\Microsoft\OneDrive\Accounts\<Personal|Business1|...>
+// UserEmail, cid, ConfiguredTenantId, UserFolder, ... and the synced SharePoint libraries below "Tenants\<tenant name>" (folder -> value)
+// the sign in times are REG_DWORD/REG_SZ unix time or REG_QWORD (unix time in seconds or milliseconds, FILETIME)
+
+use std::fs::File;
+use std::io::{BufWriter, Read, Write};
+
+use anyhow::anyhow;
+use chrono::{DateTime, Utc};
+use common::{convert_unix_time, convert_win_time, convert_win_time_opt, string_value};
+use log::error;
+use nt_hive::{Hive, KeyNode};
+use serde::Serialize;
+
+use crate::account_usage::registry::user_accounts::UserHive;
+
+const ONEDRIVE_ACCOUNTS: &str = "Software\\Microsoft\\OneDrive\\Accounts";
+
+#[derive(Debug, Serialize)]
+struct OneDriveAccountEntry {
+    sid: String,
+    profile_image_path: String,
+    account: String,
+    user_email: Option<String>,
+    display_name: Option<String>,
+    cid: Option<String>,
+    tenant_id: Option<String>,
+    tenant_name: Option<String>,
+    user_folder: Option<String>,
+    service_endpoint: Option<String>,
+    synced_folders: Vec<String>,
+    first_sign_in: Option<DateTime<Utc>>,
+    last_sign_in: Option<DateTime<Utc>>,
+    key_last_written: DateTime<Utc>,
+}
+
+pub fn get_onedrive_accounts_data(user_hives: &[UserHive], outpath: &str) -> anyhow::Result<()> {
+    print!("Working on OneDrive Accounts: ");
+    let mut account_list: Vec<OneDriveAccountEntry> = Vec::new();
+
+    for user_hive in user_hives {
+        if let Err(err) = get_user_accounts(user_hive, &mut account_list) {
+            error!(
+                "Failed to get OneDrive Accounts of {}: {err}",
+                user_hive.sid
+            )
+        }
+    }
+
+    if account_list.is_empty() {
+        println!("Nothing to do here, continuing with next job.");
+        return Ok(());
+    }
+
+    let file = File::create(format!("{outpath}/reg_onedrive_accounts.json"))?;
+    let mut writer = BufWriter::new(file);
+    serde_json::to_writer(&mut writer, &account_list)?;
+    writer.flush()?;
+
+    println!("Done here!");
+    Ok(())
+}
+
+fn get_user_accounts(
+    user_hive: &UserHive,
+    account_list: &mut Vec<OneDriveAccountEntry>,
+) -> anyhow::Result<()> {
+    let mut buffer = Vec::new();
+    File::open(&user_hive.hive_path)?.read_to_end(&mut buffer)?;
+
+    let hive = Hive::without_validation(buffer.as_ref())?;
+    let root_key_node = hive.root_key_node()?;
+    let accounts_key = root_key_node
+        .subpath(ONEDRIVE_ACCOUNTS)
+        .ok_or(anyhow!("Key '{ONEDRIVE_ACCOUNTS}' can not be found!"))??;
+
+    let account_keys = accounts_key
+        .subkeys()
+        .ok_or(anyhow!("Subkeys can not be unwrapped!"))??;
+    for account_key in account_keys {
+        let account_key = account_key?;
+        let account = account_key.name()?.to_string();
+        let text = |name: &str| string_value(&account_key, name);
+
+        // the SharePoint libraries are below the tenant, the tenant name is the key name
+        let mut tenant_name = None;
+        let mut synced_folders = Vec::new();
+        if let Some(tenant_keys) = account_key.subkey("Tenants").and_then(|k| k.ok()) {
+            if let Some(Ok(tenant_keys)) = tenant_keys.subkeys() {
+                for tenant_key in tenant_keys.flatten() {
+                    tenant_name.get_or_insert(tenant_key.name()?.to_string());
+                    if let Some(Ok(values)) = tenant_key.values() {
+                        for value in values.flatten() {
+                            synced_folders.push(value.name()?.to_string());
+                        }
+                    }
+                }
+            }
+        }
+
+        account_list.push(OneDriveAccountEntry {
+            sid: user_hive.sid.clone(),
+            profile_image_path: user_hive.profile_image_path.clone(),
+            account,
+            user_email: text("UserEmail"),
+            display_name: text("DisplayName"),
+            cid: text("cid").or_else(|| text("UserCID")),
+            tenant_id: text("ConfiguredTenantId"),
+            tenant_name: text("TenantName").or(tenant_name),
+            user_folder: text("UserFolder"),
+            service_endpoint: text("ServiceEndpointUri"),
+            synced_folders,
+            first_sign_in: time_value(&account_key, "ClientFirstSignInTimestamp"),
+            last_sign_in: time_value(&account_key, "LastSignInTime"),
+            key_last_written: convert_win_time(account_key.header().timestamp.get()),
+        });
+    }
+    Ok(())
+}
+
+/// the sign in times are stored with different types depending on the OneDrive version
+fn time_value(key: &KeyNode<&[u8]>, name: &str) -> Option<DateTime<Utc>> {
+    let value = key.value(name)?.ok()?;
+    let time = match value.dword_data() {
+        Ok(seconds) => u64::from(seconds),
+        Err(_) => match value.qword_data() {
+            Ok(time) => time,
+            Err(_) => value.string_data().ok()?.trim().parse().ok()?,
+        },
+    };
+    match time {
+        0 => None,
+        // FILETIME, much larger than unix time in milliseconds
+        t if t > 10_000_000_000_000_000 => convert_win_time_opt(t),
+        // unix time in milliseconds
+        t if t > 100_000_000_000 => convert_unix_time(i64::try_from(t / 1_000).ok()?),
+        t => convert_unix_time(i64::try_from(t).ok()?),
+    }
+}
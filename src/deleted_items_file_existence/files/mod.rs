@@ -0,0 +1,3 @@
+pub mod recycle_bin;
+pub mod thumbcache;
+pub mod windows_search;
@@ -0,0 +1,226 @@
+// "Recycle Bin is a very important location on a Windows file system to understand. It can help you when accomplishing a forensic investigation, as every file that is deleted from a Windows recycle bin aware program is generally first put in the recycle bin." - SANS Windows Forensic Analysis Poster, Recycle Bin
+// C:\$Recycle.Bin\<SID>\$I###### (metadata) and $R###### (content), Vista+
+// C:\RECYCLER\<SID>\INFO2 (metadata) and Dc#.<ext> (content), XP
+// $I version 1 (Vista - 8.1) has a fixed size path, version 2 (Windows 10+) stores the path length
+
+use std::collections::HashMap;
+use std::fs;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::anyhow;
+use chrono::{DateTime, Utc};
+use common::{
+    convert_win_time_opt, find_file_case_insensitive, find_software_hive, read_ascii_string,
+    read_u32_le, read_u64_le, read_utf16_string,
+};
+use log::error;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::account_usage::registry::user_accounts::get_profile_paths;
+
+// INFO2 header size and size of every record
+const INFO2_HEADER_SIZE: usize = 20;
+const INFO2_RECORD_SIZE: usize = 800;
+
+#[derive(Debug, Serialize)]
+struct RecycleBinEntry {
+    sid: String,
+    profile_image_path: Option<String>,
+    metadata_file: String,
+    format: String,
+    original_path: String,
+    file_size: Option<u64>,
+    deleted: Option<DateTime<Utc>>,
+    content_file: String,
+    content_exists: bool,
+    content_size: Option<u64>,
+    content_sha256: Option<String>,
+}
+
+/// the original file as recorded in $I or INFO2
+struct DeletedFile {
+    format: String,
+    original_path: String,
+    file_size: Option<u64>,
+    deleted: Option<DateTime<Utc>>,
+}
+
+pub fn get_recycle_bin_data(input: &str, outpath: &str) -> anyhow::Result<()> {
+    print!("Working on Recycle Bin: ");
+    // the SID folders are resolved to users with the ProfileList
+    let profiles = find_software_hive(input)
+        .map_err(anyhow::Error::from)
+        .and_then(|path| get_profile_paths(&path))
+        .unwrap_or_else(|err| {
+            error!("Could not read ProfileList, SIDs will not be resolved: {err}");
+            HashMap::new()
+        });
+    let mut recycle_bin_list: Vec<RecycleBinEntry> = Vec::new();
+
+    for recycle_bin in ["$Recycle.Bin", "RECYCLER"] {
+        let Some(recycle_bin_path) = find_file_case_insensitive(input, recycle_bin) else {
+            continue;
+        };
+        for sid_folder in fs::read_dir(&recycle_bin_path)? {
+            let sid_folder = sid_folder?.path();
+            if !sid_folder.is_dir() {
+                continue;
+            }
+            let sid = sid_folder
+                .file_name()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let profile_image_path = profiles.get(&sid).cloned();
+            for file in fs::read_dir(&sid_folder)? {
+                let file = file?.path();
+                let name = file
+                    .file_name()
+                    .map(|s| s.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                if !file.is_file() {
+                    continue;
+                }
+                let parsed = if name.starts_with("$I") {
+                    // the content file has the same name, starting with $R
+                    parse_i_file(&file).map(|deleted_file| {
+                        vec![(deleted_file, sid_folder.join(format!("$R{}", &name[2..])))]
+                    })
+                } else if name.eq_ignore_ascii_case("INFO2") {
+                    parse_info2(&file, &sid_folder)
+                } else {
+                    continue;
+                };
+                let deleted_files = match parsed {
+                    Ok(deleted_files) => deleted_files,
+                    Err(err) => {
+                        error!("Failed to parse {}: {err}", file.display());
+                        continue;
+                    }
+                };
+                for (deleted_file, content_file) in deleted_files {
+                    let (content_exists, content_size, content_sha256) =
+                        content_info(&content_file);
+                    recycle_bin_list.push(RecycleBinEntry {
+                        sid: sid.clone(),
+                        profile_image_path: profile_image_path.clone(),
+                        metadata_file: file.to_string_lossy().to_string(),
+                        format: deleted_file.format,
+                        original_path: deleted_file.original_path,
+                        file_size: deleted_file.file_size,
+                        deleted: deleted_file.deleted,
+                        content_file: content_file.to_string_lossy().to_string(),
+                        content_exists,
+                        content_size,
+                        content_sha256,
+                    });
+                }
+            }
+        }
+    }
+
+    if recycle_bin_list.is_empty() {
+        println!("Nothing to do here, continuing with next job.");
+        return Ok(());
+    }
+
+    let file = File::create(format!("{outpath}/rb_recycle_bin.json"))?;
+    let mut writer = BufWriter::new(file);
+    serde_json::to_writer(&mut writer, &recycle_bin_list)?;
+    writer.flush()?;
+
+    println!("Done here!");
+    Ok(())
+}
+
+/// $I files: version, file size, deletion time and original path
+fn parse_i_file(path: &Path) -> anyhow::Result<DeletedFile> {
+    let data = fs::read(path)?;
+    let version = read_u64_le(&data, 0).ok_or(anyhow!("$I header is truncated!"))?;
+    let original_path = match version {
+        1 => read_utf16_string(&data, 24),
+        2 => read_utf16_string(&data, 28),
+        _ => return Err(anyhow!("Unknown $I version {version}!")),
+    }
+    .unwrap_or_default();
+    Ok(DeletedFile {
+        format: format!("$I version {version}"),
+        original_path,
+        file_size: read_u64_le(&data, 8),
+        deleted: read_u64_le(&data, 16).and_then(convert_win_time_opt),
+    })
+}
+
+/// INFO2 contains one record per deleted file, the content file is named D<drive letter><index>.<extension>
+fn parse_info2(path: &Path, sid_folder: &Path) -> anyhow::Result<Vec<(DeletedFile, PathBuf)>> {
+    let data = fs::read(path)?;
+    let mut deleted_files = Vec::new();
+    let mut offset = INFO2_HEADER_SIZE;
+    while let Some(record) = data.get(offset..offset + INFO2_RECORD_SIZE) {
+        offset += INFO2_RECORD_SIZE;
+        // ANSI path, the unicode path at the end of the record is preferred
+        let original_path = read_utf16_string(record, 280)
+            .filter(|p| !p.is_empty())
+            .or_else(|| read_ascii_string(record, 0))
+            .unwrap_or_default();
+        let (Some(index), Some(drive)) = (read_u32_le(record, 260), read_u32_le(record, 264))
+        else {
+            continue;
+        };
+        let drive_letter = (b'a' + (drive % 26) as u8) as char;
+        let extension = Path::new(&original_path.replace('\\', "/"))
+            .extension()
+            .map(|e| format!(".{}", e.to_string_lossy()))
+            .unwrap_or_default();
+        let content_name = format!("D{drive_letter}{index}{extension}");
+        let content_file = find_file_case_insensitive(&sid_folder.to_string_lossy(), &content_name)
+            .map(Into::into)
+            .unwrap_or(sid_folder.join(&content_name));
+        deleted_files.push((
+            DeletedFile {
+                format: "INFO2".to_string(),
+                original_path,
+                file_size: read_u32_le(record, 276).map(u64::from),
+                deleted: read_u64_le(record, 268).and_then(convert_win_time_opt),
+            },
+            content_file,
+        ));
+    }
+    Ok(deleted_files)
+}
+
+/// existence, size and SHA-256 of a content file, deleted folders are summed up but not hashed
+fn content_info(path: &Path) -> (bool, Option<u64>, Option<String>) {
+    if path.is_file() {
+        let size = fs::metadata(path).ok().map(|m| m.len());
+        let sha256 = File::open(path).ok().and_then(|mut file| {
+            let mut hasher = Sha256::new();
+            std::io::copy(&mut file, &mut hasher).ok()?;
+            Some(format!("{:x}", hasher.finalize()))
+        });
+        (true, size, sha256)
+    } else if path.is_dir() {
+        (true, Some(folder_size(path)), None)
+    } else {
+        (false, None, None)
+    }
+}
+
+fn folder_size(path: &Path) -> u64 {
+    let Ok(entries) = fs::read_dir(path) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|entry| {
+            let path = entry.path();
+            if path.is_dir() {
+                folder_size(&path)
+            } else {
+                entry.metadata().map(|m| m.len()).unwrap_or(0)
+            }
+        })
+        .sum()
+}
@@ -0,0 +1,282 @@
+// "Thumbnails of pictures, office documents, and folders exist in a database called the thumbcache." - SANS Windows Forensic Analysis Poster, Thumbcache
+// "Hidden file in directory where images on machine exist stored in a smaller thumbnail graphics." - SANS Windows Forensic Analysis Poster, Thumbs.db
+// %USERPROFILE%\AppData\Local\Microsoft\Windows\Explorer\thumbcache_*.db (Vista+)
+// Thumbs.db in every folder that was viewed with thumbnails (XP, OLE compound file with a Catalog stream)
+// all thumbnails are exported to a subfolder of the output path, the JSON is the index for them
+// format reference: https://github.com/libyal/libwtcdb/blob/main/documentation/Windows%20Explorer%20Thumbnail%20Cache%20database%20format.asciidoc
+
+use std::fs;
+use std::fs::File;
+use std::io::{BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::anyhow;
+use chrono::{DateTime, Utc};
+use common::{
+    convert_win_time_opt, file_name, find_file_case_insensitive, find_user_profiles, read_u32_le,
+    read_u64_le, read_utf16_string,
+};
+use log::error;
+use serde::Serialize;
+
+const THUMBCACHE_FOLDER: &str = "AppData/Local/Microsoft/Windows/Explorer";
+const EXPORT_FOLDER: &str = "thumbnails";
+// thumbcache format versions
+const VERSION_VISTA: u32 = 20;
+const VERSION_7: u32 = 21;
+
+#[derive(Debug, Serialize)]
+struct ThumbnailEntry {
+    source_file: String,
+    cache_format: String,
+    cache_id: String,
+    // thumbcache: identifier string of the entry, Thumbs.db: name of the original file
+    name: Option<String>,
+    // Thumbs.db only
+    modified: Option<DateTime<Utc>>,
+    width: Option<u32>,
+    height: Option<u32>,
+    data_size: usize,
+    data_checksum: Option<String>,
+    exported_file: Option<String>,
+}
+
+pub fn get_thumbcache_data(input: &str, outpath: &str) -> anyhow::Result<()> {
+    print!("Working on Thumbcache and Thumbs.db: ");
+    let export_path = format!("{outpath}/{EXPORT_FOLDER}");
+    fs::create_dir_all(&export_path)?;
+    let mut thumbnail_list: Vec<ThumbnailEntry> = Vec::new();
+
+    // thumbcache of every profile
+    for profile in find_user_profiles(input).unwrap_or_default() {
+        let Some(explorer_folder) = find_file_case_insensitive(&profile, THUMBCACHE_FOLDER) else {
+            continue;
+        };
+        let profile_name = file_name(Path::new(&profile));
+        let entries = match fs::read_dir(&explorer_folder) {
+            Ok(entries) => entries,
+            Err(err) => {
+                error!("Failed to read {explorer_folder}: {err}");
+                continue;
+            }
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let name = path
+                .file_name()
+                .map(|s| s.to_string_lossy().to_lowercase())
+                .unwrap_or_default();
+            if !name.starts_with("thumbcache_") || !name.ends_with(".db") || !path.is_file() {
+                continue;
+            }
+            let prefix = format!("{profile_name}_{}", name.trim_end_matches(".db"));
+            if let Err(err) = parse_thumbcache(&path, &export_path, &prefix, &mut thumbnail_list) {
+                error!("Failed to parse {}: {err}", path.display())
+            }
+        }
+    }
+
+    // Thumbs.db can be in every folder
+    let mut thumbs_dbs = Vec::new();
+    find_thumbs_dbs(Path::new(input), &mut thumbs_dbs);
+    for (number, path) in thumbs_dbs.iter().enumerate() {
+        let prefix = format!("thumbsdb_{number}");
+        if let Err(err) = parse_thumbs_db(path, &export_path, &prefix, &mut thumbnail_list) {
+            error!("Failed to parse {}: {err}", path.display())
+        }
+    }
+
+    if thumbnail_list.is_empty() {
+        // no need to keep the empty export folder
+        let _ = fs::remove_dir(&export_path);
+        println!("Nothing to do here, continuing with next job.");
+        return Ok(());
+    }
+
+    let file = File::create(format!("{outpath}/tc_thumbnails.json"))?;
+    let mut writer = BufWriter::new(file);
+    serde_json::to_writer(&mut writer, &thumbnail_list)?;
+    writer.flush()?;
+
+    println!("Done here!");
+    Ok(())
+}
+
+/// thumbcache_*.db: "CMMM" header followed by "CMMM" cache entries
+fn parse_thumbcache(
+    path: &Path,
+    export_path: &str,
+    prefix: &str,
+    thumbnail_list: &mut Vec<ThumbnailEntry>,
+) -> anyhow::Result<()> {
+    let data = fs::read(path)?;
+    // thumbcache_idx.db has its own format ("IMMM")
+    if data.get(0..4) != Some(b"CMMM") {
+        return Ok(());
+    }
+    let version = read_u32_le(&data, 4).ok_or(anyhow!("Header is truncated!"))?;
+    // Windows 8+ has 4 more bytes before the offset of the first entry
+    let first_entry_offset = if version <= VERSION_7 { 12 } else { 16 };
+    let mut offset =
+        read_u32_le(&data, first_entry_offset).ok_or(anyhow!("Header is truncated!"))? as usize;
+
+    while let Some(entry) = data.get(offset..) {
+        if entry.get(0..4) != Some(b"CMMM") {
+            break;
+        }
+        let entry_size =
+            read_u32_le(entry, 4).ok_or(anyhow!("Cache entry is truncated!"))? as usize;
+        if entry_size == 0 {
+            break;
+        }
+        let hash = read_u64_le(entry, 8).unwrap_or_default();
+        // Vista stores the file extension in the entry, Windows 8+ width and height of the thumbnail
+        let (header_size, fields_offset, dimensions_offset) = match version {
+            VERSION_VISTA => (56, 24, None),
+            VERSION_7 => (48, 16, None),
+            _ => (56, 16, Some(28)),
+        };
+        let identifier_size = read_u32_le(entry, fields_offset).unwrap_or_default() as usize;
+        let padding_size = read_u32_le(entry, fields_offset + 4).unwrap_or_default() as usize;
+        let data_size = read_u32_le(entry, fields_offset + 8).unwrap_or_default() as usize;
+        let data_checksum = read_u64_le(entry, header_size - 16);
+        let identifier = entry
+            .get(header_size..header_size + identifier_size)
+            .and_then(|raw| read_utf16_string(raw, 0));
+        let data_start = header_size + identifier_size + padding_size;
+
+        if data_size > 0 {
+            let cache_id = format!("{hash:016x}");
+            let exported_file = entry
+                .get(data_start..data_start + data_size)
+                .and_then(|image| {
+                    export_thumbnail(export_path, &format!("{prefix}_{cache_id}"), image)
+                });
+            thumbnail_list.push(ThumbnailEntry {
+                source_file: path.to_string_lossy().to_string(),
+                cache_format: format!("thumbcache version {version}"),
+                cache_id,
+                name: identifier,
+                modified: None,
+                width: dimensions_offset.and_then(|o| read_u32_le(entry, o)),
+                height: dimensions_offset.and_then(|o| read_u32_le(entry, o + 4)),
+                data_size,
+                data_checksum: data_checksum.map(|c| format!("{c:016x}")),
+                exported_file,
+            });
+        }
+        offset += entry_size;
+    }
+    Ok(())
+}
+
+/// Thumbs.db: the Catalog stream lists index, modification time and name, the thumbnail of index 123 is in stream "321"
+fn parse_thumbs_db(
+    path: &Path,
+    export_path: &str,
+    prefix: &str,
+    thumbnail_list: &mut Vec<ThumbnailEntry>,
+) -> anyhow::Result<()> {
+    let mut compound_file = cfb::open(path)?;
+    let mut catalog = Vec::new();
+    compound_file
+        .open_stream("/Catalog")?
+        .read_to_end(&mut catalog)?;
+
+    let header_size = catalog
+        .get(0..2)
+        .map(|h| u16::from_le_bytes([h[0], h[1]]) as usize)
+        .ok_or(anyhow!("Catalog header is truncated!"))?;
+    let width = read_u32_le(&catalog, 8);
+    let height = read_u32_le(&catalog, 12);
+    let mut offset = header_size;
+    while let Some(entry) = catalog.get(offset..) {
+        let Some(entry_size) = read_u32_le(entry, 0) else {
+            break;
+        };
+        if entry_size == 0 {
+            break;
+        }
+        let index = read_u32_le(entry, 4).unwrap_or_default();
+        let modified = read_u64_le(entry, 8).and_then(convert_win_time_opt);
+        let name = read_utf16_string(entry, 16);
+        offset += entry_size as usize;
+
+        let stream_name: String = index.to_string().chars().rev().collect();
+        let mut stream = Vec::new();
+        if let Ok(mut thumbnail_stream) = compound_file.open_stream(format!("/{stream_name}")) {
+            // the catalog entry is kept without thumbnail
+            if let Err(err) = thumbnail_stream.read_to_end(&mut stream) {
+                error!(
+                    "Failed to read stream {stream_name} in {}: {err}",
+                    path.display()
+                );
+                stream.clear();
+            }
+        }
+        // the stream has a small header (header size, unknown, data size) before the image
+        let image = read_u32_le(&stream, 0)
+            .and_then(|size| stream.get(size as usize..))
+            .unwrap_or_default();
+        let cache_id = index.to_string();
+        thumbnail_list.push(ThumbnailEntry {
+            source_file: path.to_string_lossy().to_string(),
+            cache_format: "Thumbs.db".to_string(),
+            exported_file: export_thumbnail(export_path, &format!("{prefix}_{cache_id}"), image),
+            cache_id,
+            name,
+            modified,
+            width,
+            height,
+            data_size: image.len(),
+            data_checksum: None,
+        });
+    }
+    Ok(())
+}
+
+/// writes the image with the extension matching its content, returns the path
+fn export_thumbnail(export_path: &str, name: &str, image: &[u8]) -> Option<String> {
+    if image.is_empty() {
+        return None;
+    }
+    let extension = if image.starts_with(&[0xFF, 0xD8]) {
+        "jpg"
+    } else if image.starts_with(b"\x89PNG") {
+        "png"
+    } else if image.starts_with(b"BM") {
+        "bmp"
+    } else {
+        "bin"
+    };
+    let file_path = format!("{export_path}/{name}.{extension}");
+    match fs::write(&file_path, image) {
+        Ok(()) => Some(file_path),
+        Err(err) => {
+            error!("Failed to export thumbnail {file_path}: {err}");
+            None
+        }
+    }
+}
+
+/// collects all Thumbs.db files below path, symlinks and junctions are not followed
+fn find_thumbs_dbs(path: &Path, thumbs_dbs: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(path) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        if file_type.is_dir() {
+            find_thumbs_dbs(&entry.path(), thumbs_dbs);
+        } else if file_type.is_file()
+            && entry
+                .file_name()
+                .to_string_lossy()
+                .eq_ignore_ascii_case("Thumbs.db")
+        {
+            thumbs_dbs.push(entry.path());
+        }
+    }
+}
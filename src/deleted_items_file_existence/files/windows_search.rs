@@ -0,0 +1,236 @@
+// "Windows Search Database ... contains a list of indexed files, emails and internet history, including partial file content" - SANS Windows Forensic Analysis Poster, Windows Search Database
+// %PROGRAMDATA%\Microsoft\Search\Data\Applications\Windows\Windows.edb (ESE database, up to Windows 10)
+// %PROGRAMDATA%\Microsoft\Search\Data\Applications\Windows\Windows.db (SQLite database, Windows 11)
+// columns/properties are named like "4447-System_ItemPathDisplay", only the part after the first "-" is used
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+use anyhow::anyhow;
+use chrono::{DateTime, Datelike, Utc};
+use common::{convert_win_time_opt, find_file_case_insensitive};
+use log::error;
+use rusqlite::types::ValueRef;
+use serde::Serialize;
+
+use crate::formats::ese::EseDatabase;
+use crate::formats::sqlite::with_sqlite_copy;
+
+const SEARCH_FOLDERS: [&str; 2] = [
+    "ProgramData/Microsoft/Search/Data/Applications/Windows",
+    "Documents and Settings/All Users/Application Data/Microsoft/Search/Data/Applications/Windows",
+];
+
+#[derive(Debug, Serialize)]
+struct SearchEntry {
+    database: String,
+    work_id: Option<i64>,
+    record_type: String,
+    path: String,
+    name: Option<String>,
+    size: Option<u64>,
+    modified: Option<DateTime<Utc>>,
+    created: Option<DateTime<Utc>>,
+    accessed: Option<DateTime<Utc>>,
+    owner: Option<String>,
+    item_type: Option<String>,
+    content_snippet: Option<String>,
+    subject: Option<String>,
+    from: Option<String>,
+    to: Option<String>,
+}
+
+/// property value as stored by the indexer, blobs are decoded depending on the property
+enum PropertyValue {
+    Text(String),
+    Bytes(Vec<u8>),
+    Integer(i64),
+}
+
+pub fn get_windows_search_data(input: &str, outpath: &str) -> anyhow::Result<()> {
+    print!("Working on Windows Search Database: ");
+    let mut search_list: Vec<SearchEntry> = Vec::new();
+
+    for folder in SEARCH_FOLDERS {
+        if let Some(path) = find_file_case_insensitive(input, &format!("{folder}/Windows.edb")) {
+            if let Err(err) = read_windows_edb(&path, &mut search_list) {
+                error!("Failed to read {path}: {err}")
+            }
+        }
+        if let Some(path) = find_file_case_insensitive(input, &format!("{folder}/Windows.db")) {
+            if let Err(err) = read_windows_db(&path, &mut search_list) {
+                error!("Failed to read {path}: {err}")
+            }
+        }
+    }
+
+    if search_list.is_empty() {
+        println!("Nothing to do here, continuing with next job.");
+        return Ok(());
+    }
+
+    let file = File::create(format!("{outpath}/ws_windows_search.json"))?;
+    let mut writer = BufWriter::new(file);
+    serde_json::to_writer(&mut writer, &search_list)?;
+    writer.flush()?;
+
+    println!("Done here!");
+    Ok(())
+}
+
+/// Windows.edb: one record per indexed item in SystemIndex_0A (Windows 7 - 10) or SystemIndex_PropertyStore (Windows 10 1809+)
+fn read_windows_edb(path: &str, search_list: &mut Vec<SearchEntry>) -> anyhow::Result<()> {
+    let mut database = EseDatabase::open(path)?;
+    let tables: Vec<_> = database
+        .tables()?
+        .into_iter()
+        .filter(|t| t.name == "SystemIndex_0A" || t.name == "SystemIndex_PropertyStore")
+        .collect();
+    if tables.is_empty() {
+        return Err(anyhow!("No SystemIndex table found!"));
+    }
+    for table in tables {
+        database.for_each_record(&table, |record| {
+            let mut properties = HashMap::new();
+            for (column, value) in record {
+                // text (10) and long text (12) columns, everything else is binary or numeric
+                let property = if matches!(value.column_type, 10 | 12) {
+                    PropertyValue::Text(value.as_text())
+                } else {
+                    PropertyValue::Bytes(value.data.clone())
+                };
+                properties.insert(property_name(column).to_string(), property);
+            }
+            let work_id = properties.get("WorkID").and_then(integer).map(|i| i as i64);
+            if let Some(entry) = build_entry(path, work_id, &properties) {
+                search_list.push(entry);
+            }
+        })?;
+    }
+    Ok(())
+}
+
+/// Windows.db: one row per property (WorkId, ColumnId, Value), the property names are in the metadata table
+fn read_windows_db(path: &str, search_list: &mut Vec<SearchEntry>) -> anyhow::Result<()> {
+    let items = with_sqlite_copy(path, |connection| {
+        let mut statement = connection.prepare(
+            "SELECT ps.WorkId, md.UniqueKey, ps.Value FROM SystemIndex_1_PropertyStore ps \
+             JOIN SystemIndex_1_PropertyStore_Metadata md ON ps.ColumnId = md.Id",
+        )?;
+        let mut rows = statement.query([])?;
+        let mut items: BTreeMap<i64, HashMap<String, PropertyValue>> = BTreeMap::new();
+        while let Some(row) = rows.next()? {
+            let work_id: i64 = row.get(0)?;
+            let unique_key: String = row.get(1)?;
+            let value = match row.get_ref(2)? {
+                ValueRef::Integer(i) => PropertyValue::Integer(i),
+                ValueRef::Real(r) => PropertyValue::Integer(r as i64),
+                ValueRef::Text(t) => PropertyValue::Text(String::from_utf8_lossy(t).to_string()),
+                ValueRef::Blob(b) => PropertyValue::Bytes(b.to_vec()),
+                ValueRef::Null => continue,
+            };
+            items
+                .entry(work_id)
+                .or_default()
+                .insert(property_name(&unique_key).to_string(), value);
+        }
+        Ok(items)
+    })?;
+    for (work_id, properties) in items {
+        if let Some(entry) = build_entry(path, Some(work_id), &properties) {
+            search_list.push(entry);
+        }
+    }
+    Ok(())
+}
+
+fn build_entry(
+    database: &str,
+    work_id: Option<i64>,
+    properties: &HashMap<String, PropertyValue>,
+) -> Option<SearchEntry> {
+    let get_text = |name: &str| properties.get(name).map(text).filter(|t| !t.is_empty());
+    let get_time = |name: &str| properties.get(name).and_then(filetime);
+    let path = get_text("System_ItemPathDisplay").or_else(|| get_text("System_ItemUrl"))?;
+    let url = get_text("System_ItemUrl")
+        .unwrap_or_default()
+        .to_lowercase();
+    let record_type = if url.starts_with("iehistory:") {
+        "Internet History"
+    } else if url.starts_with("mapi") {
+        "Email"
+    } else {
+        "File"
+    };
+    Some(SearchEntry {
+        database: database.to_string(),
+        work_id,
+        record_type: record_type.to_string(),
+        path,
+        name: get_text("System_ItemNameDisplay").or_else(|| get_text("System_FileName")),
+        size: properties.get("System_Size").and_then(integer),
+        modified: get_time("System_DateModified"),
+        created: get_time("System_DateCreated"),
+        accessed: get_time("System_DateAccessed"),
+        owner: get_text("System_FileOwner"),
+        item_type: get_text("System_ItemType"),
+        content_snippet: get_text("System_Search_AutoSummary"),
+        subject: get_text("System_Subject"),
+        from: get_text("System_Message_FromAddress"),
+        to: get_text("System_Message_ToAddress"),
+    })
+}
+
+/// "4447-System_ItemPathDisplay" -> "System_ItemPathDisplay"
+fn property_name(column: &str) -> &str {
+    match column.split_once('-') {
+        Some((id, name)) if id.chars().all(|c| c.is_ascii_hexdigit()) => name,
+        _ => column,
+    }
+}
+
+/// blobs contain UTF-16 strings
+fn text(value: &PropertyValue) -> String {
+    match value {
+        PropertyValue::Text(text) => text.clone(),
+        PropertyValue::Bytes(bytes) => {
+            let chars: Vec<u16> = bytes
+                .chunks_exact(2)
+                .map(|c| u16::from_le_bytes([c[0], c[1]]))
+                .collect();
+            String::from_utf16_lossy(&chars)
+                .trim_end_matches('\0')
+                .to_string()
+        }
+        PropertyValue::Integer(i) => i.to_string(),
+    }
+}
+
+fn integer(value: &PropertyValue) -> Option<u64> {
+    match value {
+        PropertyValue::Integer(i) => Some(*i as u64),
+        PropertyValue::Bytes(bytes) if !bytes.is_empty() && bytes.len() <= 8 => {
+            let mut raw = [0u8; 8];
+            raw[..bytes.len()].copy_from_slice(bytes);
+            Some(u64::from_le_bytes(raw))
+        }
+        PropertyValue::Text(text) => text.parse().ok(),
+        _ => None,
+    }
+}
+
+/// FILETIMEs are stored little endian by Windows.db, Windows.edb stores some of them big endian
+fn filetime(value: &PropertyValue) -> Option<DateTime<Utc>> {
+    let plausible = |time: &DateTime<Utc>| (1980..=2100).contains(&time.year());
+    match value {
+        PropertyValue::Integer(i) => convert_win_time_opt(*i as u64),
+        PropertyValue::Bytes(bytes) if bytes.len() == 8 => {
+            let raw: [u8; 8] = bytes.as_slice().try_into().ok()?;
+            convert_win_time_opt(u64::from_le_bytes(raw))
+                .filter(plausible)
+                .or_else(|| convert_win_time_opt(u64::from_be_bytes(raw)).filter(plausible))
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,19 @@
+// FilesOnly Processing Mode for Deleted Items and File Existence
+use log::error;
+
+use crate::deleted_items_file_existence::files::recycle_bin::get_recycle_bin_data;
+use crate::deleted_items_file_existence::files::thumbcache::get_thumbcache_data;
+use crate::deleted_items_file_existence::files::windows_search::get_windows_search_data;
+
+pub fn get_deleteditems_file_data(input: &str, outpath: &str) -> anyhow::Result<()> {
+    if let Err(err) = get_recycle_bin_data(input, outpath) {
+        error!("Failed to get Recycle Bin: {err}")
+    }
+    if let Err(err) = get_thumbcache_data(input, outpath) {
+        error!("Failed to get Thumbcache and Thumbs.db: {err}")
+    }
+    if let Err(err) = get_windows_search_data(input, outpath) {
+        error!("Failed to get Windows Search Database: {err}")
+    }
+    Ok(())
+}
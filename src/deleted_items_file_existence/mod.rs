@@ -0,0 +1,4 @@
+pub mod files;
+pub mod files_only;
+pub mod registry;
+pub mod registry_only;
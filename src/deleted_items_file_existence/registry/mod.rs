@@ -0,0 +1,2 @@
+pub mod typed_paths;
+pub mod word_wheel_query;
@@ -0,0 +1,92 @@
+// NTUSER.DAT
+// "Paths typed into the File Explorer address bar" - SANS Windows Forensic Analysis Poster, User Typed Paths
+// NTUSER.DAT\Software\Microsoft\Windows\CurrentVersion\Explorer\TypedPaths
+// values are named url1 to url25, url1 is the most recently typed path
+
+use std::fs::File;
+use std::io::{BufWriter, Read, Write};
+
+use anyhow::anyhow;
+use chrono::{DateTime, Utc};
+use common::{convert_win_time, read_utf16_string};
+use log::error;
+use nt_hive::Hive;
+use serde::Serialize;
+
+use crate::account_usage::registry::user_accounts::UserHive;
+
+#[derive(Debug, Serialize)]
+struct TypedPathEntry {
+    sid: String,
+    profile_image_path: String,
+    value_name: String,
+    mru_position: Option<usize>,
+    path: String,
+    // only known for the most recently typed path
+    typed: Option<DateTime<Utc>>,
+}
+
+pub fn get_typedpaths_data(user_hives: &[UserHive], outpath: &str) -> anyhow::Result<()> {
+    print!("Working on TypedPaths: ");
+    let mut typedpaths_list: Vec<TypedPathEntry> = Vec::new();
+
+    for user_hive in user_hives {
+        if let Err(err) = get_user_typedpaths(user_hive, &mut typedpaths_list) {
+            error!("Failed to get TypedPaths of {}: {err}", user_hive.sid)
+        }
+    }
+
+    if typedpaths_list.is_empty() {
+        println!("Nothing to do here, continuing with next job.");
+        return Ok(());
+    }
+
+    let file = File::create(format!("{outpath}/reg_typedpaths.json"))?;
+    let mut writer = BufWriter::new(file);
+    serde_json::to_writer(&mut writer, &typedpaths_list)?;
+    writer.flush()?;
+
+    println!("Done here!");
+    Ok(())
+}
+
+fn get_user_typedpaths(
+    user_hive: &UserHive,
+    typedpaths_list: &mut Vec<TypedPathEntry>,
+) -> anyhow::Result<()> {
+    let mut buffer = Vec::new();
+    File::open(&user_hive.hive_path)?.read_to_end(&mut buffer)?;
+
+    let hive = Hive::without_validation(buffer.as_ref())?;
+    let root_key_node = hive.root_key_node()?;
+    let sub_key_node = root_key_node
+        .subpath("Software\\Microsoft\\Windows\\CurrentVersion\\Explorer\\TypedPaths")
+        .ok_or(anyhow!(
+            "Key 'Software\\Microsoft\\Windows\\CurrentVersion\\Explorer\\TypedPaths' can not be found!"
+        ))??;
+    let timestamp = convert_win_time(sub_key_node.header().timestamp.get());
+
+    let values = sub_key_node
+        .values()
+        .ok_or(anyhow!("Values can not be unwrapped!"))??;
+    for value in values {
+        let value = value?;
+        let value_name = value.name()?.to_string();
+        // url1 is position 0
+        let position = value_name
+            .to_lowercase()
+            .strip_prefix("url")
+            .and_then(|n| n.parse::<usize>().ok())
+            .and_then(|n| n.checked_sub(1));
+        let data = value.data()?.into_vec()?;
+        typedpaths_list.push(TypedPathEntry {
+            sid: user_hive.sid.clone(),
+            profile_image_path: user_hive.profile_image_path.clone(),
+            value_name,
+            mru_position: position,
+            path: read_utf16_string(&data, 0).unwrap_or_default(),
+            typed: (position == Some(0)).then_some(timestamp),
+        });
+    }
+    Ok(())
+}
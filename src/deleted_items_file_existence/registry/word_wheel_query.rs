@@ -0,0 +1,82 @@
+// NTUSER.DAT
+// "Keywords searched for from the START menu bar on a Windows 7+ machine" - SANS Windows Forensic Analysis Poster, Search - WordWheelQuery
+// NTUSER.DAT\Software\Microsoft\Windows\CurrentVersion\Explorer\WordWheelQuery
+// numbered values contain the search term (UTF-16), ordered by MRUListEx
+
+use std::fs::File;
+use std::io::{BufWriter, Read, Write};
+
+use anyhow::anyhow;
+use chrono::{DateTime, Utc};
+use common::read_utf16_string;
+use log::error;
+use nt_hive::Hive;
+use serde::Serialize;
+
+use crate::account_usage::registry::user_accounts::UserHive;
+use crate::formats::mru::{mru_position, read_mru_values};
+
+#[derive(Debug, Serialize)]
+struct WordWheelQueryEntry {
+    sid: String,
+    profile_image_path: String,
+    value_name: String,
+    mru_position: Option<usize>,
+    search_term: String,
+    // only known for the most recent search
+    searched: Option<DateTime<Utc>>,
+}
+
+pub fn get_wordwheelquery_data(user_hives: &[UserHive], outpath: &str) -> anyhow::Result<()> {
+    print!("Working on WordWheelQuery: ");
+    let mut wordwheelquery_list: Vec<WordWheelQueryEntry> = Vec::new();
+
+    for user_hive in user_hives {
+        if let Err(err) = get_user_wordwheelquery(user_hive, &mut wordwheelquery_list) {
+            error!("Failed to get WordWheelQuery of {}: {err}", user_hive.sid)
+        }
+    }
+
+    if wordwheelquery_list.is_empty() {
+        println!("Nothing to do here, continuing with next job.");
+        return Ok(());
+    }
+
+    let file = File::create(format!("{outpath}/reg_wordwheelquery.json"))?;
+    let mut writer = BufWriter::new(file);
+    serde_json::to_writer(&mut writer, &wordwheelquery_list)?;
+    writer.flush()?;
+
+    println!("Done here!");
+    Ok(())
+}
+
+fn get_user_wordwheelquery(
+    user_hive: &UserHive,
+    wordwheelquery_list: &mut Vec<WordWheelQueryEntry>,
+) -> anyhow::Result<()> {
+    let mut buffer = Vec::new();
+    File::open(&user_hive.hive_path)?.read_to_end(&mut buffer)?;
+
+    let hive = Hive::without_validation(buffer.as_ref())?;
+    let root_key_node = hive.root_key_node()?;
+    let sub_key_node = root_key_node
+        .subpath("Software\\Microsoft\\Windows\\CurrentVersion\\Explorer\\WordWheelQuery")
+        .ok_or(anyhow!(
+            "Key 'Software\\Microsoft\\Windows\\CurrentVersion\\Explorer\\WordWheelQuery' can not be found!"
+        ))??;
+
+    let mru_values = read_mru_values(&sub_key_node)?;
+    for (value_name, data) in &mru_values.values {
+        let position = mru_position(&mru_values.order, value_name);
+        wordwheelquery_list.push(WordWheelQueryEntry {
+            sid: user_hive.sid.clone(),
+            profile_image_path: user_hive.profile_image_path.clone(),
+            value_name: value_name.clone(),
+            mru_position: position,
+            search_term: read_utf16_string(data, 0).unwrap_or_default(),
+            searched: (position == Some(0)).then_some(mru_values.timestamp),
+        });
+    }
+    Ok(())
+}
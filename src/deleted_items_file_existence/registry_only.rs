@@ -0,0 +1,36 @@
+// RegistryOnly Processing Mode for Deleted Items and File Existence
+use anyhow::anyhow;
+use common::find_software_hive;
+use log::error;
+
+use crate::account_usage::registry::user_accounts::find_user_hives;
+use crate::deleted_items_file_existence::registry::typed_paths::get_typedpaths_data;
+use crate::deleted_items_file_existence::registry::word_wheel_query::get_wordwheelquery_data;
+
+pub fn get_deleteditems_registry_data(input: &str, outpath: &str) -> anyhow::Result<()> {
+    let mut found_something = false;
+    // SOFTWARE hive (needed to find the NTUSER.DAT hives)
+    match find_software_hive(input) {
+        Ok(path) => match find_user_hives(input, &path, "NTUSER.DAT") {
+            Ok(user_hives) => {
+                found_something = true;
+                if let Err(err) = get_typedpaths_data(&user_hives, outpath) {
+                    error!("Failed to get TypedPaths: {err}")
+                }
+                if let Err(err) = get_wordwheelquery_data(&user_hives, outpath) {
+                    error!("Failed to get WordWheelQuery: {err}")
+                }
+            }
+            Err(err) => {
+                error!("Could not find NTUSER.DAT hives: {err}")
+            }
+        },
+        Err(err) => {
+            error!("Could not find Software hive: {err}")
+        }
+    }
+    if !found_something {
+        return Err(anyhow!("No NTUSER.DAT found!"));
+    }
+    Ok(())
+}
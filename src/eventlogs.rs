@@ -1,7 +1,7 @@
 // Contains the code for the "EventLogs" Processing Mode
 // Imports
 use anyhow::anyhow;
-use common::{find_security_evtx, find_system_evtx};
+use common::{find_oalerts_evtx, find_security_evtx, find_system_evtx};
 use log::error;
 
 // Account Usage
@@ -11,6 +11,8 @@ use crate::account_usage::eventlogs::service_events::{
     sec_evtx_service_events_data, sys_evtx_service_events_data,
 };
 use crate::account_usage::eventlogs::succ_faillogons::sec_evtx_logons_data;
+// File and Folder Opening
+use crate::file_folder_opening::eventlogs::oalerts::evtx_oalerts_data;
 
 // function to get everything
 pub fn get_eventlog_data(input: &str, outpath: &str) -> anyhow::Result<()> {
@@ -48,6 +50,17 @@ pub fn get_eventlog_data(input: &str, outpath: &str) -> anyhow::Result<()> {
             error!("Could not find Security.evtx: {err}")
         }
     }
+    match find_oalerts_evtx(input) {
+        Ok(path) => {
+            found_something = true;
+            if let Err(err) = evtx_oalerts_data(&path, outpath) {
+                error!("Failed to get Office OAlerts: {err}")
+            }
+        }
+        Err(err) => {
+            error!("Could not find OAlerts.evtx: {err}")
+        }
+    }
 
     if !found_something {
         return Err(anyhow!("No .evtx found!"));
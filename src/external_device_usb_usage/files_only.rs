@@ -0,0 +1,12 @@
+// FilesOnly Processing Mode for External Devices
+use log::error;
+
+// shortcut files record drive type, volume serial number and volume label of the target's volume
+use crate::file_folder_opening::files::shortcut_files::get_shortcut_files_data;
+
+pub fn get_externaldevice_file_data(input: &str, outpath: &str) -> anyhow::Result<()> {
+    if let Err(err) = get_shortcut_files_data(input, outpath) {
+        error!("Failed to get Shortcut Files: {err}")
+    }
+    Ok(())
+}
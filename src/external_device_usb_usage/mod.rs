@@ -1,4 +1,5 @@
 //pub mod eventlogs;
 //mod eventlogs_only;
+pub mod files_only;
 pub mod registry;
 pub mod registry_only;
@@ -0,0 +1,118 @@
+// "MS Office programs produce event logs that are recorded in the OAlerts.evtx file. All Office applications log dialog boxes that are presented to the user." - SANS Windows Forensic Analysis Poster, Office OAlerts
+// %SYSTEMROOT%\System32\winevt\Logs\OAlerts.evtx
+// event id 300, the event data consists of unnamed values: application name, dialog text, alert id and office version
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use common::{parse_evtx, Event};
+use serde::Serialize;
+use serde_json::Value;
+use xmltojson::to_json;
+use {serde_xml_rs, xmltojson};
+
+#[derive(Debug, Serialize)]
+struct OAlertEntry {
+    event_record_id: u64,
+    event_id: u32,
+    timestamp: DateTime<Utc>,
+    application: Option<String>,
+    dialog_text: Option<String>,
+    file: Option<String>,
+    data: Value,
+}
+
+// function to get data
+pub fn evtx_oalerts_data(input: &str, outpath: &str) -> Result<()> {
+    print!("Working on Office OAlerts: ");
+    let mut parser = parse_evtx(input).context("Failed to parse evtx!")?;
+    let mut oalerts_list: Vec<OAlertEntry> = Vec::new();
+
+    for record in parser.records() {
+        let record = record.context("Failed to get record!")?;
+        let event: Event = serde_xml_rs::from_str(&record.data)?;
+
+        // event id 300 -> office alert dialog
+        if event.system.event_id != 300 {
+            continue;
+        }
+        let values: Vec<Option<String>> = event
+            .event_data
+            .map(|event_data| event_data.events.into_iter().map(|d| d.value).collect())
+            .unwrap_or_default();
+        let application = values.first().cloned().flatten();
+        let dialog_text = values.get(1).cloned().flatten();
+        let data = record.clone().data;
+        let json_data = to_json(&data)?;
+        oalerts_list.push(OAlertEntry {
+            event_record_id: record.event_record_id,
+            event_id: event.system.event_id,
+            timestamp: record.timestamp,
+            file: dialog_text.as_deref().and_then(find_file_reference),
+            application,
+            dialog_text,
+            data: json_data,
+        });
+    }
+    // check if list is empty
+    if oalerts_list.is_empty() {
+        println!("Nothing to do here, continuing with next job.");
+        return Ok(());
+    }
+
+    let file = File::create(format!("{outpath}/evtx_oalerts.json"))?;
+    let mut writer = BufWriter::new(file);
+    serde_json::to_writer(&mut writer, &oalerts_list)?;
+    writer.flush()?;
+
+    println!("Done here!");
+    Ok(())
+}
+
+/// finds the file mentioned in a dialog text, e.g. "Do you want to save changes to 'secret.docx'?"
+fn find_file_reference(text: &str) -> Option<String> {
+    // quoted names (straight or typographic quotes), apostrophes ("can't ... won't") are no file names
+    for (open, close) in [
+        ('\'', '\''),
+        ('"', '"'),
+        ('\u{201C}', '\u{201D}'),
+        ('\u{2018}', '\u{2019}'),
+    ] {
+        let mut rest = text;
+        while let Some((_, quoted)) = rest.split_once(open) {
+            let Some((name, _)) = quoted.split_once(close) else {
+                break;
+            };
+            if looks_like_file(name) {
+                return Some(name.to_string());
+            }
+            // the closing character may be the opening quote of the name
+            rest = quoted;
+        }
+    }
+    // unquoted local (C:\...) or network (\\server\...) paths end at the first whitespace
+    let start = text
+        .find(":\\")
+        .and_then(|i| i.checked_sub(1))
+        .or_else(|| text.find("\\\\"))?;
+    text.get(start..)?
+        .split_whitespace()
+        .next()
+        .map(|path| path.trim_end_matches(['.', ',', '?', '!']).to_string())
+}
+
+/// a path or a file name with an extension, e.g. "secret.docx"
+fn looks_like_file(name: &str) -> bool {
+    let name = name.trim();
+    if name.is_empty() || name.contains('\n') {
+        return false;
+    }
+    name.contains(['\\', '/'])
+        || name.rsplit_once('.').is_some_and(|(stem, extension)| {
+            !stem.is_empty()
+                && (1..=5).contains(&extension.len())
+                && extension.chars().all(|c| c.is_ascii_alphanumeric())
+        })
+}
@@ -0,0 +1,28 @@
+// EventLogOnly Processing Mode for File and Folder Opening
+use anyhow::anyhow;
+use common::find_oalerts_evtx;
+use log::error;
+
+use crate::file_folder_opening::eventlogs::oalerts::evtx_oalerts_data;
+
+pub fn get_filefolderopening_eventlog_data(input: &str, outpath: &str) -> anyhow::Result<()> {
+    let mut found_something = false;
+
+    match find_oalerts_evtx(input) {
+        Ok(path) => {
+            found_something = true;
+            if let Err(err) = evtx_oalerts_data(&path, outpath) {
+                error!("Failed to get Office OAlerts: {err}")
+            }
+        }
+        Err(err) => {
+            error!("Could not find OAlerts.evtx: {err}");
+        }
+    }
+
+    if !found_something {
+        return Err(anyhow!("No .evtx found!"));
+    }
+
+    Ok(())
+}
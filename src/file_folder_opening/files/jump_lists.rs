@@ -0,0 +1,255 @@
+// "Windows 7+ task bar (Jump List) is engineered to allow users to 'jump' or access items they have frequently or recently used quickly and easily." - SANS Windows Forensic Analysis Poster, Jump Lists
+// %USERPROFILE%\AppData\Roaming\Microsoft\Windows\Recent\AutomaticDestinations\
+// %USERPROFILE%\AppData\Roaming\Microsoft\Windows\Recent\CustomDestinations\
+// The file name starts with the AppID of the application, which is looked up in lists/jumplist_appids.json
+
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::fs;
+use std::fs::{read_to_string, File};
+use std::io::{BufWriter, Read, Write};
+use std::path::Path;
+
+use anyhow::anyhow;
+use chrono::{DateTime, Utc};
+use common::{
+    convert_win_time_opt, find_user_profiles, read_ascii_string, read_u16_le, read_u32_le,
+    read_u64_le,
+};
+use log::error;
+use serde::Serialize;
+
+use crate::formats::lnk::{parse_lnk, ShellLink};
+
+const DEFAULT_APP_IDS: &str = include_str!("../../../lists/jumplist_appids.json");
+const JUMP_LIST_FOLDER: &str = "AppData/Roaming/Microsoft/Windows/Recent";
+// header size followed by the shell link CLSID
+const LNK_SIGNATURE: [u8; 20] = [
+    0x4C, 0x00, 0x00, 0x00, 0x01, 0x14, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0xC0, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x46,
+];
+
+#[derive(Debug, Serialize)]
+struct JumpListEntry {
+    user_profile: String,
+    jump_list_file: String,
+    jump_list_type: String,
+    app_id: String,
+    app_name: String,
+    entry_number: Option<u32>,
+    mru_position: Option<usize>,
+    access_count: Option<u32>,
+    last_accessed: Option<DateTime<Utc>>,
+    pinned: Option<bool>,
+    hostname: Option<String>,
+    dest_list_path: Option<String>,
+    #[serde(flatten)]
+    link: Option<ShellLink>,
+}
+
+/// one entry of the DestList stream
+struct DestListEntry {
+    entry_number: u32,
+    access_count: Option<u32>,
+    last_accessed: Option<DateTime<Utc>>,
+    pinned: bool,
+    hostname: Option<String>,
+    path: Option<String>,
+}
+
+pub fn get_jump_lists_data(input: &str, outpath: &str, appids: Option<&str>) -> anyhow::Result<()> {
+    print!("Working on Jump Lists: ");
+    let app_ids = load_app_ids(appids)?;
+    let mut jump_list_entries: Vec<JumpListEntry> = Vec::new();
+
+    for profile in find_user_profiles(input)? {
+        for (jump_list_type, extension) in [
+            ("AutomaticDestinations", "automaticDestinations-ms"),
+            ("CustomDestinations", "customDestinations-ms"),
+        ] {
+            let Ok(entries) =
+                fs::read_dir(format!("{profile}/{JUMP_LIST_FOLDER}/{jump_list_type}"))
+            else {
+                continue;
+            };
+            for entry in entries {
+                let path = entry?.path();
+                let is_jump_list = path
+                    .extension()
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case(extension));
+                if !is_jump_list || !path.is_file() {
+                    continue;
+                }
+                let result = if jump_list_type == "AutomaticDestinations" {
+                    parse_automatic_destinations(&path)
+                } else {
+                    parse_custom_destinations(&path)
+                };
+                let parsed = match result {
+                    Ok(parsed) => parsed,
+                    Err(err) => {
+                        error!("Failed to parse {}: {err}", path.display());
+                        continue;
+                    }
+                };
+                let app_id = path
+                    .file_stem()
+                    .map(|s| s.to_string_lossy().to_lowercase())
+                    .unwrap_or_default();
+                let app_name = app_ids
+                    .get(&app_id)
+                    .cloned()
+                    .unwrap_or("Unknown".to_string());
+                for (mru_position, dest_list, link) in parsed {
+                    jump_list_entries.push(JumpListEntry {
+                        user_profile: profile.clone(),
+                        jump_list_file: path.to_string_lossy().to_string(),
+                        jump_list_type: jump_list_type.to_string(),
+                        app_id: app_id.clone(),
+                        app_name: app_name.clone(),
+                        entry_number: dest_list.as_ref().map(|d| d.entry_number),
+                        mru_position,
+                        access_count: dest_list.as_ref().and_then(|d| d.access_count),
+                        last_accessed: dest_list.as_ref().and_then(|d| d.last_accessed),
+                        pinned: dest_list.as_ref().map(|d| d.pinned),
+                        hostname: dest_list.as_ref().and_then(|d| d.hostname.clone()),
+                        dest_list_path: dest_list.and_then(|d| d.path),
+                        link,
+                    });
+                }
+            }
+        }
+    }
+
+    if jump_list_entries.is_empty() {
+        println!("Nothing to do here, continuing with next job.");
+        return Ok(());
+    }
+
+    let file = File::create(format!("{outpath}/jl_jump_lists.json"))?;
+    let mut writer = BufWriter::new(file);
+    serde_json::to_writer(&mut writer, &jump_list_entries)?;
+    writer.flush()?;
+
+    println!("Done here!");
+    Ok(())
+}
+
+/// loads the AppID list, the built-in one is used if no path is given
+fn load_app_ids(appids: Option<&str>) -> anyhow::Result<HashMap<String, String>> {
+    let data = match appids {
+        Some(path) => read_to_string(path)?,
+        None => DEFAULT_APP_IDS.to_string(),
+    };
+    let app_ids: HashMap<String, String> = serde_json::from_str(&data)?;
+    Ok(app_ids
+        .into_iter()
+        .map(|(id, name)| (id.to_lowercase(), name))
+        .collect())
+}
+
+type ParsedJumpList = Vec<(Option<usize>, Option<DestListEntry>, Option<ShellLink>)>;
+
+/// AutomaticDestinations are OLE compound files with a DestList stream and one LNK stream per entry
+fn parse_automatic_destinations(path: &Path) -> anyhow::Result<ParsedJumpList> {
+    let mut compound_file = cfb::open(path)?;
+    let mut dest_list = Vec::new();
+    compound_file
+        .open_stream("/DestList")?
+        .read_to_end(&mut dest_list)?;
+
+    let mut entries = parse_dest_list(&dest_list)?;
+    // MRU position 0 is the most recently used entry
+    entries.sort_by_key(|e| Reverse(e.last_accessed));
+
+    let mut parsed = Vec::new();
+    for (mru_position, entry) in entries.into_iter().enumerate() {
+        let stream_name = format!("/{:x}", entry.entry_number);
+        let mut link_data = Vec::new();
+        let link = match compound_file.open_stream(&stream_name) {
+            Ok(mut stream) => match stream
+                .read_to_end(&mut link_data)
+                .map_err(anyhow::Error::from)
+                .and_then(|_| parse_lnk(&link_data))
+            {
+                Ok(link) => Some(link),
+                Err(err) => {
+                    error!(
+                        "Failed to parse stream {stream_name} in {}: {err}",
+                        path.display()
+                    );
+                    None
+                }
+            },
+            Err(_) => None,
+        };
+        parsed.push((Some(mru_position), Some(entry), link));
+    }
+    Ok(parsed)
+}
+
+fn parse_dest_list(data: &[u8]) -> anyhow::Result<Vec<DestListEntry>> {
+    let version = read_u32_le(data, 0).ok_or(anyhow!("DestList header is truncated!"))?;
+    let mut entries = Vec::new();
+    // header is 32 bytes
+    let mut offset = 32;
+    while offset < data.len() {
+        let entry = &data[offset..];
+        let (path_size_offset, access_count) = if version >= 3 {
+            (128, read_u32_le(entry, 116))
+        } else {
+            (112, None)
+        };
+        let Some(path_size) = read_u16_le(entry, path_size_offset) else {
+            break;
+        };
+        let path_size = path_size as usize * 2;
+        let path = entry
+            .get(path_size_offset + 2..path_size_offset + 2 + path_size)
+            .map(|raw| {
+                let chars: Vec<u16> = raw
+                    .chunks_exact(2)
+                    .map(|c| u16::from_le_bytes([c[0], c[1]]))
+                    .collect();
+                String::from_utf16_lossy(&chars)
+            });
+        entries.push(DestListEntry {
+            entry_number: read_u32_le(entry, 88).ok_or(anyhow!("DestList entry is truncated!"))?,
+            access_count,
+            last_accessed: read_u64_le(entry, 100).and_then(convert_win_time_opt),
+            // -1 means not pinned, everything else is the pin position
+            pinned: read_u32_le(entry, 108).is_some_and(|p| p != u32::MAX),
+            hostname: entry.get(72..88).and_then(|h| read_ascii_string(h, 0)),
+            path,
+        });
+        offset += path_size_offset + 2 + path_size;
+        // Windows 10 entries have 4 more bytes after the path
+        if version >= 3 {
+            offset += 4;
+        }
+    }
+    Ok(entries)
+}
+
+/// CustomDestinations are concatenated LNK files grouped in categories, they are split at each LNK header
+fn parse_custom_destinations(path: &Path) -> anyhow::Result<ParsedJumpList> {
+    let data = fs::read(path)?;
+    let starts: Vec<usize> = data
+        .windows(LNK_SIGNATURE.len())
+        .enumerate()
+        .filter(|(_, w)| *w == LNK_SIGNATURE)
+        .map(|(i, _)| i)
+        .collect();
+    let mut parsed = Vec::new();
+    for (i, start) in starts.iter().enumerate() {
+        let end = starts.get(i + 1).copied().unwrap_or(data.len());
+        match parse_lnk(&data[*start..end]) {
+            Ok(link) => parsed.push((None, None, Some(link))),
+            Err(err) => error!(
+                "Failed to parse LNK at {start} in {}: {err}",
+                path.display()
+            ),
+        }
+    }
+    Ok(parsed)
+}
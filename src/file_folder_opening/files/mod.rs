@@ -0,0 +1,2 @@
+pub mod jump_lists;
+pub mod shortcut_files;
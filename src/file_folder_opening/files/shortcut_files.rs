@@ -0,0 +1,82 @@
+// "Windows automatically creates shortcut files, tracking files and folders opened by a user" - SANS Windows Forensic Analysis Poster, Shortcut (LNK) Files
+// %USERPROFILE%\AppData\Roaming\Microsoft\Windows\Recent\
+// %USERPROFILE%\Desktop\
+// %USERPROFILE%\AppData\Roaming\Microsoft\Office\Recent\
+// Note: XP profiles keep the Recent folder directly under %USERPROFILE%
+
+use std::fs;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+use common::find_user_profiles;
+use log::error;
+use serde::Serialize;
+
+use crate::formats::lnk::{parse_lnk, ShellLink};
+
+const LNK_LOCATIONS: [(&str, &str); 4] = [
+    ("Recent", "AppData/Roaming/Microsoft/Windows/Recent"),
+    ("Office Recent", "AppData/Roaming/Microsoft/Office/Recent"),
+    ("Desktop", "Desktop"),
+    ("Recent (XP)", "Recent"),
+];
+
+#[derive(Debug, Serialize)]
+struct ShortcutFileEntry {
+    user_profile: String,
+    location: String,
+    lnk_file: String,
+    #[serde(flatten)]
+    link: ShellLink,
+}
+
+pub fn get_shortcut_files_data(input: &str, outpath: &str) -> anyhow::Result<()> {
+    print!("Working on Shortcut Files: ");
+    let mut shortcut_list: Vec<ShortcutFileEntry> = Vec::new();
+
+    for profile in find_user_profiles(input)? {
+        for (location, folder) in LNK_LOCATIONS {
+            let Ok(entries) = fs::read_dir(format!("{profile}/{folder}")) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let path = entry.path();
+                let is_lnk = path
+                    .extension()
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("lnk"));
+                if !is_lnk || !path.is_file() {
+                    continue;
+                }
+                let data = match fs::read(&path) {
+                    Ok(data) => data,
+                    Err(err) => {
+                        error!("Failed to read {}: {err}", path.display());
+                        continue;
+                    }
+                };
+                match parse_lnk(&data) {
+                    Ok(link) => shortcut_list.push(ShortcutFileEntry {
+                        user_profile: profile.clone(),
+                        location: location.to_string(),
+                        lnk_file: path.to_string_lossy().to_string(),
+                        link,
+                    }),
+                    Err(err) => error!("Failed to parse {}: {err}", path.display()),
+                }
+            }
+        }
+    }
+
+    if shortcut_list.is_empty() {
+        println!("Nothing to do here, continuing with next job.");
+        return Ok(());
+    }
+
+    let file = File::create(format!("{outpath}/lnk_shortcut_files.json"))?;
+    let mut writer = BufWriter::new(file);
+    serde_json::to_writer(&mut writer, &shortcut_list)?;
+    writer.flush()?;
+
+    println!("Done here!");
+    Ok(())
+}
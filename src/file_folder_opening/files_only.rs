@@ -0,0 +1,19 @@
+// FilesOnly Processing Mode for File and Folder Opening
+use log::error;
+
+use crate::file_folder_opening::files::jump_lists::get_jump_lists_data;
+use crate::file_folder_opening::files::shortcut_files::get_shortcut_files_data;
+
+pub fn get_filefolderopening_file_data(
+    input: &str,
+    outpath: &str,
+    appids: Option<&str>,
+) -> anyhow::Result<()> {
+    if let Err(err) = get_shortcut_files_data(input, outpath) {
+        error!("Failed to get Shortcut Files: {err}")
+    }
+    if let Err(err) = get_jump_lists_data(input, outpath, appids) {
+        error!("Failed to get Jump Lists: {err}")
+    }
+    Ok(())
+}
@@ -0,0 +1,6 @@
+pub mod eventlogs;
+pub mod eventlogs_only;
+pub mod files;
+pub mod files_only;
+pub mod registry;
+pub mod registry_only;
@@ -0,0 +1,189 @@
+// NTUSER.DAT
+// "Tracks files that have been opened or saved within a Windows shell dialog box." - SANS Windows Forensic Analysis Poster, Open/Save MRU
+// "Tracks the specific executable used by an application to open the files documented in the OpenSaveMRU key." - SANS Windows Forensic Analysis Poster, Last Visited MRU
+// NTUSER.DAT\Software\Microsoft\Windows\CurrentVersion\Explorer\ComDlg32\OpenSavePidlMRU (Vista+, PIDLs)
+// NTUSER.DAT\Software\Microsoft\Windows\CurrentVersion\Explorer\ComDlg32\LastVisitedPidlMRU (Vista+, executable name followed by a PIDL)
+// NTUSER.DAT\Software\Microsoft\Windows\CurrentVersion\Explorer\ComDlg32\OpenSaveMRU (XP, paths)
+// NTUSER.DAT\Software\Microsoft\Windows\CurrentVersion\Explorer\ComDlg32\LastVisitedMRU (XP, executable name followed by a path)
+
+use std::fs::File;
+use std::io::{BufWriter, Read, Write};
+
+use anyhow::anyhow;
+use chrono::{DateTime, Utc};
+use common::read_utf16_string;
+use log::error;
+use nt_hive::Hive;
+use serde::Serialize;
+
+use crate::account_usage::registry::user_accounts::UserHive;
+use crate::formats::mru::{mru_position, read_mru_values};
+use crate::formats::shell_items::{id_list_path, parse_id_list};
+
+const COMDLG32: &str = "Software\\Microsoft\\Windows\\CurrentVersion\\Explorer\\ComDlg32";
+
+#[derive(Debug, Serialize)]
+struct OpenSaveMruEntry {
+    sid: String,
+    profile_image_path: String,
+    mru_key: String,
+    extension: String,
+    value_name: String,
+    mru_position: Option<usize>,
+    path: String,
+    // only known for the most recently used entry of a key
+    opened: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+struct LastVisitedMruEntry {
+    sid: String,
+    profile_image_path: String,
+    mru_key: String,
+    value_name: String,
+    mru_position: Option<usize>,
+    executable: String,
+    path: String,
+    // only known for the most recently used entry
+    opened: Option<DateTime<Utc>>,
+}
+
+pub fn get_opensave_mru_data(user_hives: &[UserHive], outpath: &str) -> anyhow::Result<()> {
+    print!("Working on Open/Save MRU: ");
+    let mut opensave_list: Vec<OpenSaveMruEntry> = Vec::new();
+
+    for user_hive in user_hives {
+        if let Err(err) = get_user_opensave_mru(user_hive, &mut opensave_list) {
+            error!("Failed to get Open/Save MRU of {}: {err}", user_hive.sid)
+        }
+    }
+
+    if opensave_list.is_empty() {
+        println!("Nothing to do here, continuing with next job.");
+        return Ok(());
+    }
+
+    let file = File::create(format!("{outpath}/reg_opensave_mru.json"))?;
+    let mut writer = BufWriter::new(file);
+    serde_json::to_writer(&mut writer, &opensave_list)?;
+    writer.flush()?;
+
+    println!("Done here!");
+    Ok(())
+}
+
+pub fn get_lastvisited_mru_data(user_hives: &[UserHive], outpath: &str) -> anyhow::Result<()> {
+    print!("Working on Last Visited MRU: ");
+    let mut lastvisited_list: Vec<LastVisitedMruEntry> = Vec::new();
+
+    for user_hive in user_hives {
+        if let Err(err) = get_user_lastvisited_mru(user_hive, &mut lastvisited_list) {
+            error!("Failed to get Last Visited MRU of {}: {err}", user_hive.sid)
+        }
+    }
+
+    if lastvisited_list.is_empty() {
+        println!("Nothing to do here, continuing with next job.");
+        return Ok(());
+    }
+
+    let file = File::create(format!("{outpath}/reg_lastvisited_mru.json"))?;
+    let mut writer = BufWriter::new(file);
+    serde_json::to_writer(&mut writer, &lastvisited_list)?;
+    writer.flush()?;
+
+    println!("Done here!");
+    Ok(())
+}
+
+fn get_user_opensave_mru(
+    user_hive: &UserHive,
+    opensave_list: &mut Vec<OpenSaveMruEntry>,
+) -> anyhow::Result<()> {
+    let mut buffer = Vec::new();
+    File::open(&user_hive.hive_path)?.read_to_end(&mut buffer)?;
+
+    let hive = Hive::without_validation(buffer.as_ref())?;
+    let root_key_node = hive.root_key_node()?;
+    let comdlg32_key = root_key_node
+        .subpath(COMDLG32)
+        .ok_or(anyhow!("Key '{COMDLG32}' can not be found!"))??;
+
+    for mru_key in ["OpenSavePidlMRU", "OpenSaveMRU"] {
+        let Some(sub_key_node) = comdlg32_key.subkey(mru_key) else {
+            continue;
+        };
+        let sub_key_node = sub_key_node?;
+        let sub_key_nodes = sub_key_node
+            .subkeys()
+            .ok_or(anyhow!("Subkeys can not be unwrapped!"))??;
+        // one subkey per file extension, "*" contains all of them
+        for extension_key in sub_key_nodes {
+            let extension_key = extension_key?;
+            let extension = extension_key.name()?.to_string();
+            let mru_values = read_mru_values(&extension_key)?;
+            for (value_name, data) in &mru_values.values {
+                let path = if mru_key == "OpenSavePidlMRU" {
+                    id_list_path(&parse_id_list(data))
+                } else {
+                    read_utf16_string(data, 0).unwrap_or_default()
+                };
+                let position = mru_position(&mru_values.order, value_name);
+                opensave_list.push(OpenSaveMruEntry {
+                    sid: user_hive.sid.clone(),
+                    profile_image_path: user_hive.profile_image_path.clone(),
+                    mru_key: mru_key.to_string(),
+                    extension: extension.clone(),
+                    value_name: value_name.clone(),
+                    mru_position: position,
+                    path,
+                    opened: (position == Some(0)).then_some(mru_values.timestamp),
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+fn get_user_lastvisited_mru(
+    user_hive: &UserHive,
+    lastvisited_list: &mut Vec<LastVisitedMruEntry>,
+) -> anyhow::Result<()> {
+    let mut buffer = Vec::new();
+    File::open(&user_hive.hive_path)?.read_to_end(&mut buffer)?;
+
+    let hive = Hive::without_validation(buffer.as_ref())?;
+    let root_key_node = hive.root_key_node()?;
+    let comdlg32_key = root_key_node
+        .subpath(COMDLG32)
+        .ok_or(anyhow!("Key '{COMDLG32}' can not be found!"))??;
+
+    for mru_key in ["LastVisitedPidlMRU", "LastVisitedMRU"] {
+        let Some(sub_key_node) = comdlg32_key.subkey(mru_key) else {
+            continue;
+        };
+        let mru_values = read_mru_values(&sub_key_node?)?;
+        for (value_name, data) in &mru_values.values {
+            // the executable name is followed by a PIDL (Vista+) or a path (XP)
+            let executable = read_utf16_string(data, 0).unwrap_or_default();
+            let path_offset = (executable.encode_utf16().count() + 1) * 2;
+            let path = match data.get(path_offset..) {
+                Some(rest) if mru_key == "LastVisitedPidlMRU" => id_list_path(&parse_id_list(rest)),
+                Some(rest) => read_utf16_string(rest, 0).unwrap_or_default(),
+                None => String::new(),
+            };
+            let position = mru_position(&mru_values.order, value_name);
+            lastvisited_list.push(LastVisitedMruEntry {
+                sid: user_hive.sid.clone(),
+                profile_image_path: user_hive.profile_image_path.clone(),
+                mru_key: mru_key.to_string(),
+                value_name: value_name.clone(),
+                mru_position: position,
+                executable,
+                path,
+                opened: (position == Some(0)).then_some(mru_values.timestamp),
+            });
+        }
+    }
+    Ok(())
+}
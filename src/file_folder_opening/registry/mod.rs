@@ -0,0 +1,4 @@
+pub mod comdlg32;
+pub mod office;
+pub mod recent_docs;
+pub mod shellbags;
@@ -0,0 +1,347 @@
+// NTUSER.DAT
+// "MS Office programs track their own recent files list, to make it easier for users to access previously opened files." - SANS Windows Forensic Analysis Poster, Office Recent Files
+// "Records trust relationships afforded to documents by a user when presented with a security warning." - SANS Windows Forensic Analysis Poster, Office Trust Records
+// "Beginning with Microsoft Office 2013, the last known position within a Word document is recorded when the document is closed." - SANS Windows Forensic Analysis Poster, MS Word Reading Locations
+// NTUSER.DAT\Software\Microsoft\Office\<Version>\<App>\File MRU and Place MRU
+// NTUSER.DAT\Software\Microsoft\Office\<Version>\<App>\User MRU\<LiveId_*|AD_*>\File MRU and Place MRU
+// NTUSER.DAT\Software\Microsoft\Office\<Version>\<App>\Security\Trusted Documents\TrustRecords
+// NTUSER.DAT\Software\Microsoft\Office\<Version>\Word\Reading Locations
+
+use std::fs::File;
+use std::io::{BufWriter, Read, Write};
+
+use anyhow::anyhow;
+use chrono::{DateTime, Utc};
+use common::{convert_win_time, convert_win_time_opt, read_u64_le};
+use log::error;
+use nt_hive::{Hive, KeyNode};
+use serde::Serialize;
+
+use crate::account_usage::registry::user_accounts::UserHive;
+
+const MRU_TYPES: [&str; 2] = ["File MRU", "Place MRU"];
+// last 4 bytes of a trust record if the user enabled macros (and not only editing)
+const MACROS_ENABLED: [u8; 4] = [0xFF, 0xFF, 0xFF, 0x7F];
+
+#[derive(Debug, Serialize)]
+struct OfficeMruEntry {
+    sid: String,
+    profile_image_path: String,
+    version: String,
+    application: String,
+    mru_type: String,
+    // LiveId_* (Microsoft account) or AD_* (Azure AD account) if the list belongs to a signed in account
+    account: Option<String>,
+    value_name: String,
+    path: String,
+    last_opened: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+struct TrustRecordEntry {
+    sid: String,
+    profile_image_path: String,
+    version: String,
+    application: String,
+    path: String,
+    trusted: Option<DateTime<Utc>>,
+    macros_enabled: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct ReadingLocationEntry {
+    sid: String,
+    profile_image_path: String,
+    version: String,
+    document_key: String,
+    path: String,
+    datetime: Option<String>,
+    position: Option<String>,
+    last_closed: DateTime<Utc>,
+}
+
+pub fn get_office_mru_data(user_hives: &[UserHive], outpath: &str) -> anyhow::Result<()> {
+    print!("Working on Office Recent Files: ");
+    let mut office_mru_list: Vec<OfficeMruEntry> = Vec::new();
+
+    for user_hive in user_hives {
+        let result = for_each_office_app(user_hive, |version, application, app_key| {
+            get_app_mru(
+                user_hive,
+                version,
+                application,
+                app_key,
+                &mut office_mru_list,
+            )
+        });
+        if let Err(err) = result {
+            error!(
+                "Failed to get Office Recent Files of {}: {err}",
+                user_hive.sid
+            )
+        }
+    }
+
+    if office_mru_list.is_empty() {
+        println!("Nothing to do here, continuing with next job.");
+        return Ok(());
+    }
+
+    let file = File::create(format!("{outpath}/reg_office_mru.json"))?;
+    let mut writer = BufWriter::new(file);
+    serde_json::to_writer(&mut writer, &office_mru_list)?;
+    writer.flush()?;
+
+    println!("Done here!");
+    Ok(())
+}
+
+pub fn get_office_trustrecords_data(user_hives: &[UserHive], outpath: &str) -> anyhow::Result<()> {
+    print!("Working on Office Trust Records: ");
+    let mut trustrecords_list: Vec<TrustRecordEntry> = Vec::new();
+
+    for user_hive in user_hives {
+        let result = for_each_office_app(user_hive, |version, application, app_key| {
+            get_app_trustrecords(
+                user_hive,
+                version,
+                application,
+                app_key,
+                &mut trustrecords_list,
+            )
+        });
+        if let Err(err) = result {
+            error!(
+                "Failed to get Office Trust Records of {}: {err}",
+                user_hive.sid
+            )
+        }
+    }
+
+    if trustrecords_list.is_empty() {
+        println!("Nothing to do here, continuing with next job.");
+        return Ok(());
+    }
+
+    let file = File::create(format!("{outpath}/reg_office_trustrecords.json"))?;
+    let mut writer = BufWriter::new(file);
+    serde_json::to_writer(&mut writer, &trustrecords_list)?;
+    writer.flush()?;
+
+    println!("Done here!");
+    Ok(())
+}
+
+pub fn get_word_reading_locations_data(
+    user_hives: &[UserHive],
+    outpath: &str,
+) -> anyhow::Result<()> {
+    print!("Working on Word Reading Locations: ");
+    let mut reading_locations_list: Vec<ReadingLocationEntry> = Vec::new();
+
+    for user_hive in user_hives {
+        let result = for_each_office_app(user_hive, |version, application, app_key| {
+            if application != "Word" {
+                return Ok(());
+            }
+            get_reading_locations(user_hive, version, app_key, &mut reading_locations_list)
+        });
+        if let Err(err) = result {
+            error!(
+                "Failed to get Word Reading Locations of {}: {err}",
+                user_hive.sid
+            )
+        }
+    }
+
+    if reading_locations_list.is_empty() {
+        println!("Nothing to do here, continuing with next job.");
+        return Ok(());
+    }
+
+    let file = File::create(format!("{outpath}/reg_word_reading_locations.json"))?;
+    let mut writer = BufWriter::new(file);
+    serde_json::to_writer(&mut writer, &reading_locations_list)?;
+    writer.flush()?;
+
+    println!("Done here!");
+    Ok(())
+}
+
+/// calls `f` for every application key of every installed Office version (e.g. "16.0", "Word")
+fn for_each_office_app<F>(user_hive: &UserHive, mut f: F) -> anyhow::Result<()>
+where
+    F: FnMut(&str, &str, &KeyNode<&[u8]>) -> anyhow::Result<()>,
+{
+    let mut buffer = Vec::new();
+    File::open(&user_hive.hive_path)?.read_to_end(&mut buffer)?;
+
+    let hive = Hive::without_validation(buffer.as_ref())?;
+    let root_key_node = hive.root_key_node()?;
+    let office_key = root_key_node
+        .subpath("Software\\Microsoft\\Office")
+        .ok_or(anyhow!(
+            "Key 'Software\\Microsoft\\Office' can not be found!"
+        ))??;
+
+    let version_keys = office_key
+        .subkeys()
+        .ok_or(anyhow!("Subkeys can not be unwrapped!"))??;
+    for version_key in version_keys {
+        let version_key = version_key?;
+        let version = version_key.name()?.to_string();
+        // only version keys like "16.0", not "Common" or "ClickToRun"
+        if version.parse::<f32>().is_err() {
+            continue;
+        }
+        let Some(app_keys) = version_key.subkeys() else {
+            continue;
+        };
+        for app_key in app_keys? {
+            let app_key = app_key?;
+            let application = app_key.name()?.to_string();
+            f(&version, &application, &app_key)?;
+        }
+    }
+    Ok(())
+}
+
+fn get_app_mru(
+    user_hive: &UserHive,
+    version: &str,
+    application: &str,
+    app_key: &KeyNode<&[u8]>,
+    office_mru_list: &mut Vec<OfficeMruEntry>,
+) -> anyhow::Result<()> {
+    // lists without an account
+    let mut mru_keys = Vec::new();
+    for mru_type in MRU_TYPES {
+        if let Some(mru_key) = app_key.subkey(mru_type) {
+            mru_keys.push((mru_type, None, mru_key?));
+        }
+    }
+    // lists of signed in accounts
+    if let Some(user_mru_key) = app_key.subkey("User MRU") {
+        if let Some(account_keys) = user_mru_key?.subkeys() {
+            for account_key in account_keys? {
+                let account_key = account_key?;
+                let account = account_key.name()?.to_string();
+                for mru_type in MRU_TYPES {
+                    if let Some(mru_key) = account_key.subkey(mru_type) {
+                        mru_keys.push((mru_type, Some(account.clone()), mru_key?));
+                    }
+                }
+            }
+        }
+    }
+
+    for (mru_type, account, mru_key) in mru_keys {
+        let Some(values) = mru_key.values() else {
+            continue;
+        };
+        for value in values? {
+            let value = value?;
+            let value_name = value.name()?.to_string();
+            // "Item 1", "Item 2", ... ("Item Metadata 1" contains no path)
+            if !value_name.starts_with("Item ") || value_name.starts_with("Item Metadata") {
+                continue;
+            }
+            let item = value.string_data()?;
+            office_mru_list.push(OfficeMruEntry {
+                sid: user_hive.sid.clone(),
+                profile_image_path: user_hive.profile_image_path.clone(),
+                version: version.to_string(),
+                application: application.to_string(),
+                mru_type: mru_type.to_string(),
+                account: account.clone(),
+                value_name,
+                path: mru_item_path(&item),
+                last_opened: mru_item_timestamp(&item),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// items look like "[F00000000][T01D9B4E3F0A1B2C3][O00000000]*C:\Users\ally\Documents\file.docx"
+fn mru_item_path(item: &str) -> String {
+    item.split_once('*')
+        .map(|(_, path)| path)
+        .unwrap_or(item)
+        .to_string()
+}
+
+/// the [T...] part contains the last opened time as hex FILETIME
+fn mru_item_timestamp(item: &str) -> Option<DateTime<Utc>> {
+    let start = item.find("[T")? + 2;
+    let hex = item.get(start..start + 16)?;
+    u64::from_str_radix(hex, 16)
+        .ok()
+        .and_then(convert_win_time_opt)
+}
+
+fn get_app_trustrecords(
+    user_hive: &UserHive,
+    version: &str,
+    application: &str,
+    app_key: &KeyNode<&[u8]>,
+    trustrecords_list: &mut Vec<TrustRecordEntry>,
+) -> anyhow::Result<()> {
+    let Some(trust_records_key) = app_key.subpath("Security\\Trusted Documents\\TrustRecords")
+    else {
+        return Ok(());
+    };
+    let Some(values) = trust_records_key?.values() else {
+        return Ok(());
+    };
+    for value in values? {
+        let value = value?;
+        let data = value.data()?.into_vec()?;
+        // FILETIME when the document was trusted, the last 4 bytes contain the kind of trust
+        trustrecords_list.push(TrustRecordEntry {
+            sid: user_hive.sid.clone(),
+            profile_image_path: user_hive.profile_image_path.clone(),
+            version: version.to_string(),
+            application: application.to_string(),
+            path: value.name()?.to_string(),
+            trusted: read_u64_le(&data, 0).and_then(convert_win_time_opt),
+            macros_enabled: data.ends_with(&MACROS_ENABLED),
+        });
+    }
+    Ok(())
+}
+
+fn get_reading_locations(
+    user_hive: &UserHive,
+    version: &str,
+    word_key: &KeyNode<&[u8]>,
+    reading_locations_list: &mut Vec<ReadingLocationEntry>,
+) -> anyhow::Result<()> {
+    let Some(reading_locations_key) = word_key.subkey("Reading Locations") else {
+        return Ok(());
+    };
+    let Some(document_keys) = reading_locations_key?.subkeys() else {
+        return Ok(());
+    };
+    for document_key in document_keys? {
+        let document_key = document_key?;
+        let string_value = |name: &str| {
+            document_key
+                .value(name)
+                .and_then(|v| v.ok())
+                .and_then(|v| v.string_data().ok())
+        };
+        reading_locations_list.push(ReadingLocationEntry {
+            sid: user_hive.sid.clone(),
+            profile_image_path: user_hive.profile_image_path.clone(),
+            version: version.to_string(),
+            document_key: document_key.name()?.to_string(),
+            path: string_value("File Path").unwrap_or_default(),
+            datetime: string_value("Datetime"),
+            position: string_value("Position"),
+            // the key is written when the document is closed
+            last_closed: convert_win_time(document_key.header().timestamp.get()),
+        });
+    }
+    Ok(())
+}
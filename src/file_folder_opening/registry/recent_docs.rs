@@ -0,0 +1,121 @@
+// NTUSER.DAT
+// "Tracks last files and folders opened and is used to populate data in 'Recent' menus of the Start menu." - SANS Windows Forensic Analysis Poster, Recent Files
+// NTUSER.DAT\Software\Microsoft\Windows\CurrentVersion\Explorer\RecentDocs
+// one subkey per file extension, every value contains the file name (UTF-16) followed by a shell item of the LNK file
+
+use std::fs::File;
+use std::io::{BufWriter, Read, Write};
+
+use anyhow::anyhow;
+use chrono::{DateTime, Utc};
+use common::{convert_win_time, read_utf16_string};
+use log::error;
+use nt_hive::{Hive, KeyNode};
+use serde::Serialize;
+
+use crate::account_usage::registry::user_accounts::UserHive;
+use crate::formats::mru::{mru_position, parse_mru_list_ex};
+
+#[derive(Debug, Serialize)]
+struct RecentDocsEntry {
+    sid: String,
+    profile_image_path: String,
+    extension: String,
+    value_name: String,
+    mru_position: Option<usize>,
+    name: String,
+    // only known for the most recently used entry of a key
+    opened: Option<DateTime<Utc>>,
+}
+
+pub fn get_recentdocs_data(user_hives: &[UserHive], outpath: &str) -> anyhow::Result<()> {
+    print!("Working on RecentDocs: ");
+    let mut recentdocs_list: Vec<RecentDocsEntry> = Vec::new();
+
+    for user_hive in user_hives {
+        if let Err(err) = get_user_recentdocs(user_hive, &mut recentdocs_list) {
+            error!("Failed to get RecentDocs of {}: {err}", user_hive.sid)
+        }
+    }
+
+    if recentdocs_list.is_empty() {
+        println!("Nothing to do here, continuing with next job.");
+        return Ok(());
+    }
+
+    let file = File::create(format!("{outpath}/reg_recentdocs.json"))?;
+    let mut writer = BufWriter::new(file);
+    serde_json::to_writer(&mut writer, &recentdocs_list)?;
+    writer.flush()?;
+
+    println!("Done here!");
+    Ok(())
+}
+
+fn get_user_recentdocs(
+    user_hive: &UserHive,
+    recentdocs_list: &mut Vec<RecentDocsEntry>,
+) -> anyhow::Result<()> {
+    let mut buffer = Vec::new();
+    File::open(&user_hive.hive_path)?.read_to_end(&mut buffer)?;
+
+    let hive = Hive::without_validation(buffer.as_ref())?;
+    let root_key_node = hive.root_key_node()?;
+    let sub_key_node = root_key_node
+        .subpath("Software\\Microsoft\\Windows\\CurrentVersion\\Explorer\\RecentDocs")
+        .ok_or(anyhow!(
+            "Key 'Software\\Microsoft\\Windows\\CurrentVersion\\Explorer\\RecentDocs' can not be found!"
+        ))??;
+
+    // all extensions together
+    read_recentdocs_key(&sub_key_node, "", user_hive, recentdocs_list)?;
+
+    if let Some(sub_key_nodes) = sub_key_node.subkeys() {
+        for extension_key in sub_key_nodes? {
+            let extension_key = extension_key?;
+            let extension = extension_key.name()?.to_string();
+            read_recentdocs_key(&extension_key, &extension, user_hive, recentdocs_list)?;
+        }
+    }
+    Ok(())
+}
+
+fn read_recentdocs_key(
+    key: &KeyNode<&[u8]>,
+    extension: &str,
+    user_hive: &UserHive,
+    recentdocs_list: &mut Vec<RecentDocsEntry>,
+) -> anyhow::Result<()> {
+    let timestamp = convert_win_time(key.header().timestamp.get());
+    let Some(values) = key.values() else {
+        return Ok(());
+    };
+
+    let mut mru_order = Vec::new();
+    let mut entries = Vec::new();
+    for value in values? {
+        let value = value?;
+        let value_name = value.name()?.to_string();
+        if value_name == "MRUListEx" {
+            mru_order = parse_mru_list_ex(&value.data()?.into_vec()?);
+        } else if let Ok(number) = value_name.parse::<u32>() {
+            let data = value.data()?.into_vec()?;
+            let name = read_utf16_string(&data, 0).unwrap_or_default();
+            entries.push((number, value_name, name));
+        }
+    }
+
+    for (number, value_name, name) in entries {
+        let position = mru_position(&mru_order, &number);
+        recentdocs_list.push(RecentDocsEntry {
+            sid: user_hive.sid.clone(),
+            profile_image_path: user_hive.profile_image_path.clone(),
+            extension: extension.to_string(),
+            value_name,
+            mru_position: position,
+            name,
+            opened: (position == Some(0)).then_some(timestamp),
+        });
+    }
+    Ok(())
+}
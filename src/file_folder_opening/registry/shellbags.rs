@@ -0,0 +1,180 @@
+// NTUSER.DAT and UsrClass.dat
+// "Which folders were accessed on the local machine, the network, and/or removable devices. Evidence of previously existing folders after deletion/overwrite. When certain folders were accessed." - SANS Windows Forensic Analysis Poster, Shell Bags
+// USRCLASS.DAT\Local Settings\Software\Microsoft\Windows\Shell\BagMRU
+// NTUSER.DAT\Software\Microsoft\Windows\Shell\BagMRU
+// every numbered value is a shell item, the subkey with the same number contains its children
+
+use std::fs::File;
+use std::io::{BufWriter, Read, Write};
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use common::convert_win_time;
+use log::error;
+use nt_hive::{Hive, KeyNode};
+use serde::Serialize;
+
+use crate::account_usage::registry::user_accounts::UserHive;
+use crate::formats::mru::{mru_position, parse_mru_list_ex};
+use crate::formats::shell_items::parse_shell_item;
+
+const NTUSER_BAG_MRUS: [&str; 2] = [
+    "Software\\Microsoft\\Windows\\Shell\\BagMRU",
+    "Software\\Microsoft\\Windows\\ShellNoRoam\\BagMRU",
+];
+const USRCLASS_BAG_MRUS: [&str; 1] =
+    ["Local Settings\\Software\\Microsoft\\Windows\\Shell\\BagMRU"];
+
+#[derive(Debug, Serialize)]
+struct ShellbagEntry {
+    sid: String,
+    profile_image_path: String,
+    hive: String,
+    bag_mru_key: String,
+    path: String,
+    item_type: String,
+    mru_position: Option<usize>,
+    node_slot: Option<u32>,
+    key_last_write: Option<DateTime<Utc>>,
+    // only set for the most recently used item of a folder, as the parent key was written when it was accessed
+    last_interacted: Option<DateTime<Utc>>,
+    created: Option<NaiveDateTime>,
+    modified: Option<NaiveDateTime>,
+    accessed: Option<NaiveDateTime>,
+    mft_entry: Option<u64>,
+    mft_sequence: Option<u16>,
+}
+
+/// properties every entry of one hive shares
+struct HiveContext<'a> {
+    user_hive: &'a UserHive,
+    hive: String,
+}
+
+pub fn get_shellbags_data(user_hives: &[UserHive], outpath: &str) -> anyhow::Result<()> {
+    print!("Working on Shellbags: ");
+    let mut shellbag_list: Vec<ShellbagEntry> = Vec::new();
+
+    for user_hive in user_hives {
+        if let Err(err) = get_user_shellbags(user_hive, &mut shellbag_list) {
+            error!(
+                "Failed to get Shellbags of {} ({}): {err}",
+                user_hive.sid, user_hive.hive_path
+            )
+        }
+    }
+
+    if shellbag_list.is_empty() {
+        println!("Nothing to do here, continuing with next job.");
+        return Ok(());
+    }
+
+    let file = File::create(format!("{outpath}/reg_shellbags.json"))?;
+    let mut writer = BufWriter::new(file);
+    serde_json::to_writer(&mut writer, &shellbag_list)?;
+    writer.flush()?;
+
+    println!("Done here!");
+    Ok(())
+}
+
+fn get_user_shellbags(
+    user_hive: &UserHive,
+    shellbag_list: &mut Vec<ShellbagEntry>,
+) -> anyhow::Result<()> {
+    let mut buffer = Vec::new();
+    File::open(&user_hive.hive_path)?.read_to_end(&mut buffer)?;
+
+    let hive = Hive::without_validation(buffer.as_ref())?;
+    let root_key_node = hive.root_key_node()?;
+
+    let is_usrclass = user_hive.hive_path.to_lowercase().ends_with("usrclass.dat");
+    let (hive_name, bag_mrus) = if is_usrclass {
+        ("UsrClass.dat", USRCLASS_BAG_MRUS.as_slice())
+    } else {
+        ("NTUSER.DAT", NTUSER_BAG_MRUS.as_slice())
+    };
+    let context = HiveContext {
+        user_hive,
+        hive: hive_name.to_string(),
+    };
+
+    for bag_mru in bag_mrus {
+        if let Some(bag_mru_key) = root_key_node.subpath(bag_mru) {
+            walk_bag_mru(&bag_mru_key?, "BagMRU", "", &context, shellbag_list)?;
+        }
+    }
+    Ok(())
+}
+
+/// walks one BagMRU key and all of its children
+fn walk_bag_mru(
+    key: &KeyNode<&[u8]>,
+    key_path: &str,
+    parent_path: &str,
+    context: &HiveContext,
+    shellbag_list: &mut Vec<ShellbagEntry>,
+) -> anyhow::Result<()> {
+    let parent_last_write = convert_win_time(key.header().timestamp.get());
+    let Some(values) = key.values() else {
+        return Ok(());
+    };
+
+    let mut items = Vec::new();
+    let mut mru_order = Vec::new();
+    for value in values? {
+        let value = value?;
+        let name = value.name()?.to_string();
+        if name == "MRUListEx" {
+            mru_order = parse_mru_list_ex(&value.data()?.into_vec()?);
+        } else if let Ok(number) = name.parse::<u32>() {
+            items.push((number, value.data()?.into_vec()?));
+        }
+    }
+
+    for (number, data) in items {
+        let Some(item) = parse_shell_item(&data) else {
+            continue;
+        };
+        let path = if parent_path.is_empty() {
+            item.name.trim_end_matches('\\').to_string()
+        } else {
+            format!("{parent_path}\\{}", item.name.trim_end_matches('\\'))
+        };
+        let child_path = format!("{key_path}\\{number}");
+        let child_key = match key.subkey(&number.to_string()) {
+            Some(child_key) => Some(child_key?),
+            None => None,
+        };
+        let mru_position = mru_position(&mru_order, &number);
+        let node_slot = child_key
+            .as_ref()
+            .and_then(|k| k.value("NodeSlot"))
+            .and_then(|v| v.ok())
+            .and_then(|v| v.dword_data().ok());
+
+        shellbag_list.push(ShellbagEntry {
+            sid: context.user_hive.sid.clone(),
+            profile_image_path: context.user_hive.profile_image_path.clone(),
+            hive: context.hive.clone(),
+            bag_mru_key: child_path.clone(),
+            path: path.clone(),
+            item_type: item.item_type,
+            mru_position,
+            node_slot,
+            key_last_write: child_key
+                .as_ref()
+                .map(|k| convert_win_time(k.header().timestamp.get())),
+            last_interacted: (mru_position == Some(0)).then_some(parent_last_write),
+            created: item.created,
+            modified: item.modified,
+            accessed: item.accessed,
+            mft_entry: item.mft_entry,
+            mft_sequence: item.mft_sequence,
+        });
+
+        if let Some(child_key) = child_key {
+            walk_bag_mru(&child_key, &child_path, &path, context, shellbag_list)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,71 @@
+// RegistryOnly Processing Mode for File and Folder Opening
+use anyhow::anyhow;
+use common::find_software_hive;
+use log::error;
+
+use crate::account_usage::registry::user_accounts::{find_user_hives, USRCLASS_DAT};
+use crate::file_folder_opening::registry::comdlg32::{
+    get_lastvisited_mru_data, get_opensave_mru_data,
+};
+use crate::file_folder_opening::registry::office::{
+    get_office_mru_data, get_office_trustrecords_data, get_word_reading_locations_data,
+};
+use crate::file_folder_opening::registry::recent_docs::get_recentdocs_data;
+use crate::file_folder_opening::registry::shellbags::get_shellbags_data;
+
+pub fn get_filefolderopening_registry_data(input: &str, outpath: &str) -> anyhow::Result<()> {
+    let mut found_something = false;
+    // SOFTWARE hive (needed to find the user hives)
+    match find_software_hive(input) {
+        Ok(path) => {
+            let ntuser_hives = match find_user_hives(input, &path, "NTUSER.DAT") {
+                Ok(ntuser_hives) => ntuser_hives,
+                Err(err) => {
+                    error!("Could not find NTUSER.DAT hives: {err}");
+                    Vec::new()
+                }
+            };
+            let usrclass_hives = match find_user_hives(input, &path, USRCLASS_DAT) {
+                Ok(usrclass_hives) => usrclass_hives,
+                Err(err) => {
+                    error!("Could not find UsrClass.dat hives: {err}");
+                    Vec::new()
+                }
+            };
+            let user_hives = [ntuser_hives.as_slice(), usrclass_hives.as_slice()].concat();
+            if !user_hives.is_empty() {
+                found_something = true;
+                if let Err(err) = get_shellbags_data(&user_hives, outpath) {
+                    error!("Failed to get Shellbags: {err}")
+                }
+            }
+            if !ntuser_hives.is_empty() {
+                if let Err(err) = get_recentdocs_data(&ntuser_hives, outpath) {
+                    error!("Failed to get RecentDocs: {err}")
+                }
+                if let Err(err) = get_opensave_mru_data(&ntuser_hives, outpath) {
+                    error!("Failed to get Open/Save MRU: {err}")
+                }
+                if let Err(err) = get_lastvisited_mru_data(&ntuser_hives, outpath) {
+                    error!("Failed to get Last Visited MRU: {err}")
+                }
+                if let Err(err) = get_office_mru_data(&ntuser_hives, outpath) {
+                    error!("Failed to get Office Recent Files: {err}")
+                }
+                if let Err(err) = get_office_trustrecords_data(&ntuser_hives, outpath) {
+                    error!("Failed to get Office Trust Records: {err}")
+                }
+                if let Err(err) = get_word_reading_locations_data(&ntuser_hives, outpath) {
+                    error!("Failed to get Word Reading Locations: {err}")
+                }
+            }
+        }
+        Err(err) => {
+            error!("Could not find Software hive: {err}")
+        }
+    }
+    if !found_something {
+        return Err(anyhow!("No user hives found!"));
+    }
+    Ok(())
+}
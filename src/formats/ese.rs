@@ -0,0 +1,575 @@
+// Extensible Storage Engine (ESE) databases, read-only, as used by Windows.edb and SRUDB.dat
+// format reference: https://github.com/libyal/libesedb/blob/main/documentation/Extensible%20Storage%20Engine%20(ESE)%20Database%20File%20(EDB)%20format.asciidoc
+// only what is needed to read whole tables is implemented: catalog, B+-tree leaf pages, records, long values, 7-bit and LZXPRESS compression
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+
+use anyhow::anyhow;
+use common::{read_u16_le, read_u32_le};
+use log::error;
+
+use crate::formats::lzxpress;
+
+const SIGNATURE: u32 = 0x89AB_CDEF;
+// the catalog (MSysObjects) always starts at page 4
+const CATALOG_FDP: u32 = 4;
+const PAGE_FLAG_LEAF: u32 = 0x0002;
+const PAGE_FLAG_PARENT: u32 = 0x0004;
+const PAGE_FLAG_SPACE_TREE: u32 = 0x0020;
+const TAG_FLAG_DEFUNCT: u16 = 0x2;
+const TAG_FLAG_COMMON_KEY: u16 = 0x4;
+// catalog object types
+const CATALOG_TABLE: u16 = 1;
+const CATALOG_COLUMN: u16 = 2;
+const CATALOG_LONG_VALUE: u16 = 4;
+// tagged data flags
+const TAGGED_COMPRESSED: u8 = 0x02;
+const TAGGED_LONG_VALUE: u8 = 0x04;
+const TAGGED_MULTI_VALUE: u8 = 0x08;
+// compression types (upper 5 bits of the first byte of compressed data)
+const COMPRESSION_7BIT_ASCII: u8 = 1;
+const COMPRESSION_7BIT_UTF16: u8 = 2;
+const COMPRESSION_LZXPRESS: u8 = 3;
+// separator of the values of multi valued columns
+const MULTI_VALUE_SEPARATOR: &str = "; ";
+
+/// an opened ESE database
+pub struct EseDatabase {
+    file: File,
+    page_size: usize,
+}
+
+/// table definition from the catalog
+#[derive(Debug, Clone)]
+pub struct EseTable {
+    pub name: String,
+    fdp: u32,
+    long_value_fdp: Option<u32>,
+    pub columns: Vec<EseColumn>,
+}
+
+/// column definition from the catalog
+#[derive(Debug, Clone)]
+pub struct EseColumn {
+    pub id: u32,
+    pub name: String,
+    pub column_type: u32,
+    pub codepage: u32,
+}
+
+/// a single column value of a record
+#[derive(Debug, Clone)]
+pub struct EseValue {
+    pub column_type: u32,
+    pub codepage: u32,
+    pub data: Vec<u8>,
+}
+
+impl EseDatabase {
+    pub fn open(path: &str) -> anyhow::Result<EseDatabase> {
+        let mut file = File::open(path)?;
+        let mut header = vec![0u8; 240];
+        file.read_exact(&mut header)?;
+        if read_u32_le(&header, 4) != Some(SIGNATURE) {
+            return Err(anyhow!("{path} is no ESE database!"));
+        }
+        let page_size = read_u32_le(&header, 236).ok_or(anyhow!("Header is truncated!"))?;
+        if !matches!(page_size, 2048 | 4096 | 8192 | 16384 | 32768) {
+            return Err(anyhow!("Unsupported page size {page_size}!"));
+        }
+        Ok(EseDatabase {
+            file,
+            page_size: page_size as usize,
+        })
+    }
+
+    /// reads all table and column definitions of the catalog
+    pub fn tables(&mut self) -> anyhow::Result<Vec<EseTable>> {
+        let mut tables: BTreeMap<u32, EseTable> = BTreeMap::new();
+        let mut columns: Vec<(u32, EseColumn)> = Vec::new();
+        let mut long_values: Vec<(u32, u32)> = Vec::new();
+        let mut records = Vec::new();
+        self.walk_leaves(CATALOG_FDP, |_, data| records.push(data.to_vec()))?;
+        for record in &records {
+            // fixed columns: ObjidTable, Type, Id, ColtypOrPgnoFDP, SpaceUsage, Flags, PagesOrLocale
+            let (Some(table_id), Some(object_type), Some(id), Some(type_or_fdp)) = (
+                read_u32_le(record, 4),
+                read_u16_le(record, 8),
+                read_u32_le(record, 10),
+                read_u32_le(record, 14),
+            ) else {
+                continue;
+            };
+            let codepage = read_u32_le(record, 26).unwrap_or_default();
+            // the first variable column (128) is the name
+            let name = variable_column(record, 128)
+                .map(|name| String::from_utf8_lossy(name).to_string())
+                .unwrap_or_default();
+            match object_type {
+                CATALOG_TABLE => {
+                    tables.insert(
+                        table_id,
+                        EseTable {
+                            name,
+                            fdp: type_or_fdp,
+                            long_value_fdp: None,
+                            columns: Vec::new(),
+                        },
+                    );
+                }
+                CATALOG_COLUMN => columns.push((
+                    table_id,
+                    EseColumn {
+                        id,
+                        name,
+                        column_type: type_or_fdp,
+                        codepage,
+                    },
+                )),
+                CATALOG_LONG_VALUE => long_values.push((table_id, type_or_fdp)),
+                _ => {}
+            }
+        }
+        for (table_id, column) in columns {
+            if let Some(table) = tables.get_mut(&table_id) {
+                table.columns.push(column);
+            }
+        }
+        for (table_id, fdp) in long_values {
+            if let Some(table) = tables.get_mut(&table_id) {
+                table.long_value_fdp = Some(fdp);
+            }
+        }
+        let mut tables: Vec<EseTable> = tables.into_values().collect();
+        for table in &mut tables {
+            table.columns.sort_by_key(|c| c.id);
+        }
+        Ok(tables)
+    }
+
+    /// calls `f` with every record of the table (column name -> value)
+    pub fn for_each_record<F>(&mut self, table: &EseTable, mut f: F) -> anyhow::Result<()>
+    where
+        F: FnMut(&HashMap<String, EseValue>),
+    {
+        let mut long_values = BTreeMap::new();
+        if let Some(fdp) = table.long_value_fdp {
+            self.walk_leaves(fdp, |key, data| {
+                long_values.insert(key, data.to_vec());
+            })?;
+        }
+        let page_size = self.page_size;
+        self.walk_leaves(table.fdp, |_, data| {
+            f(&parse_record(data, table, &long_values, page_size))
+        })
+    }
+
+    fn read_page(&mut self, page_number: u32) -> anyhow::Result<Vec<u8>> {
+        let mut page = vec![0u8; self.page_size];
+        // the first two pages are the file header and its shadow copy
+        self.file.seek(SeekFrom::Start(
+            (page_number as u64 + 1) * self.page_size as u64,
+        ))?;
+        self.file.read_exact(&mut page)?;
+        Ok(page)
+    }
+
+    /// calls `f` with the full key and the data of every leaf entry of the B+-tree starting at root, in key order
+    fn walk_leaves<F>(&mut self, root: u32, mut f: F) -> anyhow::Result<()>
+    where
+        F: FnMut(Vec<u8>, &[u8]),
+    {
+        let mut pages = vec![root];
+        let mut visited = 0;
+        while let Some(page_number) = pages.pop() {
+            // protects against loops in corrupted databases
+            visited += 1;
+            if visited > 10_000_000 {
+                return Err(anyhow!("Too many pages, database seems to be corrupted!"));
+            }
+            let page = self.read_page(page_number)?;
+            let flags = read_u32_le(&page, 36).unwrap_or_default();
+            if flags & PAGE_FLAG_SPACE_TREE != 0 {
+                continue;
+            }
+            let tags = self.page_tags(&page);
+            let prefix = tags
+                .first()
+                .map(|(value, _)| value.clone())
+                .unwrap_or_default();
+            let mut children = Vec::new();
+            for (value, tag_flags) in tags.iter().skip(1) {
+                if tag_flags & TAG_FLAG_DEFUNCT != 0 {
+                    continue;
+                }
+                let Some((key, data)) = self.split_entry(value, *tag_flags, &prefix) else {
+                    continue;
+                };
+                if flags & PAGE_FLAG_PARENT != 0 {
+                    if let Some(child) = read_u32_le(data, data.len().saturating_sub(4)) {
+                        children.push(child);
+                    }
+                } else if flags & PAGE_FLAG_LEAF != 0 {
+                    f(key, data);
+                }
+            }
+            // pages are popped from the end, so children are pushed in reverse to keep the key order
+            pages.extend(children.into_iter().rev());
+        }
+        Ok(())
+    }
+
+    /// page tags (value and flags), tag 0 is the common key prefix of the page
+    fn page_tags(&self, page: &[u8]) -> Vec<(Vec<u8>, u16)> {
+        let large_pages = self.page_size >= 16384;
+        let header_size = if large_pages { 80 } else { 40 };
+        let tag_count = read_u16_le(page, 34).unwrap_or_default() as usize;
+        let mut tags = Vec::new();
+        for i in 0..tag_count {
+            let Some(tag_offset) = self.page_size.checked_sub(4 * (i + 1)) else {
+                break;
+            };
+            let (Some(raw_size), Some(raw_offset)) = (
+                read_u16_le(page, tag_offset),
+                read_u16_le(page, tag_offset + 2),
+            ) else {
+                break;
+            };
+            let (size, offset, mut flags) = if large_pages {
+                (raw_size & 0x7FFF, raw_offset & 0x7FFF, 0)
+            } else {
+                (raw_size & 0x1FFF, raw_offset & 0x1FFF, raw_offset >> 13)
+            };
+            let start = header_size + offset as usize;
+            let Some(value) = page.get(start..start + size as usize) else {
+                continue;
+            };
+            let mut value = value.to_vec();
+            // large pages keep the flags in the upper bits of the first value bytes
+            if large_pages && i > 0 && value.len() >= 2 {
+                flags = u16::from_le_bytes([value[0], value[1]]) >> 13;
+                value[1] &= 0x1F;
+            }
+            tags.push((value, flags));
+        }
+        tags
+    }
+
+    /// splits a page entry into its full key and its data
+    fn split_entry<'a>(
+        &self,
+        value: &'a [u8],
+        flags: u16,
+        prefix: &[u8],
+    ) -> Option<(Vec<u8>, &'a [u8])> {
+        let mut position = 0;
+        let mut key = Vec::new();
+        if flags & TAG_FLAG_COMMON_KEY != 0 {
+            let common_size = read_u16_le(value, 0)? as usize;
+            key.extend_from_slice(prefix.get(..common_size.min(prefix.len()))?);
+            position = 2;
+        }
+        let local_size = read_u16_le(value, position)? as usize;
+        position += 2;
+        key.extend_from_slice(value.get(position..position + local_size)?);
+        Some((key, value.get(position + local_size..)?))
+    }
+}
+
+/// size of a fixed column type, None for variable ones
+fn fixed_size(column_type: u32) -> Option<usize> {
+    match column_type {
+        1 | 2 => Some(1),
+        3 | 17 => Some(2),
+        4 | 6 | 14 => Some(4),
+        5 | 7 | 8 | 15 => Some(8),
+        16 => Some(16),
+        _ => None,
+    }
+}
+
+/// value of a variable column (128 - 255) of a record
+fn variable_column(record: &[u8], column_id: u32) -> Option<&[u8]> {
+    let last_variable = *record.get(1)? as u32;
+    let variable_offset = read_u16_le(record, 2)? as usize;
+    if column_id < 128 || column_id > last_variable {
+        return None;
+    }
+    let count = (last_variable - 127) as usize;
+    let data_start = variable_offset + count * 2;
+    let index = (column_id - 128) as usize;
+    let end = read_u16_le(record, variable_offset + index * 2)?;
+    // the highest bit marks an empty value
+    if end & 0x8000 != 0 {
+        return None;
+    }
+    let start = if index == 0 {
+        0
+    } else {
+        (read_u16_le(record, variable_offset + (index - 1) * 2)? & 0x7FFF) as usize
+    };
+    record.get(data_start + start..data_start + (end & 0x7FFF) as usize)
+}
+
+fn parse_record(
+    record: &[u8],
+    table: &EseTable,
+    long_values: &BTreeMap<Vec<u8>, Vec<u8>>,
+    page_size: usize,
+) -> HashMap<String, EseValue> {
+    let mut values = HashMap::new();
+    let (Some(last_fixed), Some(last_variable), Some(variable_offset)) =
+        (record.first(), record.get(1), read_u16_le(record, 2))
+    else {
+        return values;
+    };
+    let (last_fixed, last_variable) = (*last_fixed as u32, *last_variable as u32);
+
+    // fixed columns, followed by a bitmap of empty ones
+    let mut offset = 4;
+    let fixed_columns: Vec<&EseColumn> = table
+        .columns
+        .iter()
+        .filter(|c| c.id <= last_fixed)
+        .collect();
+    let fixed_end = 4 + fixed_columns
+        .iter()
+        .map(|c| fixed_size(c.column_type).unwrap_or(0))
+        .sum::<usize>();
+    for column in fixed_columns {
+        let size = fixed_size(column.column_type).unwrap_or(0);
+        let index = (column.id - 1) as usize;
+        let empty = record
+            .get(fixed_end + index / 8)
+            .is_some_and(|bits| bits & (1 << (index % 8)) != 0);
+        if let (false, Some(data)) = (empty, record.get(offset..offset + size)) {
+            values.insert(column.name.clone(), value_of(column, data.to_vec()));
+        }
+        offset += size;
+    }
+
+    // variable columns
+    for column in table
+        .columns
+        .iter()
+        .filter(|c| (128..=last_variable).contains(&c.id))
+    {
+        if let Some(data) = variable_column(record, column.id) {
+            values.insert(column.name.clone(), value_of(column, data.to_vec()));
+        }
+    }
+
+    // tagged columns start after the variable data
+    let variable_count = last_variable.saturating_sub(127) as usize;
+    let variable_data_size = if variable_count > 0 {
+        read_u16_le(record, variable_offset as usize + (variable_count - 1) * 2)
+            .map(|end| (end & 0x7FFF) as usize)
+            .unwrap_or(0)
+    } else {
+        0
+    };
+    let tagged_start = variable_offset as usize + variable_count * 2 + variable_data_size;
+    let Some(tagged) = record.get(tagged_start..) else {
+        return values;
+    };
+    let large_pages = page_size >= 16384;
+    let offset_mask = if large_pages { 0x7FFF } else { 0x3FFF };
+    let tagged_count = read_u16_le(tagged, 2)
+        .map(|first_offset| (first_offset & offset_mask) as usize / 4)
+        .unwrap_or(0);
+    for i in 0..tagged_count {
+        let (Some(column_id), Some(raw_offset)) =
+            (read_u16_le(tagged, i * 4), read_u16_le(tagged, i * 4 + 2))
+        else {
+            break;
+        };
+        let start = (raw_offset & offset_mask) as usize;
+        let end = if i + 1 < tagged_count {
+            read_u16_le(tagged, (i + 1) * 4 + 2)
+                .map(|o| (o & offset_mask) as usize)
+                .unwrap_or(tagged.len())
+        } else {
+            tagged.len()
+        };
+        let Some(mut data) = tagged.get(start..end) else {
+            continue;
+        };
+        let Some(column) = table.columns.iter().find(|c| c.id == column_id as u32) else {
+            continue;
+        };
+        // the data starts with a flags byte on large pages or if bit 0x4000 is set
+        let mut flags = 0;
+        if (large_pages || raw_offset & 0x4000 != 0) && !data.is_empty() {
+            flags = data[0];
+            data = &data[1..];
+        }
+        let parts = if flags & TAGGED_LONG_VALUE != 0 {
+            vec![read_long_value(data, long_values)]
+        } else if flags & TAGGED_MULTI_VALUE != 0 {
+            multi_values(data).into_iter().map(<[u8]>::to_vec).collect()
+        } else {
+            vec![data.to_vec()]
+        };
+        let mut decoded = Vec::new();
+        for part in parts {
+            if flags & TAGGED_COMPRESSED == 0 {
+                decoded.push(part);
+                continue;
+            }
+            // the compressed bytes are never returned as value
+            match decompress(&part) {
+                Some(part) => decoded.push(part),
+                None => error!(
+                    "Failed to decompress column {} (compression type {})",
+                    column.name,
+                    part.first().map(|b| b >> 3).unwrap_or_default()
+                ),
+            }
+        }
+        if decoded.is_empty() {
+            continue;
+        }
+        values.insert(
+            column.name.clone(),
+            value_of(column, join_values(column, decoded)),
+        );
+    }
+    values
+}
+
+fn value_of(column: &EseColumn, data: Vec<u8>) -> EseValue {
+    EseValue {
+        column_type: column.column_type,
+        codepage: column.codepage,
+        data,
+    }
+}
+
+/// long values are stored in their own tree, keyed by the (big endian) long value id and the offset of every chunk
+fn read_long_value(reference: &[u8], long_values: &BTreeMap<Vec<u8>, Vec<u8>>) -> Vec<u8> {
+    let id: Vec<u8> = match reference.len() {
+        4 | 8 => reference.iter().rev().copied().collect(),
+        _ => return Vec::new(),
+    };
+    long_values
+        .range(id.clone()..)
+        .take_while(|(key, _)| key.starts_with(&id))
+        .filter(|(key, _)| key.len() > id.len())
+        .flat_map(|(_, chunk)| chunk.iter().copied())
+        .collect()
+}
+
+/// multi values start with an offset table, the first offset points behind it
+fn multi_values(data: &[u8]) -> Vec<&[u8]> {
+    let first = read_u16_le(data, 0)
+        .map(|o| (o & 0x7FFF) as usize)
+        .filter(|o| *o >= 2 && o % 2 == 0);
+    let Some(first) = first else {
+        return vec![data];
+    };
+    let offsets: Vec<usize> = (0..first / 2)
+        .filter_map(|i| read_u16_le(data, i * 2))
+        .map(|o| (o & 0x7FFF) as usize)
+        .collect();
+    offsets
+        .iter()
+        .enumerate()
+        .filter_map(|(i, start)| {
+            let end = offsets.get(i + 1).copied().unwrap_or(data.len());
+            data.get(*start..end)
+        })
+        .collect()
+}
+
+/// joins the values of a multi valued column, text is separated by "; " in the encoding of the column
+fn join_values(column: &EseColumn, mut values: Vec<Vec<u8>>) -> Vec<u8> {
+    if values.len() == 1 {
+        return values.remove(0);
+    }
+    // a fixed size value can only hold one of them
+    if fixed_size(column.column_type).is_some() {
+        return values.remove(0);
+    }
+    // ASCII/Windows-1252 text columns, everything else (UTF-16 text and binary blobs of Windows.edb) is UTF-16
+    let ascii = matches!(column.column_type, 10 | 12) && column.codepage != 1200;
+    let separator: Vec<u8> = if ascii {
+        MULTI_VALUE_SEPARATOR.as_bytes().to_vec()
+    } else {
+        MULTI_VALUE_SEPARATOR
+            .encode_utf16()
+            .flat_map(u16::to_le_bytes)
+            .collect()
+    };
+    let mut joined = Vec::new();
+    for (i, mut value) in values.into_iter().enumerate() {
+        // terminating nulls
+        if ascii {
+            while value.last() == Some(&0) {
+                value.pop();
+            }
+        } else {
+            while value.len() >= 2 && value.ends_with(&[0, 0]) {
+                value.truncate(value.len() - 2);
+            }
+        }
+        if i > 0 {
+            joined.extend_from_slice(&separator);
+        }
+        joined.extend(value);
+    }
+    joined
+}
+
+/// the upper 5 bits of the first byte are the compression type, None for unsupported types (e.g. Xpress9) or damaged data
+pub fn decompress(data: &[u8]) -> Option<Vec<u8>> {
+    let compression_type = data.first()? >> 3;
+    match compression_type {
+        // 7-bit ASCII and 7-bit UTF-16 (every character is expanded to 2 bytes)
+        COMPRESSION_7BIT_ASCII | COMPRESSION_7BIT_UTF16 => {
+            let mut output = Vec::new();
+            let mut bits: u32 = 0;
+            let mut bit_count = 0;
+            for byte in &data[1..] {
+                bits |= (*byte as u32) << bit_count;
+                bit_count += 8;
+                while bit_count >= 7 {
+                    output.push((bits & 0x7F) as u8);
+                    if compression_type == COMPRESSION_7BIT_UTF16 {
+                        output.push(0);
+                    }
+                    bits >>= 7;
+                    bit_count -= 7;
+                }
+            }
+            Some(output)
+        }
+        // Windows 8+: u16 size of the uncompressed data, LZXPRESS data
+        COMPRESSION_LZXPRESS => {
+            let size = read_u16_le(data, 1)? as usize;
+            lzxpress::decompress(data.get(3..)?, size)
+        }
+        _ => None,
+    }
+}
+
+impl EseValue {
+    /// text columns are either UTF-16 (codepage 1200) or ASCII/Windows-1252
+    pub fn as_text(&self) -> String {
+        if self.codepage == 1200 {
+            let chars: Vec<u16> = self
+                .data
+                .chunks_exact(2)
+                .map(|c| u16::from_le_bytes([c[0], c[1]]))
+                .collect();
+            String::from_utf16_lossy(&chars)
+                .trim_end_matches('\0')
+                .to_string()
+        } else {
+            String::from_utf8_lossy(&self.data)
+                .trim_end_matches('\0')
+                .to_string()
+        }
+    }
+}
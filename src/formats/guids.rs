@@ -0,0 +1,100 @@
+// Lookup tables for GUIDs that show up in shell items, shortcut files and the registry
+
+/// names of shell folders as they appear in root folder shell items (class type 0x1F)
+pub fn shell_folder_name(guid: &str) -> Option<&'static str> {
+    let name = match guid.to_uppercase().as_str() {
+        "{20D04FE0-3AEA-1069-A2D8-08002B30309D}" => "My Computer",
+        "{450D8FBA-AD25-11D0-98A8-0800361B1103}" => "My Documents",
+        "{59031A47-3F72-44A7-89C5-5595FE6B30EE}" => "Users Files",
+        "{208D2C60-3AEA-1069-A2D7-08002B30309D}" => "My Network Places",
+        "{F02C1A0D-BE21-4350-88B0-7367FC96EF3C}" => "Network",
+        "{645FF040-5081-101B-9F08-00AA002F954E}" => "Recycle Bin",
+        "{21EC2020-3AEA-1069-A2DD-08002B30309D}" => "Control Panel",
+        "{26EE0668-A00A-44D7-9371-BEB064C98683}" => "Control Panel (Category View)",
+        "{871C5380-42A0-1069-A2EA-08002B30309D}" => "Internet Explorer",
+        "{031E4825-7B94-4DC3-B131-E946B44C8DD5}" => "Libraries",
+        "{22877A6D-37A1-461A-91B0-DBDA5AAEBC99}" => "Recent Places",
+        "{679F85CB-0220-4080-B29B-5540CC05AAB6}" => "Quick Access",
+        "{F874310E-B6B7-47DC-BC84-B9E6B38F5903}" => "Home",
+        "{018D5C66-4533-4307-9B53-224DE2ED1FE6}" => "OneDrive",
+        "{04731B67-D933-450A-90E6-4ACD2E9408FE}" => "Search Folder",
+        "{B4BFCC3A-DB2C-424C-B029-7FE99A87C641}" => "Desktop",
+        "{D3162B92-9365-467A-956B-92703ACA08AF}" => "Documents",
+        "{088E3905-0323-4B02-9826-5D99428E115F}" => "Downloads",
+        "{374DE290-123F-4565-9164-39C4925E467B}" => "Downloads",
+        "{24AD3AD4-A569-4530-98E1-AB02F9417AA8}" => "Pictures",
+        "{3DFDF296-DBEC-4FB4-81D1-6A3438BCF4DE}" => "Music",
+        "{1CF1260C-4DD0-4EBB-811F-33C572699FDE}" => "Music",
+        "{F86FA3AB-70D2-4FC7-9C99-FCBF05467F3A}" => "Videos",
+        "{A0953C92-50DC-43BF-BE83-3742FED03C9C}" => "Videos",
+        "{3ADD1653-EB32-4CB0-BBD7-DFA0ABB5ACCA}" => "Pictures",
+        "{A8CDFF1C-4878-43BE-B5FD-F8091C1C60D0}" => "Documents",
+        "{0DB7E03F-FC29-4DC6-9020-FF41B59E513A}" => "3D Objects",
+        "{9E52AB10-F80D-49DF-ACB8-4330F5687855}" => "CD Burning",
+        "{B155BDF8-02F0-451E-9A26-AE317CFD7779}" => "Delegate Folder (Network Drives)",
+        "{ED228FDF-9EA8-4870-83B1-96B02CFE0D52}" => "Games",
+        "{2227A280-3AEA-1069-A2DE-08002B30309D}" => "Printers",
+        "{7007ACC7-3202-11D1-AAD2-00805FC1270E}" => "Network Connections",
+        "{5E5F29CE-E0A8-49D3-AF32-7A7BDC173478}" => "This PC",
+        _ => return None,
+    };
+    Some(name)
+}
+
+/// paths of known folders, used e.g. in UserAssist value names ({1AC14E77-02E7-4E5D-B744-2EB1AE5198B7}\cmd.exe)
+pub fn known_folder_path(guid: &str) -> Option<&'static str> {
+    let path = match guid.to_uppercase().as_str() {
+        "{1AC14E77-02E7-4E5D-B744-2EB1AE5198B7}" => "C:\\Windows\\System32",
+        "{D65231B0-B2F1-4857-A4CE-A8E7C6EA7D27}" => "C:\\Windows\\SysWOW64",
+        "{F38BF404-1D43-42F2-9305-67DE0B28FC23}" => "C:\\Windows",
+        "{905E63B6-C1BF-494E-B29C-65B732D3D21A}" => "C:\\Program Files",
+        "{6D809377-6AF0-444B-8957-A3773F02200E}" => "C:\\Program Files",
+        "{7C5A40EF-A0FB-4BFC-874A-C0F2E0B9FA8E}" => "C:\\Program Files (x86)",
+        "{F7F1ED05-9F6D-47A2-AAAE-29D317C6F066}" => "C:\\Program Files\\Common Files",
+        "{6365D5A7-0F0D-45E5-87F6-0DA56B6A4F7D}" => "C:\\Program Files\\Common Files",
+        "{DE974D24-D9C6-4D3E-BF91-F4455120B917}" => "C:\\Program Files (x86)\\Common Files",
+        "{62AB5D82-FDC1-4DC3-A9DD-070D1D495D97}" => "C:\\ProgramData",
+        "{0139D44E-6AFE-49F2-8690-3DAFCAE6FFB8}" => {
+            "C:\\ProgramData\\Microsoft\\Windows\\Start Menu\\Programs"
+        }
+        "{A4115719-D62E-491D-AA7C-E74B8BE3B067}" => "C:\\ProgramData\\Microsoft\\Windows\\Start Menu",
+        "{82A5EA35-D9CD-47C5-9629-E15D2F714E6E}" => {
+            "C:\\ProgramData\\Microsoft\\Windows\\Start Menu\\Programs\\StartUp"
+        }
+        "{C4AA340D-F20F-4863-AFEF-F87EF2E6BA25}" => "C:\\Users\\Public\\Desktop",
+        "{DFDF76A2-C82A-4D63-906A-5644AC457385}" => "C:\\Users\\Public",
+        "{5E6C858F-0E22-4760-9AFE-EA3317B67173}" => "%USERPROFILE%",
+        "{B4BFCC3A-DB2C-424C-B029-7FE99A87C641}" => "%USERPROFILE%\\Desktop",
+        "{FDD39AD0-238F-46AF-ADB4-6C85480369C7}" => "%USERPROFILE%\\Documents",
+        "{374DE290-123F-4565-9164-39C4925E467B}" => "%USERPROFILE%\\Downloads",
+        "{4BD8D571-6D19-48D3-BE97-422220080E43}" => "%USERPROFILE%\\Music",
+        "{33E28130-4E1E-4676-835A-98395C3BC3BB}" => "%USERPROFILE%\\Pictures",
+        "{18989B1D-99B5-455B-841C-AB7C74E4DDFC}" => "%USERPROFILE%\\Videos",
+        "{F1B32785-6FBA-4FCF-9D55-7B8E7F157091}" => "%USERPROFILE%\\AppData\\Local",
+        "{A520A1A4-1780-4FF6-BD18-167343C5AF16}" => "%USERPROFILE%\\AppData\\LocalLow",
+        "{3EB685DB-65F9-4CF6-A03A-E3EF65729F3D}" => "%USERPROFILE%\\AppData\\Roaming",
+        "{A77F5D77-2E2B-44C3-A6A2-ABA601054A51}" => {
+            "%USERPROFILE%\\AppData\\Roaming\\Microsoft\\Windows\\Start Menu\\Programs"
+        }
+        "{625B53C3-AB48-4EC1-BA1F-A1EF4146FC19}" => {
+            "%USERPROFILE%\\AppData\\Roaming\\Microsoft\\Windows\\Start Menu"
+        }
+        "{B97D20BB-F46A-4C97-BA10-5E3608430854}" => {
+            "%USERPROFILE%\\AppData\\Roaming\\Microsoft\\Windows\\Start Menu\\Programs\\StartUp"
+        }
+        "{9E3995AB-1F9C-4F13-B827-48B24B6C7174}" => {
+            "%USERPROFILE%\\AppData\\Roaming\\Microsoft\\Internet Explorer\\Quick Launch\\User Pinned"
+        }
+        "{AE50C081-EBD2-438A-8655-8A092E34987A}" => {
+            "%USERPROFILE%\\AppData\\Roaming\\Microsoft\\Windows\\Recent"
+        }
+        "{A63293E8-664E-48DB-A079-DF759E0509F7}" => {
+            "%USERPROFILE%\\AppData\\Roaming\\Microsoft\\Windows\\Templates"
+        }
+        "{52A4F021-7B75-48A9-9F6B-4B87A210BC8F}" => {
+            "%USERPROFILE%\\AppData\\Roaming\\Microsoft\\Internet Explorer\\Quick Launch"
+        }
+        _ => return None,
+    };
+    Some(path)
+}
@@ -0,0 +1,318 @@
+// LevelDB databases, read-only, as used by Chromium (Local Storage, Session Storage, IndexedDB, File System, ...)
+// format reference: https://github.com/google/leveldb/blob/main/doc/log_format.md and table_format.md
+// <number>.log: write ahead log of 32 KiB blocks with (fragmented) write batches
+// <number>.ldb / .sst: sorted tables of (Snappy compressed) blocks with prefix compressed keys
+// records are read from all files without merging, so values that were overwritten or deleted but not
+// compacted yet are kept and marked as not live
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::anyhow;
+use common::{read_u32_le, read_u64_le};
+use log::error;
+
+const LOG_BLOCK_SIZE: usize = 32 * 1024;
+const LOG_HEADER_SIZE: usize = 7;
+// log record types
+const LOG_FULL: u8 = 1;
+const LOG_FIRST: u8 = 2;
+const LOG_MIDDLE: u8 = 3;
+const LOG_LAST: u8 = 4;
+const TABLE_MAGIC: u64 = 0xdb47_7524_8b80_fb57;
+const TABLE_FOOTER_SIZE: usize = 48;
+const BLOCK_TRAILER_SIZE: usize = 5;
+const BLOCK_SNAPPY: u8 = 1;
+// value types of records
+const TYPE_DELETION: u8 = 0;
+const TYPE_VALUE: u8 = 1;
+
+/// a key and value of a LevelDB database
+#[derive(Debug, Clone)]
+pub struct LevelDbRecord {
+    pub key: Vec<u8>,
+    pub value: Vec<u8>,
+    // false if a newer record overwrote or deleted the key
+    pub live: bool,
+    pub source_file: String,
+}
+
+// a record before the live state is known, value is None for deletions
+struct RawRecord {
+    key: Vec<u8>,
+    value: Option<Vec<u8>>,
+    sequence: u64,
+    source_file: String,
+}
+
+/// all values of the LevelDB database in folder, deletions themselves are not returned
+pub fn read_leveldb(folder: &str) -> anyhow::Result<Vec<LevelDbRecord>> {
+    let mut files: Vec<_> = fs::read_dir(folder)?
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_file())
+        .collect();
+    files.sort();
+
+    let mut raw_records: Vec<RawRecord> = Vec::new();
+    for file in files {
+        let extension = file
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        let result = match extension.as_str() {
+            "log" => read_log_file(&file, &mut raw_records),
+            "ldb" | "sst" => read_table_file(&file, &mut raw_records),
+            _ => continue,
+        };
+        if let Err(err) = result {
+            error!("Failed to read {}: {err}", file.display())
+        }
+    }
+
+    // the same record can be in a log and a table until the log is deleted
+    raw_records.sort_by(|a, b| (&a.key, a.sequence).cmp(&(&b.key, b.sequence)));
+    raw_records.dedup_by(|a, b| a.key == b.key && a.sequence == b.sequence);
+
+    // sorted by key and sequence, the last record of a key is the live one
+    let mut records = Vec::new();
+    let mut raw_records = raw_records.into_iter().peekable();
+    while let Some(record) = raw_records.next() {
+        let live = raw_records.peek().is_none_or(|next| next.key != record.key);
+        if let Some(value) = record.value {
+            records.push(LevelDbRecord {
+                key: record.key,
+                value,
+                live,
+                source_file: record.source_file,
+            });
+        }
+    }
+    Ok(records)
+}
+
+/// physical records are reassembled to write batches, checksums are not verified
+fn read_log_file(path: &Path, raw_records: &mut Vec<RawRecord>) -> anyhow::Result<()> {
+    let data = fs::read(path)?;
+    let source_file = path.to_string_lossy().to_string();
+    let mut batch: Vec<u8> = Vec::new();
+    for block in data.chunks(LOG_BLOCK_SIZE) {
+        let mut offset = 0;
+        while offset + LOG_HEADER_SIZE <= block.len() {
+            let length = u16::from_le_bytes([block[offset + 4], block[offset + 5]]) as usize;
+            let record_type = block[offset + 6];
+            let start = offset + LOG_HEADER_SIZE;
+            let Some(fragment) = block.get(start..start + length) else {
+                break;
+            };
+            offset = start + length;
+            match record_type {
+                LOG_FULL => {
+                    read_write_batch(fragment, &source_file, raw_records);
+                    batch.clear();
+                }
+                LOG_FIRST => {
+                    batch.clear();
+                    batch.extend_from_slice(fragment);
+                }
+                LOG_MIDDLE => batch.extend_from_slice(fragment),
+                LOG_LAST => {
+                    batch.extend_from_slice(fragment);
+                    read_write_batch(&batch, &source_file, raw_records);
+                    batch.clear();
+                }
+                // zeroed (preallocated) space, the rest of the block is unused
+                _ => break,
+            }
+        }
+    }
+    Ok(())
+}
+
+/// u64 sequence, u32 count, then count records of type, key and value (not for deletions)
+fn read_write_batch(batch: &[u8], source_file: &str, raw_records: &mut Vec<RawRecord>) {
+    let (Some(mut sequence), Some(count)) = (read_u64_le(batch, 0), read_u32_le(batch, 8)) else {
+        return;
+    };
+    let mut offset = 12;
+    for _ in 0..count {
+        let Some(&record_type) = batch.get(offset) else {
+            return;
+        };
+        offset += 1;
+        let Some(key) = read_length_prefixed(batch, &mut offset) else {
+            return;
+        };
+        let value = match record_type {
+            TYPE_VALUE => match read_length_prefixed(batch, &mut offset) {
+                Some(value) => Some(value.to_vec()),
+                None => return,
+            },
+            TYPE_DELETION => None,
+            _ => return,
+        };
+        raw_records.push(RawRecord {
+            key: key.to_vec(),
+            value,
+            sequence,
+            source_file: source_file.to_string(),
+        });
+        sequence += 1;
+    }
+}
+
+/// all data blocks referenced by the index block
+fn read_table_file(path: &Path, raw_records: &mut Vec<RawRecord>) -> anyhow::Result<()> {
+    let data = fs::read(path)?;
+    let source_file = path.to_string_lossy().to_string();
+    if data.len() < TABLE_FOOTER_SIZE {
+        return Err(anyhow!("table is truncated!"));
+    }
+    let footer = &data[data.len() - TABLE_FOOTER_SIZE..];
+    if read_u64_le(footer, TABLE_FOOTER_SIZE - 8) != Some(TABLE_MAGIC) {
+        return Err(anyhow!("not a LevelDB table!"));
+    }
+    let mut offset = 0;
+    // the metaindex handle comes first and is not needed
+    read_block_handle(footer, &mut offset).ok_or(anyhow!("invalid table footer!"))?;
+    let index_handle =
+        read_block_handle(footer, &mut offset).ok_or(anyhow!("invalid table footer!"))?;
+    let index_block = read_block(&data, index_handle)?;
+
+    for (_, handle) in read_block_entries(&index_block) {
+        let mut offset = 0;
+        let Some(handle) = read_block_handle(&handle, &mut offset) else {
+            continue;
+        };
+        let block = match read_block(&data, handle) {
+            Ok(block) => block,
+            Err(err) => {
+                error!("Failed to read block of {source_file}: {err}");
+                continue;
+            }
+        };
+        // internal keys end with (sequence << 8 | type)
+        for (internal_key, value) in read_block_entries(&block) {
+            let Some(split) = internal_key.len().checked_sub(8) else {
+                continue;
+            };
+            let Some(tag) = read_u64_le(&internal_key, split) else {
+                continue;
+            };
+            let value = match (tag & 0xff) as u8 {
+                TYPE_VALUE => Some(value),
+                TYPE_DELETION => None,
+                _ => continue,
+            };
+            raw_records.push(RawRecord {
+                key: internal_key[..split].to_vec(),
+                value,
+                sequence: tag >> 8,
+                source_file: source_file.clone(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// contents of a block, decompressed if needed
+fn read_block(data: &[u8], (offset, size): (usize, usize)) -> anyhow::Result<Vec<u8>> {
+    let end = offset
+        .checked_add(size)
+        .filter(|end| end + BLOCK_TRAILER_SIZE <= data.len())
+        .ok_or(anyhow!("block is out of bounds!"))?;
+    let block = &data[offset..end];
+    match data[end] {
+        BLOCK_SNAPPY => Ok(snap::raw::Decoder::new().decompress_vec(block)?),
+        _ => Ok(block.to_vec()),
+    }
+}
+
+/// entries of shared key length, unshared key length, value length, unshared key and value,
+/// followed by the restart offsets and their count
+fn read_block_entries(block: &[u8]) -> Vec<(Vec<u8>, Vec<u8>)> {
+    let mut entries = Vec::new();
+    let Some(restarts) = block
+        .len()
+        .checked_sub(4)
+        .and_then(|o| read_u32_le(block, o))
+    else {
+        return entries;
+    };
+    let Some(end) = (restarts as usize)
+        .checked_mul(4)
+        .and_then(|r| block.len().checked_sub(4 + r))
+    else {
+        return entries;
+    };
+    let block = &block[..end];
+
+    let mut offset = 0;
+    let mut key: Vec<u8> = Vec::new();
+    while offset < block.len() {
+        let (Some(shared), Some(unshared), Some(value_length)) = (
+            read_varint(block, &mut offset),
+            read_varint(block, &mut offset),
+            read_varint(block, &mut offset),
+        ) else {
+            break;
+        };
+        let (Ok(shared), Ok(unshared), Ok(value_length)) = (
+            usize::try_from(shared),
+            usize::try_from(unshared),
+            usize::try_from(value_length),
+        ) else {
+            break;
+        };
+        if shared > key.len() {
+            break;
+        }
+        let Some(key_delta) = offset
+            .checked_add(unshared)
+            .and_then(|end| block.get(offset..end))
+        else {
+            break;
+        };
+        key.truncate(shared);
+        key.extend_from_slice(key_delta);
+        offset += unshared;
+        let Some(value) = offset
+            .checked_add(value_length)
+            .and_then(|end| block.get(offset..end))
+        else {
+            break;
+        };
+        offset += value_length;
+        entries.push((key.clone(), value.to_vec()));
+    }
+    entries
+}
+
+/// offset and size as varints
+fn read_block_handle(data: &[u8], offset: &mut usize) -> Option<(usize, usize)> {
+    let block_offset = usize::try_from(read_varint(data, offset)?).ok()?;
+    let size = usize::try_from(read_varint(data, offset)?).ok()?;
+    Some((block_offset, size))
+}
+
+/// varint length followed by the bytes
+fn read_length_prefixed<'a>(data: &'a [u8], offset: &mut usize) -> Option<&'a [u8]> {
+    let length = usize::try_from(read_varint(data, offset)?).ok()?;
+    let bytes = data.get(*offset..offset.checked_add(length)?)?;
+    *offset += length;
+    Some(bytes)
+}
+
+/// little endian base 128 varint (up to 64 bits)
+pub fn read_varint(data: &[u8], offset: &mut usize) -> Option<u64> {
+    let mut value: u64 = 0;
+    for shift in (0..64).step_by(7) {
+        let byte = *data.get(*offset)?;
+        *offset += 1;
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Some(value);
+        }
+    }
+    None
+}
@@ -0,0 +1,347 @@
+// Shell Link (.lnk) parser
+// format reference: [MS-SHLLINK] https://learn.microsoft.com/en-us/openspecs/windows_protocols/ms-shllink
+
+use anyhow::anyhow;
+use chrono::{DateTime, Utc};
+use common::{
+    convert_win_time_opt, format_guid, read_ascii_string, read_extended_ascii, read_u16_le,
+    read_u32_le, read_u64_le, read_utf16_string,
+};
+use serde::Serialize;
+
+use crate::formats::guids::shell_folder_name;
+use crate::formats::shell_items::{id_list_path, parse_id_list, ShellItem};
+
+const HEADER_SIZE: usize = 0x4C;
+const LINK_CLSID: &str = "{00021401-0000-0000-C000-000000000046}";
+
+// link flags
+const HAS_LINK_TARGET_ID_LIST: u32 = 0x0000_0001;
+const HAS_LINK_INFO: u32 = 0x0000_0002;
+const HAS_NAME: u32 = 0x0000_0004;
+const HAS_RELATIVE_PATH: u32 = 0x0000_0008;
+const HAS_WORKING_DIR: u32 = 0x0000_0010;
+const HAS_ARGUMENTS: u32 = 0x0000_0020;
+const HAS_ICON_LOCATION: u32 = 0x0000_0040;
+const IS_UNICODE: u32 = 0x0000_0080;
+
+/// Everything that was decoded from one shortcut
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct ShellLink {
+    pub target_created: Option<DateTime<Utc>>,
+    pub target_accessed: Option<DateTime<Utc>>,
+    pub target_modified: Option<DateTime<Utc>>,
+    pub target_size: u32,
+    pub target_attributes: Vec<String>,
+    pub show_command: String,
+    pub id_list_path: Option<String>,
+    pub id_list: Vec<ShellItem>,
+    pub link_info: Option<LinkInfo>,
+    pub name: Option<String>,
+    pub relative_path: Option<String>,
+    pub working_dir: Option<String>,
+    pub arguments: Option<String>,
+    pub icon_location: Option<String>,
+    pub environment_target: Option<String>,
+    pub known_folder: Option<String>,
+    pub special_folder_id: Option<u32>,
+    pub tracker: Option<TrackerData>,
+    pub extra_data_blocks: Vec<String>,
+}
+
+/// LinkInfo structure: where the target was located when the link was created
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct LinkInfo {
+    pub drive_type: Option<String>,
+    pub drive_serial_number: Option<String>,
+    pub volume_label: Option<String>,
+    pub local_base_path: Option<String>,
+    pub network_share_name: Option<String>,
+    pub network_device_name: Option<String>,
+    pub common_path_suffix: Option<String>,
+}
+
+/// Distributed Link Tracker data block (0xA0000003)
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct TrackerData {
+    pub machine_id: String,
+    pub droid_volume_id: String,
+    pub droid_file_id: String,
+    pub birth_droid_volume_id: String,
+    pub birth_droid_file_id: String,
+    pub mac_address: String,
+    pub file_id_created: Option<DateTime<Utc>>,
+}
+
+/// parses a complete shell link from a buffer
+pub fn parse_lnk(data: &[u8]) -> anyhow::Result<ShellLink> {
+    if read_u32_le(data, 0) != Some(HEADER_SIZE as u32)
+        || format_guid(data, 4).as_deref() != Some(LINK_CLSID)
+    {
+        return Err(anyhow!("Not a shell link (invalid header)!"));
+    }
+    let flags = read_u32_le(data, 0x14).ok_or(anyhow!("Link flags can not be read!"))?;
+    let attributes = read_u32_le(data, 0x18).unwrap_or(0);
+
+    let mut link = ShellLink {
+        target_created: read_u64_le(data, 0x1C).and_then(convert_win_time_opt),
+        target_accessed: read_u64_le(data, 0x24).and_then(convert_win_time_opt),
+        target_modified: read_u64_le(data, 0x2C).and_then(convert_win_time_opt),
+        target_size: read_u32_le(data, 0x34).unwrap_or(0),
+        target_attributes: file_attributes(attributes),
+        show_command: show_command(read_u32_le(data, 0x3C).unwrap_or(0)),
+        ..Default::default()
+    };
+
+    let mut offset = HEADER_SIZE;
+    if flags & HAS_LINK_TARGET_ID_LIST != 0 {
+        let size =
+            read_u16_le(data, offset).ok_or(anyhow!("IDList size can not be read!"))? as usize;
+        let id_list = data
+            .get(offset + 2..offset + 2 + size)
+            .ok_or(anyhow!("IDList is truncated!"))?;
+        link.id_list = parse_id_list(id_list);
+        if !link.id_list.is_empty() {
+            link.id_list_path = Some(id_list_path(&link.id_list));
+        }
+        offset += 2 + size;
+    }
+
+    if flags & HAS_LINK_INFO != 0 {
+        let size =
+            read_u32_le(data, offset).ok_or(anyhow!("LinkInfo size can not be read!"))? as usize;
+        let link_info = data
+            .get(offset..offset + size)
+            .ok_or(anyhow!("LinkInfo is truncated!"))?;
+        link.link_info = parse_link_info(link_info);
+        offset += size;
+    }
+
+    let unicode = flags & IS_UNICODE != 0;
+    for (flag, field) in [
+        (HAS_NAME, &mut link.name),
+        (HAS_RELATIVE_PATH, &mut link.relative_path),
+        (HAS_WORKING_DIR, &mut link.working_dir),
+        (HAS_ARGUMENTS, &mut link.arguments),
+        (HAS_ICON_LOCATION, &mut link.icon_location),
+    ] {
+        if flags & flag != 0 {
+            let (string, size) = read_string_data(data, offset, unicode)
+                .ok_or(anyhow!("StringData is truncated!"))?;
+            *field = Some(string);
+            offset += size;
+        }
+    }
+
+    parse_extra_data(data, offset, &mut link);
+
+    Ok(link)
+}
+
+/// reads a StringData structure (character count followed by the characters), returns the string and the consumed size
+fn read_string_data(data: &[u8], offset: usize, unicode: bool) -> Option<(String, usize)> {
+    let count = read_u16_le(data, offset)? as usize;
+    if unicode {
+        let raw = data.get(offset + 2..offset + 2 + count * 2)?;
+        let chars: Vec<u16> = raw
+            .chunks_exact(2)
+            .map(|c| u16::from_le_bytes([c[0], c[1]]))
+            .collect();
+        Some((String::from_utf16_lossy(&chars), 2 + count * 2))
+    } else {
+        Some((read_extended_ascii(data, offset + 2, count)?, 2 + count))
+    }
+}
+
+fn parse_link_info(data: &[u8]) -> Option<LinkInfo> {
+    let header_size = read_u32_le(data, 4)?;
+    let flags = read_u32_le(data, 8)?;
+    let volume_id_offset = read_u32_le(data, 12)? as usize;
+    let local_base_path_offset = read_u32_le(data, 16)? as usize;
+    let network_offset = read_u32_le(data, 20)? as usize;
+    let suffix_offset = read_u32_le(data, 24)? as usize;
+
+    let mut info = LinkInfo::default();
+
+    // VolumeIDAndLocalBasePath
+    if flags & 0x1 != 0 {
+        let volume = data.get(volume_id_offset..)?;
+        info.drive_type = read_u32_le(volume, 4).map(drive_type);
+        info.drive_serial_number = read_u32_le(volume, 8).map(|s| format!("{s:08X}"));
+        let label_offset = read_u32_le(volume, 12)? as usize;
+        info.volume_label = if label_offset == 0x14 {
+            let unicode_offset = read_u32_le(volume, 16)? as usize;
+            read_utf16_string(volume, unicode_offset)
+        } else {
+            read_ascii_string(volume, label_offset)
+        };
+        info.local_base_path = if header_size >= 0x24 {
+            read_u32_le(data, 28)
+                .and_then(|o| read_utf16_string(data, o as usize))
+                .filter(|p| !p.is_empty())
+                .or_else(|| read_ascii_string(data, local_base_path_offset))
+        } else {
+            read_ascii_string(data, local_base_path_offset)
+        };
+    }
+
+    // CommonNetworkRelativeLinkAndPathSuffix
+    if flags & 0x2 != 0 {
+        let network = data.get(network_offset..)?;
+        let network_flags = read_u32_le(network, 4)?;
+        let net_name_offset = read_u32_le(network, 8)? as usize;
+        let device_name_offset = read_u32_le(network, 12)? as usize;
+        if net_name_offset > 0x14 {
+            info.network_share_name =
+                read_u32_le(network, 20).and_then(|o| read_utf16_string(network, o as usize));
+            if network_flags & 0x1 != 0 {
+                info.network_device_name =
+                    read_u32_le(network, 24).and_then(|o| read_utf16_string(network, o as usize));
+            }
+        } else {
+            info.network_share_name = read_ascii_string(network, net_name_offset);
+            if network_flags & 0x1 != 0 {
+                info.network_device_name = read_ascii_string(network, device_name_offset);
+            }
+        }
+    }
+
+    info.common_path_suffix = if header_size >= 0x24 {
+        read_u32_le(data, 32)
+            .and_then(|o| read_utf16_string(data, o as usize))
+            .or_else(|| read_ascii_string(data, suffix_offset))
+    } else {
+        read_ascii_string(data, suffix_offset)
+    }
+    .filter(|s| !s.is_empty());
+
+    Some(info)
+}
+
+fn parse_extra_data(data: &[u8], mut offset: usize, link: &mut ShellLink) {
+    while let Some(size) = read_u32_le(data, offset) {
+        let size = size as usize;
+        // terminal block
+        if size < 8 {
+            break;
+        }
+        let Some(block) = data.get(offset..offset + size) else {
+            break;
+        };
+        let Some(signature) = read_u32_le(block, 4) else {
+            break;
+        };
+        match signature {
+            0xA000_0001 => {
+                link.environment_target = read_utf16_string(block, 268)
+                    .filter(|s| !s.is_empty())
+                    .or_else(|| read_ascii_string(block, 8));
+            }
+            0xA000_0003 => link.tracker = parse_tracker(block),
+            0xA000_0005 => link.special_folder_id = read_u32_le(block, 8),
+            0xA000_000B => {
+                link.known_folder =
+                    format_guid(block, 8).map(|guid| match shell_folder_name(&guid) {
+                        Some(name) => format!("{name} {guid}"),
+                        None => guid,
+                    })
+            }
+            _ => {}
+        }
+        link.extra_data_blocks.push(extra_data_name(signature));
+        offset += size;
+    }
+}
+
+fn parse_tracker(block: &[u8]) -> Option<TrackerData> {
+    let droid_file = block.get(48..64)?;
+    // the droid file id is a version 1 UUID, which contains the MAC address and the creation time
+    let mac_address = droid_file[10..16]
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect::<Vec<String>>()
+        .join(":");
+    let file_id_created = if droid_file[7] >> 4 == 1 {
+        let time_low = read_u32_le(droid_file, 0)? as u64;
+        let time_mid = read_u16_le(droid_file, 4)? as u64;
+        let time_high = (read_u16_le(droid_file, 6)? & 0x0FFF) as u64;
+        let uuid_time = (time_high << 48) | (time_mid << 32) | time_low;
+        // UUID time counts 100ns intervals since 1582-10-15, FILETIME since 1601-01-01
+        uuid_time
+            .checked_sub(0x0014_6BF3_3E42_C000)
+            .and_then(convert_win_time_opt)
+    } else {
+        None
+    };
+    Some(TrackerData {
+        machine_id: read_ascii_string(block.get(16..32)?, 0)?,
+        droid_volume_id: format_guid(block, 32)?,
+        droid_file_id: format_guid(block, 48)?,
+        birth_droid_volume_id: format_guid(block, 64)?,
+        birth_droid_file_id: format_guid(block, 80)?,
+        mac_address,
+        file_id_created,
+    })
+}
+
+fn extra_data_name(signature: u32) -> String {
+    match signature {
+        0xA000_0001 => "EnvironmentVariableDataBlock".to_string(),
+        0xA000_0002 => "ConsoleDataBlock".to_string(),
+        0xA000_0003 => "TrackerDataBlock".to_string(),
+        0xA000_0004 => "ConsoleFEDataBlock".to_string(),
+        0xA000_0005 => "SpecialFolderDataBlock".to_string(),
+        0xA000_0006 => "DarwinDataBlock".to_string(),
+        0xA000_0007 => "IconEnvironmentDataBlock".to_string(),
+        0xA000_0008 => "ShimDataBlock".to_string(),
+        0xA000_0009 => "PropertyStoreDataBlock".to_string(),
+        0xA000_000B => "KnownFolderDataBlock".to_string(),
+        0xA000_000C => "VistaAndAboveIDListDataBlock".to_string(),
+        other => format!("Unknown (0x{other:08X})"),
+    }
+}
+
+fn drive_type(drive_type: u32) -> String {
+    match drive_type {
+        0 => "DRIVE_UNKNOWN",
+        1 => "DRIVE_NO_ROOT_DIR",
+        2 => "DRIVE_REMOVABLE",
+        3 => "DRIVE_FIXED",
+        4 => "DRIVE_REMOTE",
+        5 => "DRIVE_CDROM",
+        6 => "DRIVE_RAMDISK",
+        _ => "Unknown",
+    }
+    .to_string()
+}
+
+fn show_command(show_command: u32) -> String {
+    match show_command {
+        3 => "SW_SHOWMAXIMIZED",
+        7 => "SW_SHOWMINNOACTIVE",
+        _ => "SW_SHOWNORMAL",
+    }
+    .to_string()
+}
+
+fn file_attributes(attributes: u32) -> Vec<String> {
+    [
+        (0x0001, "READONLY"),
+        (0x0002, "HIDDEN"),
+        (0x0004, "SYSTEM"),
+        (0x0010, "DIRECTORY"),
+        (0x0020, "ARCHIVE"),
+        (0x0080, "NORMAL"),
+        (0x0100, "TEMPORARY"),
+        (0x0200, "SPARSE_FILE"),
+        (0x0400, "REPARSE_POINT"),
+        (0x0800, "COMPRESSED"),
+        (0x1000, "OFFLINE"),
+        (0x2000, "NOT_CONTENT_INDEXED"),
+        (0x4000, "ENCRYPTED"),
+    ]
+    .iter()
+    .filter(|(flag, _)| attributes & flag != 0)
+    .map(|(_, name)| name.to_string())
+    .collect()
+}
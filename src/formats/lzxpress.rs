@@ -0,0 +1,75 @@
+// LZXPRESS (plain LZ77) compression as used by ESE databases of Windows 8+ (Windows.edb, SRUDB.dat)
+// format reference: [MS-XCA] 2.4 LZ77 Decompression Algorithm
+// 32 bit flags (highest bit first) tell whether the next element is a literal byte or a 16 bit match (offset and length)
+
+use common::{read_u16_le, read_u32_le};
+
+/// decompresses the data, the output is limited to the expected size, None if the data is damaged
+pub fn decompress(data: &[u8], uncompressed_size: usize) -> Option<Vec<u8>> {
+    let mut output = Vec::with_capacity(uncompressed_size);
+    let mut position = 0;
+    let mut flags: u32 = 0;
+    let mut flag_count = 0;
+    // the nibble of a shared length byte that is still unused
+    let mut last_length_half_byte: Option<usize> = None;
+
+    while output.len() < uncompressed_size {
+        if flag_count == 0 {
+            flags = read_u32_le(data, position)?;
+            position += 4;
+            flag_count = 32;
+        }
+        flag_count -= 1;
+        if flags & (1 << flag_count) == 0 {
+            // literal
+            let Some(byte) = data.get(position) else {
+                break;
+            };
+            output.push(*byte);
+            position += 1;
+            continue;
+        }
+
+        if position >= data.len() {
+            break;
+        }
+        let match_bytes = read_u16_le(data, position)? as usize;
+        position += 2;
+        let match_offset = match_bytes / 8 + 1;
+        let mut match_length = match_bytes % 8;
+        if match_length == 7 {
+            match_length = match last_length_half_byte.take() {
+                Some(half_byte_position) => (data[half_byte_position] / 16) as usize,
+                None => {
+                    let length = (data.get(position)? % 16) as usize;
+                    last_length_half_byte = Some(position);
+                    position += 1;
+                    length
+                }
+            };
+            if match_length == 15 {
+                match_length = *data.get(position)? as usize;
+                position += 1;
+                if match_length == 255 {
+                    match_length = read_u16_le(data, position)? as usize;
+                    position += 2;
+                    if match_length == 0 {
+                        match_length = read_u32_le(data, position)? as usize;
+                        position += 4;
+                    }
+                    match_length = match_length.checked_sub(15 + 7)?;
+                }
+                match_length += 15;
+            }
+            match_length += 7;
+        }
+        match_length += 3;
+
+        let start = output.len().checked_sub(match_offset)?;
+        // the match can overlap the bytes it produces
+        for i in 0..match_length.min(uncompressed_size - output.len()) {
+            output.push(output[start + i]);
+        }
+    }
+    Some(output)
+}
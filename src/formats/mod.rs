@@ -0,0 +1,11 @@
+// Parsers for binary formats that are shared by several artifacts
+pub mod ese;
+pub mod guids;
+pub mod leveldb;
+pub mod lnk;
+pub mod lzxpress;
+pub mod mozlz4;
+pub mod mru;
+pub mod pickle;
+pub mod shell_items;
+pub mod sqlite;
@@ -0,0 +1,27 @@
+// Mozilla LZ4 files (sessionstore.jsonlz4, recovery.jsonlz4, addonStartup.json.lz4, ...)
+// "mozLz40\0" magic, u32 size of the decompressed data, LZ4 block
+
+use std::fs;
+
+use anyhow::anyhow;
+use common::read_u32_le;
+
+const MAGIC: &[u8; 8] = b"mozLz40\0";
+// LZ4 can not compress better than 255:1, larger sizes in the header are damaged
+const MAX_RATIO: usize = 255;
+
+/// reads and decompresses a mozLz4 file
+pub fn read_mozlz4(path: &str) -> anyhow::Result<Vec<u8>> {
+    let data = fs::read(path)?;
+    if !data.starts_with(MAGIC) {
+        return Err(anyhow!("{path} is not a mozLz4 file!"));
+    }
+    let size = read_u32_le(&data, 8).ok_or(anyhow!("mozLz4 header is truncated!"))? as usize;
+    let block = &data[12..];
+    if size > block.len().saturating_mul(MAX_RATIO) {
+        return Err(anyhow!(
+            "{path} claims {size} bytes of decompressed data, the header is damaged!"
+        ));
+    }
+    Ok(lz4_flex::block::decompress(block, size)?)
+}
@@ -0,0 +1,59 @@
+// MRU lists as used by many Explorer keys in the registry
+
+use chrono::{DateTime, Utc};
+use common::convert_win_time;
+use nt_hive::KeyNode;
+
+/// values of an MRU key in their MRU order, numbered (MRUListEx) and lettered (MRUList) keys are handled the same way
+pub struct MruValues {
+    pub timestamp: DateTime<Utc>,
+    pub order: Vec<String>,
+    pub values: Vec<(String, Vec<u8>)>,
+}
+
+/// MRUListEx (REG_BINARY): u32 item numbers, most recent first, terminated by 0xFFFFFFFF
+pub fn parse_mru_list_ex(data: &[u8]) -> Vec<u32> {
+    data.chunks_exact(4)
+        .map(|c| u32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+        .take_while(|n| *n != u32::MAX)
+        .collect()
+}
+
+/// MRUList (REG_SZ): one letter per value name, most recent first
+pub fn parse_mru_list(list: &str) -> Vec<String> {
+    list.chars().map(|c| c.to_string()).collect()
+}
+
+/// position of a value in the MRU order, 0 is the most recently used one
+pub fn mru_position<T: PartialEq>(order: &[T], name: &T) -> Option<usize> {
+    order.iter().position(|n| n == name)
+}
+
+/// reads all MRU values of a key together with their order
+pub fn read_mru_values(key: &KeyNode<&[u8]>) -> anyhow::Result<MruValues> {
+    let mut mru_values = MruValues {
+        timestamp: convert_win_time(key.header().timestamp.get()),
+        order: Vec::new(),
+        values: Vec::new(),
+    };
+    let Some(values) = key.values() else {
+        return Ok(mru_values);
+    };
+    for value in values? {
+        let value = value?;
+        let value_name = value.name()?.to_string();
+        match value_name.as_str() {
+            "MRUListEx" => {
+                mru_values.order = parse_mru_list_ex(&value.data()?.into_vec()?)
+                    .iter()
+                    .map(|n| n.to_string())
+                    .collect()
+            }
+            "MRUList" => mru_values.order = parse_mru_list(&value.string_data()?),
+            _ => mru_values
+                .values
+                .push((value_name, value.data()?.into_vec()?)),
+        }
+    }
+    Ok(mru_values)
+}
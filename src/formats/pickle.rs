@@ -0,0 +1,47 @@
+// Chromium pickles, as used by session files and the File System
+// u32 payload size, then the fields, each starting 4 byte aligned
+// strings are an i32 length followed by UTF-8 bytes, string16 an i32 length in characters followed by UTF-16LE
+
+/// reader for Chromium pickles, every field starts 4 byte aligned
+pub struct Pickle<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Pickle<'a> {
+    /// skips the u32 payload size header
+    pub fn new(data: &'a [u8]) -> Self {
+        Pickle { data, offset: 4 }
+    }
+
+    pub fn bytes(&mut self, length: usize) -> Option<&'a [u8]> {
+        let bytes = self
+            .data
+            .get(self.offset..self.offset.checked_add(length)?)?;
+        self.offset += length.div_ceil(4) * 4;
+        Some(bytes)
+    }
+
+    pub fn int(&mut self) -> Option<i32> {
+        Some(i32::from_le_bytes(self.bytes(4)?.try_into().ok()?))
+    }
+
+    pub fn int64(&mut self) -> Option<i64> {
+        Some(i64::from_le_bytes(self.bytes(8)?.try_into().ok()?))
+    }
+
+    pub fn string(&mut self) -> Option<String> {
+        let length = usize::try_from(self.int()?).ok()?;
+        Some(String::from_utf8_lossy(self.bytes(length)?).to_string())
+    }
+
+    pub fn string16(&mut self) -> Option<String> {
+        let length = usize::try_from(self.int()?).ok()?;
+        let bytes = self.bytes(length.checked_mul(2)?)?;
+        let chars: Vec<u16> = bytes
+            .chunks_exact(2)
+            .map(|c| u16::from_le_bytes([c[0], c[1]]))
+            .collect();
+        Some(String::from_utf16_lossy(&chars))
+    }
+}
@@ -0,0 +1,269 @@
+// Shell items (and lists of them, aka PIDLs) as used in shortcut files, jump lists and shellbags
+// format reference: https://github.com/libyal/libfwsi/blob/main/documentation/Windows%20Shell%20Item%20format.asciidoc
+
+use chrono::NaiveDateTime;
+use common::{
+    convert_dos_time, format_guid, read_ascii_string, read_u16_le, read_u32_le, read_u64_le,
+    read_utf16_string,
+};
+use serde::Serialize;
+
+use crate::formats::guids::shell_folder_name;
+
+/// A single decoded shell item
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct ShellItem {
+    pub item_type: String,
+    pub name: String,
+    pub short_name: Option<String>,
+    pub file_size: Option<u32>,
+    pub modified: Option<NaiveDateTime>,
+    pub created: Option<NaiveDateTime>,
+    pub accessed: Option<NaiveDateTime>,
+    pub mft_entry: Option<u64>,
+    pub mft_sequence: Option<u16>,
+}
+
+/// parses a list of shell items (each prefixed by its size), stops at the terminating empty item
+pub fn parse_id_list(data: &[u8]) -> Vec<ShellItem> {
+    let mut items = Vec::new();
+    let mut offset = 0;
+    while let Some(size) = read_u16_le(data, offset) {
+        let size = size as usize;
+        if size < 2 || offset + size > data.len() {
+            break;
+        }
+        if let Some(item) = parse_shell_item(&data[offset..offset + size]) {
+            items.push(item);
+        }
+        offset += size;
+    }
+    items
+}
+
+/// builds a readable path out of a list of shell items, e.g. "My Computer\C:\Users\ally\Desktop"
+pub fn id_list_path(items: &[ShellItem]) -> String {
+    items
+        .iter()
+        .map(|item| item.name.trim_end_matches('\\'))
+        .collect::<Vec<&str>>()
+        .join("\\")
+}
+
+/// parses a single shell item, `data` has to start with the item size
+pub fn parse_shell_item(data: &[u8]) -> Option<ShellItem> {
+    let class_type = *data.get(2)?;
+    match class_type {
+        0x00 => parse_property_view(data),
+        0x1F => parse_root_folder(data),
+        0x20..=0x2F => parse_volume(data, class_type),
+        0x30..=0x3F => parse_file_entry(data, class_type),
+        0x40..=0x4F => parse_network_location(data),
+        0x52 => parse_zip_content(data),
+        0x61 => parse_uri(data),
+        0x71 => parse_control_panel(data),
+        0x74 => parse_delegate(data),
+        _ => Some(ShellItem {
+            item_type: format!("Unknown (0x{class_type:02X})"),
+            name: format!("<unknown shell item 0x{class_type:02X}>"),
+            ..Default::default()
+        }),
+    }
+}
+
+fn parse_root_folder(data: &[u8]) -> Option<ShellItem> {
+    let guid = format_guid(data, 4)?;
+    let name = shell_folder_name(&guid)
+        .map(|n| n.to_string())
+        .unwrap_or(guid);
+    Some(ShellItem {
+        item_type: "Root Folder".to_string(),
+        name,
+        ..Default::default()
+    })
+}
+
+fn parse_volume(data: &[u8], class_type: u8) -> Option<ShellItem> {
+    // 0x2E items contain a GUID instead of a drive letter (e.g. shell folders on a device)
+    if class_type == 0x2E {
+        let guid = format_guid(data, 4)?;
+        let name = shell_folder_name(&guid)
+            .map(|n| n.to_string())
+            .unwrap_or(guid);
+        return Some(ShellItem {
+            item_type: "Volume".to_string(),
+            name,
+            ..Default::default()
+        });
+    }
+    Some(ShellItem {
+        item_type: "Volume".to_string(),
+        name: read_ascii_string(data, 3)?,
+        ..Default::default()
+    })
+}
+
+fn parse_file_entry(data: &[u8], class_type: u8) -> Option<ShellItem> {
+    let item_type = match class_type & 0x03 {
+        0x01 => "Directory",
+        0x02 => "File",
+        _ => "File Entry",
+    };
+    let file_size = read_u32_le(data, 4);
+    let modified = convert_dos_time(read_u16_le(data, 8)?, read_u16_le(data, 10)?);
+    // the short (8.3) name is unicode if bit 0x04 is set
+    let (short_name, name_end) = if class_type & 0x04 != 0 {
+        let name = read_utf16_string(data, 14)?;
+        let end = 14 + (name.encode_utf16().count() + 1) * 2;
+        (name, end)
+    } else {
+        let name = read_ascii_string(data, 14)?;
+        let length = data[14..].iter().position(|b| *b == 0).unwrap_or(0);
+        // ascii names are padded to an even size
+        let end = 14 + length + 1;
+        (name, end + end % 2)
+    };
+    let mut item = ShellItem {
+        item_type: item_type.to_string(),
+        name: short_name.clone(),
+        short_name: Some(short_name),
+        file_size,
+        modified,
+        ..Default::default()
+    };
+    if let Some(ext) = find_beef0004(data, name_end) {
+        apply_beef0004(&mut item, ext);
+    }
+    Some(item)
+}
+
+fn parse_network_location(data: &[u8]) -> Option<ShellItem> {
+    Some(ShellItem {
+        item_type: "Network Location".to_string(),
+        name: read_ascii_string(data, 5)?,
+        ..Default::default()
+    })
+}
+
+fn parse_uri(data: &[u8]) -> Option<ShellItem> {
+    let flags = *data.get(3)?;
+    let data_size = read_u16_le(data, 4)? as usize;
+    let offset = 6 + data_size;
+    let name = if flags & 0x80 != 0 {
+        read_utf16_string(data, offset)?
+    } else {
+        read_ascii_string(data, offset)?
+    };
+    Some(ShellItem {
+        item_type: "URI".to_string(),
+        name,
+        ..Default::default()
+    })
+}
+
+fn parse_control_panel(data: &[u8]) -> Option<ShellItem> {
+    Some(ShellItem {
+        item_type: "Control Panel Item".to_string(),
+        name: format_guid(data, 14)?,
+        ..Default::default()
+    })
+}
+
+/// class type 0x00 items are "users property views", used e.g. for MTP devices (phones, cameras) and search results
+fn parse_property_view(data: &[u8]) -> Option<ShellItem> {
+    let signature = read_u32_le(data, 6)?;
+    let item_type = match signature {
+        0x1031_2005 => "MTP Device Item",
+        0x1014_1981 => "Games Folder",
+        _ => "Users Property View",
+    };
+    Some(ShellItem {
+        item_type: item_type.to_string(),
+        name: find_utf16_name(data, 10)
+            .or_else(|| format_guid(data, 14).and_then(|g| shell_folder_name(&g).map(String::from)))
+            .unwrap_or(format!("<property view 0x{signature:08X}>")),
+        ..Default::default()
+    })
+}
+
+/// contents of a zip file opened in explorer
+fn parse_zip_content(data: &[u8]) -> Option<ShellItem> {
+    Some(ShellItem {
+        item_type: "Zip Content".to_string(),
+        name: find_utf16_name(data, 0x20).unwrap_or("<zip content>".to_string()),
+        ..Default::default()
+    })
+}
+
+/// delegate items wrap a file entry after the "CFSF" signature
+fn parse_delegate(data: &[u8]) -> Option<ShellItem> {
+    if data.get(4..8)? != b"CFSF" {
+        return Some(ShellItem {
+            item_type: "Delegate".to_string(),
+            name: "<delegate item>".to_string(),
+            ..Default::default()
+        });
+    }
+    // the wrapped file entry starts with its own size and class type
+    let inner = data.get(8..)?;
+    parse_file_entry(inner, *inner.get(2)?)
+}
+
+/// finds the first readable UTF-16 string (at least 2 characters) after offset
+fn find_utf16_name(data: &[u8], offset: usize) -> Option<String> {
+    let mut position = offset;
+    while position + 4 <= data.len() {
+        let name = read_utf16_string(data, position)?;
+        let printable =
+            name.chars().count() >= 2 && name.chars().all(|c| !c.is_control() && c != '\u{FFFD}');
+        if printable {
+            return Some(name);
+        }
+        position += 2;
+    }
+    None
+}
+
+/// looks for the 0xBEEF0004 extension block (file entry extension) after offset
+fn find_beef0004(data: &[u8], offset: usize) -> Option<&[u8]> {
+    let signature = [0x04, 0x00, 0xEF, 0xBE];
+    let position = data
+        .get(offset..)?
+        .windows(4)
+        .position(|w| w == signature)?;
+    // the signature is preceded by the block size and version
+    let start = (offset + position).checked_sub(4)?;
+    let size = read_u16_le(data, start)? as usize;
+    data.get(start..start + size)
+}
+
+/// adds long name, creation/access times and the NTFS file reference from a 0xBEEF0004 block
+fn apply_beef0004(item: &mut ShellItem, ext: &[u8]) {
+    let Some(version) = read_u16_le(ext, 2) else {
+        return;
+    };
+    if let (Some(date), Some(time)) = (read_u16_le(ext, 8), read_u16_le(ext, 10)) {
+        item.created = convert_dos_time(date, time);
+    }
+    if let (Some(date), Some(time)) = (read_u16_le(ext, 12), read_u16_le(ext, 14)) {
+        item.accessed = convert_dos_time(date, time);
+    }
+    if version >= 7 {
+        if let Some(reference) = read_u64_le(ext, 20) {
+            item.mft_entry = Some(reference & 0x0000_FFFF_FFFF_FFFF);
+            item.mft_sequence = Some((reference >> 48) as u16);
+        }
+    }
+    let name_offset = match version {
+        9.. => 46,
+        8 => 42,
+        7 => 38,
+        3..=6 => 20,
+        _ => return,
+    };
+    if let Some(long_name) = read_utf16_string(ext, name_offset) {
+        if !long_name.is_empty() {
+            item.name = long_name;
+        }
+    }
+}
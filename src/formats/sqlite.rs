@@ -0,0 +1,68 @@
+// SQLite databases (Windows.db, ActivitiesCache.db, browser profiles, ...)
+// the database is copied together with its journal (-wal/-journal) first, so the mounted image is never written
+// and uncommitted changes from the write-ahead log are included
+
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use log::error;
+use rusqlite::types::ValueRef;
+use rusqlite::{Connection, OpenFlags, Row};
+
+static COPY_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// opens a copy of the database at path and calls `f` with the connection
+pub fn with_sqlite_copy<T, F>(path: &str, f: F) -> anyhow::Result<T>
+where
+    F: FnOnce(&Connection) -> anyhow::Result<T>,
+{
+    let copy_folder = std::env::temp_dir().join(format!(
+        "truffleyard_{}_{}",
+        std::process::id(),
+        COPY_COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    fs::create_dir_all(&copy_folder)?;
+    let result = copy_and_open(path, &copy_folder).and_then(|connection| f(&connection));
+    if let Err(err) = fs::remove_dir_all(&copy_folder) {
+        error!("Failed to remove {}: {err}", copy_folder.display())
+    }
+    result
+}
+
+fn copy_and_open(path: &str, copy_folder: &Path) -> anyhow::Result<Connection> {
+    let copy = copy_folder.join("database.db");
+    fs::copy(path, &copy)?;
+    for journal in ["-wal", "-journal"] {
+        let journal_path = format!("{path}{journal}");
+        if Path::new(&journal_path).is_file() {
+            fs::copy(
+                &journal_path,
+                copy_folder.join(format!("database.db{journal}")),
+            )?;
+        }
+    }
+    Ok(Connection::open_with_flags(
+        &copy,
+        OpenFlags::SQLITE_OPEN_READ_WRITE | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )?)
+}
+
+/// reads an integer column by name, missing columns (older schema versions) are None
+pub fn integer_column(row: &Row, name: &str) -> Option<i64> {
+    match row.get_ref(name).ok()? {
+        ValueRef::Integer(i) => Some(i),
+        ValueRef::Real(r) => Some(r as i64),
+        ValueRef::Text(t) => String::from_utf8_lossy(t).parse().ok(),
+        _ => None,
+    }
+}
+
+/// reads a text column by name, blobs are read as UTF-8
+pub fn text_column(row: &Row, name: &str) -> Option<String> {
+    match row.get_ref(name).ok()? {
+        ValueRef::Text(t) | ValueRef::Blob(t) => Some(String::from_utf8_lossy(t).to_string()),
+        ValueRef::Integer(i) => Some(i.to_string()),
+        _ => None,
+    }
+}
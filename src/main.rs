@@ -1,14 +1,16 @@
 #![warn(clippy::unwrap_used, clippy::expect_used)]
 
 mod account_usage;
-//mod application_execution;
-//mod browser_activity;
-//mod cloud_storage;
-//mod deleted_items_file_existence;
+mod application_execution;
+mod browser_activity;
+mod cloud_storage;
+mod deleted_items_file_existence;
 mod eventlogs;
 mod external_device_usb_usage;
-//mod file_folder_opening;
-//mod network_activity_physical_location;
+mod file_folder_opening;
+mod files;
+mod formats;
+mod network_activity_physical_location;
 mod registry;
 mod system_information;
 //mod tests;
@@ -24,8 +26,21 @@ use log::error;
 
 use crate::account_usage::eventlogs_only::get_accountusage_eventlog_data;
 use crate::account_usage::registry_only::get_accountusage_registry_data;
+use crate::application_execution::files_only::get_applicationexecution_file_data;
+use crate::application_execution::registry_only::get_applicationexecution_registry_data;
+use crate::browser_activity::files_only::get_browseractivity_file_data;
+use crate::cloud_storage::files_only::get_cloudstorage_file_data;
+use crate::cloud_storage::registry_only::get_cloudstorage_registry_data;
+use crate::deleted_items_file_existence::files_only::get_deleteditems_file_data;
+use crate::deleted_items_file_existence::registry_only::get_deleteditems_registry_data;
 use crate::eventlogs::get_eventlog_data;
+use crate::external_device_usb_usage::files_only::get_externaldevice_file_data;
 use crate::external_device_usb_usage::registry_only::get_externaldevice_registry_data;
+use crate::file_folder_opening::eventlogs_only::get_filefolderopening_eventlog_data;
+use crate::file_folder_opening::files_only::get_filefolderopening_file_data;
+use crate::file_folder_opening::registry_only::get_filefolderopening_registry_data;
+use crate::files::get_file_data;
+use crate::network_activity_physical_location::registry_only::get_networkactivity_registry_data;
 use crate::registry::get_registry_data;
 use crate::system_information::registry_only::get_systeminfo_registry_data;
 //use crate::tests::test::testing;
@@ -56,6 +71,12 @@ struct Cli {
     /// path to file that contains vid&pid  
     #[arg(short)]
     vidpid_path: String,
+    /// path to file that contains jump list AppIDs, default is the built-in list (lists/jumplist_appids.json)
+    #[arg(short)]
+    appid_path: Option<String>,
+    /// export cached bodies of the browser caches to the result-folder
+    #[arg(short = 'c', long)]
+    export_cache: bool,
     /// specifying Subcommands
     #[clap(subcommand)]
     command: Commands,
@@ -66,6 +87,7 @@ struct Cli {
 enum ProcessingMode {
     RegistryOnly,
     EventLogOnly,
+    FilesOnly,
     All,
 }
 // implementing Display for Processing Modes, so it shows up in CLI
@@ -74,6 +96,7 @@ impl Display for ProcessingMode {
         match self {
             ProcessingMode::RegistryOnly => write!(f, "registry-only"),
             ProcessingMode::EventLogOnly => write!(f, "eventlog-only"),
+            ProcessingMode::FilesOnly => write!(f, "files-only"),
             ProcessingMode::All => write!(f, "all"),
         }
     }
@@ -88,6 +111,8 @@ enum Commands {
     Registry,
     /// Analyzes only EventLog artifacts (that are implemented so far)
     EventLogs,
+    /// Analyzes only File artifacts (that are implemented so far)
+    Files,
     /// Analyzes Account Usage artifacts
     AccountUsage {
         #[arg(short, default_value_t = ProcessingMode::All)]
@@ -103,8 +128,36 @@ enum Commands {
         #[arg(short, default_value_t = ProcessingMode::All)]
         mode: ProcessingMode,
     },
-    // to be implemented:
-    /* ApplicationExecution, BrowserActivity, CloudStorage, DeletedItems, FileFolderOpening, NetworkActivity*/
+    /// Analyzes File and Folder Opening artifacts
+    FileFolderOpening {
+        #[arg(short, default_value_t = ProcessingMode::All)]
+        mode: ProcessingMode,
+    },
+    /// Analyzes Application Execution artifacts
+    ApplicationExecution {
+        #[arg(short, default_value_t = ProcessingMode::All)]
+        mode: ProcessingMode,
+    },
+    /// Analyzes Deleted Items and File Existence artifacts
+    DeletedItems {
+        #[arg(short, default_value_t = ProcessingMode::All)]
+        mode: ProcessingMode,
+    },
+    /// Analyzes Browser Activity artifacts
+    BrowserActivity {
+        #[arg(short, default_value_t = ProcessingMode::All)]
+        mode: ProcessingMode,
+    },
+    /// Analyzes Cloud Storage artifacts
+    CloudStorage {
+        #[arg(short, default_value_t = ProcessingMode::All)]
+        mode: ProcessingMode,
+    },
+    /// Analyzes Network Activity and Physical Location artifacts
+    NetworkActivity {
+        #[arg(short, default_value_t = ProcessingMode::All)]
+        mode: ProcessingMode,
+    },
 }
 
 fn main() -> Result<()> {
@@ -121,6 +174,14 @@ fn main() -> Result<()> {
             if let Err(err) = get_registry_data(&cli.image_path, &out_put_path, &cli.vidpid_path) {
                 error!("Failed to get Registry Data: {err}")
             }
+            if let Err(err) = get_file_data(
+                &cli.image_path,
+                &out_put_path,
+                cli.appid_path.as_deref(),
+                cli.export_cache,
+            ) {
+                error!("Failed to get File Data: {err}")
+            }
             println!("All done!");
             println!(
                 "Elapsed time from very beginning (hitting enter): {:.2?}",
@@ -168,6 +229,29 @@ fn main() -> Result<()> {
             );
             Ok(())
         }
+        Commands::Files => {
+            let path = format!("{}/{}", cli.output_path, cli.folder_name);
+            let out_put_path = make_path(path).context("Failed to create directory!")?;
+            let before2 = Instant::now();
+            if let Err(err) = get_file_data(
+                &cli.image_path,
+                &out_put_path,
+                cli.appid_path.as_deref(),
+                cli.export_cache,
+            ) {
+                error!("Failed to get File Data: {err}")
+            }
+            println!("All done!");
+            println!(
+                "Elapsed time from very beginning (hitting enter): {:.2?}",
+                before.elapsed()
+            );
+            println!(
+                "Elapsed time from actual start of first extracting command: {:.2?}",
+                before2.elapsed()
+            );
+            Ok(())
+        }
         Commands::AccountUsage { mode } => match mode {
             ProcessingMode::RegistryOnly => {
                 let path = format!("{}/{}", cli.output_path, cli.folder_name);
@@ -205,6 +289,10 @@ fn main() -> Result<()> {
                 );
                 Ok(())
             }
+            ProcessingMode::FilesOnly => {
+                println!("Sorry, not implemented yet!");
+                Ok(())
+            }
             ProcessingMode::All => {
                 let path = format!("{}/{}", cli.output_path, cli.folder_name);
                 let out_put_path = make_path(path).context("Failed to create directory!")?;
@@ -254,8 +342,26 @@ fn main() -> Result<()> {
                 println!("Sorry, not implemented yet!");
                 Ok(())
             }
+            ProcessingMode::FilesOnly => {
+                let path = format!("{}/{}", cli.output_path, cli.folder_name);
+                let out_put_path = make_path(path).context("Failed to create directory!")?;
+                let before2 = Instant::now();
+                if let Err(err) = get_externaldevice_file_data(&cli.image_path, &out_put_path) {
+                    error!("Failed to get File Data for External Devices: {err}")
+                }
+                println!("All done!");
+                println!(
+                    "Elapsed time from very beginning (hitting enter): {:.2?}",
+                    before.elapsed()
+                );
+                println!(
+                    "Elapsed time from actual start of first extracting command: {:.2?}",
+                    before2.elapsed()
+                );
+                Ok(())
+            }
             ProcessingMode::All => {
-                println!("EventLogs are not implemented yet, will continue with Registry Only!");
+                println!("EventLogs are not implemented yet, will continue with Registry and Files Only!");
                 let path = format!("{}/{}", cli.output_path, cli.folder_name);
                 let out_put_path = make_path(path).context("Failed to create directory: {err}")?;
                 let before2 = Instant::now();
@@ -266,6 +372,9 @@ fn main() -> Result<()> {
                 ) {
                     error!("Failed to get Registry Data for External Devices: {err}")
                 }
+                if let Err(err) = get_externaldevice_file_data(&cli.image_path, &out_put_path) {
+                    error!("Failed to get File Data for External Devices: {err}")
+                }
                 println!("All done!");
                 println!(
                     "Elapsed time from very beginning (hitting enter): {:.2?}",
@@ -297,7 +406,7 @@ fn main() -> Result<()> {
                 );
                 Ok(())
             }
-            ProcessingMode::EventLogOnly => {
+            ProcessingMode::EventLogOnly | ProcessingMode::FilesOnly => {
                 println!("Sorry, not implemented yet!");
                 Ok(())
             }
@@ -321,5 +430,374 @@ fn main() -> Result<()> {
                 Ok(())
             }
         },
+        Commands::FileFolderOpening { mode } => match mode {
+            ProcessingMode::RegistryOnly => {
+                let path = format!("{}/{}", cli.output_path, cli.folder_name);
+                let out_put_path = make_path(path).context("Failed to create directory!")?;
+                let before2 = Instant::now();
+                if let Err(err) =
+                    get_filefolderopening_registry_data(&cli.image_path, &out_put_path)
+                {
+                    error!("Failed to get Registry Data for File and Folder Opening: {err}")
+                }
+                println!("All done!");
+                println!(
+                    "Elapsed time from very beginning (hitting enter): {:.2?}",
+                    before.elapsed()
+                );
+                println!(
+                    "Elapsed time from actual start of first extracting command: {:.2?}",
+                    before2.elapsed()
+                );
+                Ok(())
+            }
+            ProcessingMode::EventLogOnly => {
+                let path = format!("{}/{}", cli.output_path, cli.folder_name);
+                let out_put_path = make_path(path).context("Failed to create directory!")?;
+                let before2 = Instant::now();
+                if let Err(err) =
+                    get_filefolderopening_eventlog_data(&cli.image_path, &out_put_path)
+                {
+                    error!("Failed to get EventLog Data for File and Folder Opening: {err}")
+                }
+                println!("All done!");
+                println!(
+                    "Elapsed time from very beginning (hitting enter): {:.2?}",
+                    before.elapsed()
+                );
+                println!(
+                    "Elapsed time from actual start of first extracting command: {:.2?}",
+                    before2.elapsed()
+                );
+                Ok(())
+            }
+            ProcessingMode::FilesOnly => {
+                let path = format!("{}/{}", cli.output_path, cli.folder_name);
+                let out_put_path = make_path(path).context("Failed to create directory!")?;
+                let before2 = Instant::now();
+                if let Err(err) = get_filefolderopening_file_data(
+                    &cli.image_path,
+                    &out_put_path,
+                    cli.appid_path.as_deref(),
+                ) {
+                    error!("Failed to get File Data for File and Folder Opening: {err}")
+                }
+                println!("All done!");
+                println!(
+                    "Elapsed time from very beginning (hitting enter): {:.2?}",
+                    before.elapsed()
+                );
+                println!(
+                    "Elapsed time from actual start of first extracting command: {:.2?}",
+                    before2.elapsed()
+                );
+                Ok(())
+            }
+            ProcessingMode::All => {
+                let path = format!("{}/{}", cli.output_path, cli.folder_name);
+                let out_put_path = make_path(path).context("Failed to create directory!")?;
+                let before2 = Instant::now();
+                if let Err(err) =
+                    get_filefolderopening_eventlog_data(&cli.image_path, &out_put_path)
+                {
+                    error!("Failed to get EventLog Data for File and Folder Opening: {err}")
+                }
+                if let Err(err) =
+                    get_filefolderopening_registry_data(&cli.image_path, &out_put_path)
+                {
+                    error!("Failed to get Registry Data for File and Folder Opening: {err}")
+                }
+                if let Err(err) = get_filefolderopening_file_data(
+                    &cli.image_path,
+                    &out_put_path,
+                    cli.appid_path.as_deref(),
+                ) {
+                    error!("Failed to get File Data for File and Folder Opening: {err}")
+                }
+                println!("All done!");
+                println!(
+                    "Elapsed time from very beginning (hitting enter): {:.2?}",
+                    before.elapsed()
+                );
+                println!(
+                    "Elapsed time from actual start of first extracting command: {:.2?}",
+                    before2.elapsed()
+                );
+                Ok(())
+            }
+        },
+        Commands::ApplicationExecution { mode } => match mode {
+            ProcessingMode::RegistryOnly => {
+                let path = format!("{}/{}", cli.output_path, cli.folder_name);
+                let out_put_path = make_path(path).context("Failed to create directory!")?;
+                let before2 = Instant::now();
+                if let Err(err) =
+                    get_applicationexecution_registry_data(&cli.image_path, &out_put_path)
+                {
+                    error!("Failed to get Registry Data for Application Execution: {err}")
+                }
+                println!("All done!");
+                println!(
+                    "Elapsed time from very beginning (hitting enter): {:.2?}",
+                    before.elapsed()
+                );
+                println!(
+                    "Elapsed time from actual start of first extracting command: {:.2?}",
+                    before2.elapsed()
+                );
+                Ok(())
+            }
+            ProcessingMode::EventLogOnly => {
+                println!("Sorry, not implemented yet!");
+                Ok(())
+            }
+            ProcessingMode::FilesOnly => {
+                let path = format!("{}/{}", cli.output_path, cli.folder_name);
+                let out_put_path = make_path(path).context("Failed to create directory!")?;
+                let before2 = Instant::now();
+                if let Err(err) = get_applicationexecution_file_data(&cli.image_path, &out_put_path)
+                {
+                    error!("Failed to get File Data for Application Execution: {err}")
+                }
+                println!("All done!");
+                println!(
+                    "Elapsed time from very beginning (hitting enter): {:.2?}",
+                    before.elapsed()
+                );
+                println!(
+                    "Elapsed time from actual start of first extracting command: {:.2?}",
+                    before2.elapsed()
+                );
+                Ok(())
+            }
+            ProcessingMode::All => {
+                println!("EventLogs are not implemented yet, will continue with Registry and Files Only!");
+                let path = format!("{}/{}", cli.output_path, cli.folder_name);
+                let out_put_path = make_path(path).context("Failed to create directory!")?;
+                let before2 = Instant::now();
+                if let Err(err) =
+                    get_applicationexecution_registry_data(&cli.image_path, &out_put_path)
+                {
+                    error!("Failed to get Registry Data for Application Execution: {err}")
+                }
+                if let Err(err) = get_applicationexecution_file_data(&cli.image_path, &out_put_path)
+                {
+                    error!("Failed to get File Data for Application Execution: {err}")
+                }
+                println!("All done!");
+                println!(
+                    "Elapsed time from very beginning (hitting enter): {:.2?}",
+                    before.elapsed()
+                );
+                println!(
+                    "Elapsed time from actual start of first extracting command: {:.2?}",
+                    before2.elapsed()
+                );
+                Ok(())
+            }
+        },
+        Commands::DeletedItems { mode } => match mode {
+            ProcessingMode::RegistryOnly => {
+                let path = format!("{}/{}", cli.output_path, cli.folder_name);
+                let out_put_path = make_path(path).context("Failed to create directory!")?;
+                let before2 = Instant::now();
+                if let Err(err) = get_deleteditems_registry_data(&cli.image_path, &out_put_path) {
+                    error!(
+                        "Failed to get Registry Data for Deleted Items and File Existence: {err}"
+                    )
+                }
+                println!("All done!");
+                println!(
+                    "Elapsed time from very beginning (hitting enter): {:.2?}",
+                    before.elapsed()
+                );
+                println!(
+                    "Elapsed time from actual start of first extracting command: {:.2?}",
+                    before2.elapsed()
+                );
+                Ok(())
+            }
+            ProcessingMode::EventLogOnly => {
+                println!("Sorry, not implemented yet!");
+                Ok(())
+            }
+            ProcessingMode::FilesOnly => {
+                let path = format!("{}/{}", cli.output_path, cli.folder_name);
+                let out_put_path = make_path(path).context("Failed to create directory!")?;
+                let before2 = Instant::now();
+                if let Err(err) = get_deleteditems_file_data(&cli.image_path, &out_put_path) {
+                    error!("Failed to get File Data for Deleted Items and File Existence: {err}")
+                }
+                println!("All done!");
+                println!(
+                    "Elapsed time from very beginning (hitting enter): {:.2?}",
+                    before.elapsed()
+                );
+                println!(
+                    "Elapsed time from actual start of first extracting command: {:.2?}",
+                    before2.elapsed()
+                );
+                Ok(())
+            }
+            ProcessingMode::All => {
+                println!("EventLogs are not implemented yet, will continue with Registry and Files Only!");
+                let path = format!("{}/{}", cli.output_path, cli.folder_name);
+                let out_put_path = make_path(path).context("Failed to create directory!")?;
+                let before2 = Instant::now();
+                if let Err(err) = get_deleteditems_registry_data(&cli.image_path, &out_put_path) {
+                    error!(
+                        "Failed to get Registry Data for Deleted Items and File Existence: {err}"
+                    )
+                }
+                if let Err(err) = get_deleteditems_file_data(&cli.image_path, &out_put_path) {
+                    error!("Failed to get File Data for Deleted Items and File Existence: {err}")
+                }
+                println!("All done!");
+                println!(
+                    "Elapsed time from very beginning (hitting enter): {:.2?}",
+                    before.elapsed()
+                );
+                println!(
+                    "Elapsed time from actual start of first extracting command: {:.2?}",
+                    before2.elapsed()
+                );
+                Ok(())
+            }
+        },
+        Commands::BrowserActivity { mode } => match mode {
+            ProcessingMode::EventLogOnly | ProcessingMode::RegistryOnly => {
+                println!("Sorry, not implemented yet!");
+                Ok(())
+            }
+            ProcessingMode::FilesOnly | ProcessingMode::All => {
+                let path = format!("{}/{}", cli.output_path, cli.folder_name);
+                let out_put_path = make_path(path).context("Failed to create directory!")?;
+                let before2 = Instant::now();
+                if let Err(err) =
+                    get_browseractivity_file_data(&cli.image_path, &out_put_path, cli.export_cache)
+                {
+                    error!("Failed to get File Data for Browser Activity: {err}")
+                }
+                println!("All done!");
+                println!(
+                    "Elapsed time from very beginning (hitting enter): {:.2?}",
+                    before.elapsed()
+                );
+                println!(
+                    "Elapsed time from actual start of first extracting command: {:.2?}",
+                    before2.elapsed()
+                );
+                Ok(())
+            }
+        },
+        Commands::CloudStorage { mode } => match mode {
+            ProcessingMode::RegistryOnly => {
+                let path = format!("{}/{}", cli.output_path, cli.folder_name);
+                let out_put_path = make_path(path).context("Failed to create directory!")?;
+                let before2 = Instant::now();
+                if let Err(err) = get_cloudstorage_registry_data(&cli.image_path, &out_put_path) {
+                    error!("Failed to get Registry Data for Cloud Storage: {err}")
+                }
+                println!("All done!");
+                println!(
+                    "Elapsed time from very beginning (hitting enter): {:.2?}",
+                    before.elapsed()
+                );
+                println!(
+                    "Elapsed time from actual start of first extracting command: {:.2?}",
+                    before2.elapsed()
+                );
+                Ok(())
+            }
+            ProcessingMode::EventLogOnly => {
+                println!("Sorry, not implemented yet!");
+                Ok(())
+            }
+            ProcessingMode::FilesOnly => {
+                let path = format!("{}/{}", cli.output_path, cli.folder_name);
+                let out_put_path = make_path(path).context("Failed to create directory!")?;
+                let before2 = Instant::now();
+                if let Err(err) = get_cloudstorage_file_data(&cli.image_path, &out_put_path) {
+                    error!("Failed to get File Data for Cloud Storage: {err}")
+                }
+                println!("All done!");
+                println!(
+                    "Elapsed time from very beginning (hitting enter): {:.2?}",
+                    before.elapsed()
+                );
+                println!(
+                    "Elapsed time from actual start of first extracting command: {:.2?}",
+                    before2.elapsed()
+                );
+                Ok(())
+            }
+            ProcessingMode::All => {
+                println!("EventLogs are not implemented yet, will continue with Registry and Files Only!");
+                let path = format!("{}/{}", cli.output_path, cli.folder_name);
+                let out_put_path = make_path(path).context("Failed to create directory!")?;
+                let before2 = Instant::now();
+                if let Err(err) = get_cloudstorage_registry_data(&cli.image_path, &out_put_path) {
+                    error!("Failed to get Registry Data for Cloud Storage: {err}")
+                }
+                if let Err(err) = get_cloudstorage_file_data(&cli.image_path, &out_put_path) {
+                    error!("Failed to get File Data for Cloud Storage: {err}")
+                }
+                println!("All done!");
+                println!(
+                    "Elapsed time from very beginning (hitting enter): {:.2?}",
+                    before.elapsed()
+                );
+                println!(
+                    "Elapsed time from actual start of first extracting command: {:.2?}",
+                    before2.elapsed()
+                );
+                Ok(())
+            }
+        },
+        Commands::NetworkActivity { mode } => match mode {
+            ProcessingMode::RegistryOnly => {
+                let path = format!("{}/{}", cli.output_path, cli.folder_name);
+                let out_put_path = make_path(path).context("Failed to create directory!")?;
+                let before2 = Instant::now();
+                if let Err(err) = get_networkactivity_registry_data(&cli.image_path, &out_put_path)
+                {
+                    error!("Failed to get Registry Data for Network Activity and Physical Location: {err}")
+                }
+                println!("All done!");
+                println!(
+                    "Elapsed time from very beginning (hitting enter): {:.2?}",
+                    before.elapsed()
+                );
+                println!(
+                    "Elapsed time from actual start of first extracting command: {:.2?}",
+                    before2.elapsed()
+                );
+                Ok(())
+            }
+            ProcessingMode::EventLogOnly | ProcessingMode::FilesOnly => {
+                println!("Sorry, not implemented yet!");
+                Ok(())
+            }
+            ProcessingMode::All => {
+                println!("EventLogs are not implemented yet, will continue with Registry Only!");
+                let path = format!("{}/{}", cli.output_path, cli.folder_name);
+                let out_put_path = make_path(path).context("Failed to create directory!")?;
+                let before2 = Instant::now();
+                if let Err(err) = get_networkactivity_registry_data(&cli.image_path, &out_put_path)
+                {
+                    error!("Failed to get Registry Data for Network Activity and Physical Location: {err}")
+                }
+                println!("All done!");
+                println!(
+                    "Elapsed time from very beginning (hitting enter): {:.2?}",
+                    before.elapsed()
+                );
+                println!(
+                    "Elapsed time from actual start of first extracting command: {:.2?}",
+                    before2.elapsed()
+                );
+                Ok(())
+            }
+        },
     }
 }
@@ -0,0 +1,169 @@
+// SOFTWARE hive
+// "Identify networks to which the computer connected. Networks can be intranets or wireless networks, the MAC address of the gateway can be used to locate the network." - SANS Windows Forensic Analysis Poster, Network History
+// SOFTWARE\Microsoft\Windows NT\CurrentVersion\NetworkList\Profiles\<ProfileGuid>: ProfileName (SSID), Description, Category,
+// NameType, DateCreated and DateLastConnected (16 byte SYSTEMTIME in local time)
+// SOFTWARE\Microsoft\Windows NT\CurrentVersion\NetworkList\Signatures\<Managed|Unmanaged>\<signature>: ProfileGuid, DnsSuffix,
+// FirstNetwork and DefaultGatewayMac, joined with the profile on the ProfileGuid
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufWriter, Read, Write};
+
+use anyhow::anyhow;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use common::{convert_systemtime, convert_win_time, lookup, string_value};
+use nt_hive::Hive;
+use serde::Serialize;
+
+const NETWORK_LIST: &str = "Microsoft\\Windows NT\\CurrentVersion\\NetworkList";
+const SIGNATURE_TYPES: [&str; 2] = ["Managed", "Unmanaged"];
+const CATEGORIES: [&str; 3] = ["Public", "Private", "Domain"];
+
+#[derive(Debug, Serialize)]
+struct NetworkHistoryEntry {
+    profile_guid: String,
+    profile_name: Option<String>,
+    description: Option<String>,
+    category: Option<String>,
+    name_type: Option<String>,
+    // local time of the system
+    first_connected: Option<NaiveDateTime>,
+    last_connected: Option<NaiveDateTime>,
+    signature_type: Option<String>,
+    dns_suffix: Option<String>,
+    first_network: Option<String>,
+    gateway_mac: Option<String>,
+    profile_last_written: DateTime<Utc>,
+}
+
+/// the values of a signature that are joined to its profile
+struct Signature {
+    signature_type: String,
+    dns_suffix: Option<String>,
+    first_network: Option<String>,
+    gateway_mac: Option<String>,
+}
+
+pub fn get_network_history_data(reg_file: &str, outpath: &str) -> anyhow::Result<()> {
+    print!("Working on Network History: ");
+    let mut buffer = Vec::new();
+    File::open(reg_file)?.read_to_end(&mut buffer)?;
+
+    let hive = Hive::without_validation(buffer.as_ref())?;
+    let root_key_node = hive.root_key_node()?;
+    let network_list_key = root_key_node
+        .subpath(NETWORK_LIST)
+        .ok_or(anyhow!("Key '{NETWORK_LIST}' can not be found!"))??;
+
+    // signatures by profile guid, a profile can have several signatures
+    let mut signatures: HashMap<String, Vec<Signature>> = HashMap::new();
+    for signature_type in SIGNATURE_TYPES {
+        let Some(Ok(type_key)) = network_list_key.subpath(&format!("Signatures\\{signature_type}"))
+        else {
+            continue;
+        };
+        let Some(signature_keys) = type_key.subkeys() else {
+            continue;
+        };
+        for signature_key in signature_keys? {
+            let signature_key = signature_key?;
+            let Some(profile_guid) = string_value(&signature_key, "ProfileGuid") else {
+                continue;
+            };
+            let gateway_mac = signature_key
+                .value("DefaultGatewayMac")
+                .and_then(|v| v.ok())
+                .and_then(|v| v.data().ok())
+                .and_then(|d| d.into_vec().ok())
+                .filter(|mac| !mac.is_empty())
+                .map(|mac| {
+                    mac.iter()
+                        .map(|byte| format!("{byte:02X}"))
+                        .collect::<Vec<String>>()
+                        .join(":")
+                });
+            signatures
+                .entry(profile_guid.to_uppercase())
+                .or_default()
+                .push(Signature {
+                    signature_type: signature_type.to_string(),
+                    dns_suffix: string_value(&signature_key, "DnsSuffix"),
+                    first_network: string_value(&signature_key, "FirstNetwork"),
+                    gateway_mac,
+                });
+        }
+    }
+
+    let mut network_list: Vec<NetworkHistoryEntry> = Vec::new();
+    let profile_keys = network_list_key
+        .subkey("Profiles")
+        .ok_or(anyhow!("Key '{NETWORK_LIST}\\Profiles' can not be found!"))??
+        .subkeys()
+        .ok_or(anyhow!("Subkeys can not be unwrapped!"))??;
+    for profile_key in profile_keys {
+        let profile_key = profile_key?;
+        let profile_guid = profile_key.name()?.to_string();
+        let time = |name: &str| {
+            profile_key
+                .value(name)
+                .and_then(|v| v.ok())
+                .and_then(|v| v.data().ok())
+                .and_then(|d| d.into_vec().ok())
+                .and_then(|data| convert_systemtime(&data))
+        };
+        let dword = |name: &str| {
+            profile_key
+                .value(name)
+                .and_then(|v| v.ok())
+                .and_then(|v| v.dword_data().ok())
+        };
+        let profile_signatures = signatures
+            .remove(&profile_guid.to_uppercase())
+            .unwrap_or_default();
+        // one entry per signature, profiles without signature are kept
+        let profile_signatures = if profile_signatures.is_empty() {
+            vec![None]
+        } else {
+            profile_signatures.into_iter().map(Some).collect()
+        };
+        for signature in profile_signatures {
+            network_list.push(NetworkHistoryEntry {
+                profile_guid: profile_guid.clone(),
+                profile_name: string_value(&profile_key, "ProfileName"),
+                description: string_value(&profile_key, "Description"),
+                category: dword("Category").map(|c| lookup(&CATEGORIES, i64::from(c))),
+                name_type: dword("NameType").map(name_type),
+                first_connected: time("DateCreated"),
+                last_connected: time("DateLastConnected"),
+                signature_type: signature.as_ref().map(|s| s.signature_type.clone()),
+                dns_suffix: signature.as_ref().and_then(|s| s.dns_suffix.clone()),
+                first_network: signature.as_ref().and_then(|s| s.first_network.clone()),
+                gateway_mac: signature.and_then(|s| s.gateway_mac),
+                profile_last_written: convert_win_time(profile_key.header().timestamp.get()),
+            });
+        }
+    }
+
+    if network_list.is_empty() {
+        println!("Nothing to do here, continuing with next job.");
+        return Ok(());
+    }
+
+    let file = File::create(format!("{outpath}/reg_network_history.json"))?;
+    let mut writer = BufWriter::new(file);
+    serde_json::to_writer(&mut writer, &network_list)?;
+    writer.flush()?;
+
+    println!("Done here!");
+    Ok(())
+}
+
+/// IANA interface type of the network, unknown types are kept as hex number
+fn name_type(name_type: u32) -> String {
+    match name_type {
+        0x06 => "Wired".to_string(),
+        0x17 | 0xF3 => "Mobile Broadband".to_string(),
+        0x47 => "Wireless".to_string(),
+        _ => format!("{name_type:#x}"),
+    }
+}
@@ -0,0 +1,26 @@
+// RegistryOnly Processing Mode for Network Activity and Physical Location
+use anyhow::anyhow;
+use common::find_software_hive;
+use log::error;
+
+use crate::network_activity_physical_location::registry::network_history::get_network_history_data;
+
+pub fn get_networkactivity_registry_data(input: &str, outpath: &str) -> anyhow::Result<()> {
+    let mut found_something = false;
+    // SOFTWARE hive
+    match find_software_hive(input) {
+        Ok(path) => {
+            found_something = true;
+            if let Err(err) = get_network_history_data(&path, outpath) {
+                error!("Failed to get Network History: {err}")
+            }
+        }
+        Err(err) => {
+            error!("Could not find Software hive: {err}")
+        }
+    }
+    if !found_something {
+        return Err(anyhow!("No Software hive found!"));
+    }
+    Ok(())
+}
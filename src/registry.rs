@@ -5,7 +5,16 @@ use anyhow::anyhow;
 use common::{find_software_hive, find_system_hive};
 use log::error;
 
-use crate::account_usage::registry::user_accounts::get_profile_list;
+use crate::account_usage::registry::user_accounts::{
+    find_user_hives, get_user_accounts_data, USRCLASS_DAT,
+};
+use crate::application_execution::registry::capability_access::get_capability_access_data;
+use crate::application_execution::registry::feature_usage::get_featureusage_data;
+use crate::application_execution::registry::run_mru::get_runmru_data;
+use crate::application_execution::registry::user_assist::get_userassist_data;
+use crate::cloud_storage::registry::onedrive_accounts::get_onedrive_accounts_data;
+use crate::deleted_items_file_existence::registry::typed_paths::get_typedpaths_data;
+use crate::deleted_items_file_existence::registry::word_wheel_query::get_wordwheelquery_data;
 use crate::external_device_usb_usage::registry::sof_volinfcache::sof_get_vic_data;
 use crate::external_device_usb_usage::registry::sof_volname::sof_get_device_data;
 use crate::external_device_usb_usage::registry::sys_hid::sys_get_hid_data;
@@ -13,6 +22,15 @@ use crate::external_device_usb_usage::registry::sys_mounteddev::sys_get_mountedd
 use crate::external_device_usb_usage::registry::sys_scsi::sys_get_scsi_data;
 use crate::external_device_usb_usage::registry::sys_usb::sys_get_usb_data;
 use crate::external_device_usb_usage::registry::sys_usbstor::sys_get_usbstor_data;
+use crate::file_folder_opening::registry::comdlg32::{
+    get_lastvisited_mru_data, get_opensave_mru_data,
+};
+use crate::file_folder_opening::registry::office::{
+    get_office_mru_data, get_office_trustrecords_data, get_word_reading_locations_data,
+};
+use crate::file_folder_opening::registry::recent_docs::get_recentdocs_data;
+use crate::file_folder_opening::registry::shellbags::get_shellbags_data;
+use crate::network_activity_physical_location::registry::network_history::get_network_history_data;
 use crate::system_information::registry::computer_name::get_computer_name;
 use crate::system_information::registry::current_version::get_current_os_version;
 use crate::system_information::registry::operating_system_version::get_os_updates;
@@ -24,10 +42,6 @@ pub fn get_registry_data(input: &str, outpath: &str, vidpid: &str) -> anyhow::Re
     match find_software_hive(input) {
         Ok(path) => {
             found_something = true;
-            // account usage
-            if let Err(err) = get_profile_list(&path, outpath) {
-                error!("Failed to get Profile List Data: {err}")
-            }
             // usb devices
             if let Err(err) = sof_get_vic_data(&path, outpath) {
                 error!("Failed to get Volume Info Cache: {err}")
@@ -38,6 +52,76 @@ pub fn get_registry_data(input: &str, outpath: &str, vidpid: &str) -> anyhow::Re
             if let Err(err) = get_current_os_version(&path, outpath) {
                 error!("Failed to get Current OS Version: {err}")
             }
+            // user hives of all profiles
+            let ntuser_hives = match find_user_hives(input, &path, "NTUSER.DAT") {
+                Ok(ntuser_hives) => ntuser_hives,
+                Err(err) => {
+                    error!("Could not find NTUSER.DAT hives: {err}");
+                    Vec::new()
+                }
+            };
+            let usrclass_hives = match find_user_hives(input, &path, USRCLASS_DAT) {
+                Ok(usrclass_hives) => usrclass_hives,
+                Err(err) => {
+                    error!("Could not find UsrClass.dat hives: {err}");
+                    Vec::new()
+                }
+            };
+            // account usage
+            if let Err(err) = get_user_accounts_data(input, &path, &ntuser_hives, outpath) {
+                error!("Failed to get User Accounts: {err}")
+            }
+            // application execution
+            if let Err(err) = get_userassist_data(&ntuser_hives, outpath) {
+                error!("Failed to get UserAssist Data: {err}")
+            }
+            if let Err(err) = get_runmru_data(&ntuser_hives, outpath) {
+                error!("Failed to get RunMRU: {err}")
+            }
+            if let Err(err) = get_featureusage_data(&ntuser_hives, outpath) {
+                error!("Failed to get Taskbar FeatureUsage: {err}")
+            }
+            if let Err(err) = get_capability_access_data(&path, &ntuser_hives, outpath) {
+                error!("Failed to get CapabilityAccessManager: {err}")
+            }
+            // file and folder opening
+            let all_user_hives = [ntuser_hives.as_slice(), usrclass_hives.as_slice()].concat();
+            if let Err(err) = get_shellbags_data(&all_user_hives, outpath) {
+                error!("Failed to get Shellbags: {err}")
+            }
+            if let Err(err) = get_recentdocs_data(&ntuser_hives, outpath) {
+                error!("Failed to get RecentDocs: {err}")
+            }
+            if let Err(err) = get_opensave_mru_data(&ntuser_hives, outpath) {
+                error!("Failed to get Open/Save MRU: {err}")
+            }
+            if let Err(err) = get_lastvisited_mru_data(&ntuser_hives, outpath) {
+                error!("Failed to get Last Visited MRU: {err}")
+            }
+            if let Err(err) = get_office_mru_data(&ntuser_hives, outpath) {
+                error!("Failed to get Office Recent Files: {err}")
+            }
+            if let Err(err) = get_office_trustrecords_data(&ntuser_hives, outpath) {
+                error!("Failed to get Office Trust Records: {err}")
+            }
+            if let Err(err) = get_word_reading_locations_data(&ntuser_hives, outpath) {
+                error!("Failed to get Word Reading Locations: {err}")
+            }
+            // deleted items and file existence
+            if let Err(err) = get_typedpaths_data(&ntuser_hives, outpath) {
+                error!("Failed to get TypedPaths: {err}")
+            }
+            if let Err(err) = get_wordwheelquery_data(&ntuser_hives, outpath) {
+                error!("Failed to get WordWheelQuery: {err}")
+            }
+            // network activity and physical location
+            if let Err(err) = get_network_history_data(&path, outpath) {
+                error!("Failed to get Network History: {err}")
+            }
+            // cloud storage
+            if let Err(err) = get_onedrive_accounts_data(&ntuser_hives, outpath) {
+                error!("Failed to get OneDrive Accounts: {err}")
+            }
         }
         Err(err) => {
             error!("Could not find Software hive: {err}")